@@ -1,13 +1,15 @@
+#[allow(dead_code)]
 mod scenarios;
 
-use bunner_qs_rs::parsing::parse;
+use bunner_qs_rs::parsing::{parse, with_parsed};
 use bunner_qs_rs::stringify::stringify;
 use criterion::{Criterion, criterion_group, criterion_main};
 use serde_json::Value;
 use std::hint::black_box;
 
 use scenarios::{
-    Scenario, max_bracket_depth, scenario_extreme, scenario_high, scenario_medium, scenario_simple,
+    BorrowedForm, OwnedForm, Scenario, max_bracket_depth, scenario_extreme, scenario_form,
+    scenario_high, scenario_medium, scenario_simple,
 };
 
 fn bench_parse_simple(c: &mut Criterion) {
@@ -25,6 +27,48 @@ fn bench_parse_high(c: &mut Criterion) {
 fn bench_parse_extreme(c: &mut Criterion) {
     run_parse_bench(c, "parse/extreme_struct", scenario_extreme());
 }
+fn bench_parse_form_owned(c: &mut Criterion) {
+    let Scenario {
+        query,
+        parse_options,
+        ..
+    } = scenario_form();
+
+    c.bench_function("parse/form_owned", move |b| {
+        b.iter(|| {
+            let parsed: OwnedForm =
+                parse(black_box(query.as_str()), &parse_options).expect("parse");
+            black_box(parsed);
+        });
+    });
+}
+
+fn bench_parse_form_borrowed(c: &mut Criterion) {
+    let Scenario {
+        query,
+        parse_options,
+        ..
+    } = scenario_form();
+
+    let tags = with_parsed(&query, &parse_options, |view| {
+        let form: BorrowedForm<'_> = view.deserialize()?;
+        Ok(form.tags.len())
+    })
+    .expect("baseline borrowed parse should succeed");
+    assert!(tags > 0, "form scenario should produce tags");
+
+    c.bench_function("parse/form_borrowed", move |b| {
+        b.iter(|| {
+            let parsed = with_parsed(black_box(query.as_str()), &parse_options, |view| {
+                let form: BorrowedForm<'_> = view.deserialize()?;
+                Ok(black_box(form).summary.len())
+            })
+            .expect("with_parsed");
+            black_box(parsed);
+        });
+    });
+}
+
 fn bench_stringify_simple(c: &mut Criterion) {
     run_stringify_bench(c, "stringify/simple_struct", scenario_simple());
 }
@@ -119,6 +163,8 @@ criterion_group!(
     bench_parse_medium,
     bench_parse_high,
     bench_parse_extreme,
+    bench_parse_form_owned,
+    bench_parse_form_borrowed,
     bench_stringify_simple,
     bench_stringify_medium,
     bench_stringify_high,
@@ -1,3 +1,4 @@
+#[allow(dead_code)]
 mod scenarios;
 
 use bunner_qs_rs::parsing::parse;
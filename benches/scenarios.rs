@@ -1,5 +1,6 @@
 use bunner_qs_rs::stringify::stringify;
 use bunner_qs_rs::{ParseOptions, StringifyOptions};
+use serde::Deserialize;
 use serde_json::{Value, json};
 
 pub const SIMPLE_TARGET_BYTES: usize = 500;
@@ -14,6 +15,10 @@ pub const HIGH_TARGET_BYTES: usize = 8 * 1024;
 pub const HIGH_TOLERANCE: usize = 512;
 pub const HIGH_MAX_DEPTH: usize = 8;
 
+pub const FORM_TARGET_BYTES: usize = 4 * 1024;
+pub const FORM_TOLERANCE: usize = 256;
+pub const FORM_MAX_DEPTH: usize = 2;
+
 pub const EXTREME_TARGET_BYTES: usize = 16 * 1024;
 pub const EXTREME_TOLERANCE: usize = 1024;
 pub const EXTREME_MAX_DEPTH: usize = 16;
@@ -29,6 +34,38 @@ pub struct Scenario {
 
 pub type PayloadBuilder = fn(&str) -> Value;
 
+#[derive(Debug, Default, Deserialize)]
+pub struct OwnedForm {
+    pub title: String,
+    pub summary: String,
+    pub tags: Vec<String>,
+    pub profile: OwnedProfile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct OwnedProfile {
+    pub name: String,
+    pub team: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BorrowedForm<'a> {
+    pub title: &'a str,
+    pub summary: &'a str,
+    #[serde(borrow)]
+    pub tags: Vec<&'a str>,
+    #[serde(borrow)]
+    pub profile: BorrowedProfile<'a>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BorrowedProfile<'a> {
+    pub name: &'a str,
+    pub team: &'a str,
+    pub signature: &'a str,
+}
+
 pub fn scenario_simple() -> Scenario {
     let stringify_options = StringifyOptions::new().space_as_plus(false);
     stringify_options
@@ -104,6 +141,31 @@ pub fn scenario_high() -> Scenario {
     }
 }
 
+pub fn scenario_form() -> Scenario {
+    let stringify_options = StringifyOptions::new().space_as_plus(false);
+    stringify_options
+        .validate()
+        .expect("build stringify options");
+
+    let (payload, query) = calibrate_payload(
+        FORM_TARGET_BYTES,
+        FORM_TOLERANCE,
+        &stringify_options,
+        FORM_MAX_DEPTH,
+        build_form_payload,
+    );
+
+    let parse_options = parse_options_for(FORM_TARGET_BYTES, FORM_MAX_DEPTH);
+
+    Scenario {
+        payload,
+        query,
+        parse_options,
+        stringify_options,
+        max_depth: FORM_MAX_DEPTH,
+    }
+}
+
 pub fn scenario_extreme() -> Scenario {
     let stringify_options = StringifyOptions::new().space_as_plus(false);
     stringify_options
@@ -293,6 +355,19 @@ fn build_high_payload(filler: &str) -> Value {
     })
 }
 
+fn build_form_payload(filler: &str) -> Value {
+    json!({
+        "title": format!("form_{:0>4}", filler.len()),
+        "summary": filler,
+        "tags": chunk_into_array(filler, 24),
+        "profile": {
+            "name": "bench",
+            "team": "parsing",
+            "signature": substring_ascii(filler, 0, 32)
+        }
+    })
+}
+
 fn build_extreme_payload(filler: &str) -> Value {
     let chunks: Vec<String> = chunk_into_array(filler, 256);
     let mut batches = Vec::new();
@@ -0,0 +1,94 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::parsing::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentKind {
+    Key,
+    Value,
+}
+
+pub trait Encoder: fmt::Debug + Send + Sync {
+    fn encode(&self, component: &str, context: &EncodeContext<'_>, output: &mut String);
+}
+
+pub trait Decoder: fmt::Debug + Send + Sync {
+    fn decode<'a>(
+        &self,
+        component: &'a str,
+        context: &mut DecodeContext<'_>,
+    ) -> Result<Cow<'a, str>, ParseError>;
+}
+
+pub struct EncodeContext<'a> {
+    key_path: &'a str,
+    kind: ComponentKind,
+    fallback: &'a dyn Fn(&str, &mut String),
+}
+
+impl<'a> EncodeContext<'a> {
+    pub(crate) fn new(
+        key_path: &'a str,
+        kind: ComponentKind,
+        fallback: &'a dyn Fn(&str, &mut String),
+    ) -> Self {
+        Self {
+            key_path,
+            kind,
+            fallback,
+        }
+    }
+
+    pub fn key_path(&self) -> &str {
+        self.key_path
+    }
+
+    pub fn kind(&self) -> ComponentKind {
+        self.kind
+    }
+
+    pub fn encode_default(&self, component: &str, output: &mut String) {
+        (self.fallback)(component, output);
+    }
+}
+
+pub(crate) trait DefaultDecode {
+    fn decode_default<'c>(&mut self, component: &'c str) -> Result<Cow<'c, str>, ParseError>;
+}
+
+pub struct DecodeContext<'a> {
+    key_path: &'a str,
+    kind: ComponentKind,
+    fallback: &'a mut dyn DefaultDecode,
+}
+
+impl<'a> DecodeContext<'a> {
+    pub(crate) fn new(
+        key_path: &'a str,
+        kind: ComponentKind,
+        fallback: &'a mut dyn DefaultDecode,
+    ) -> Self {
+        Self {
+            key_path,
+            kind,
+            fallback,
+        }
+    }
+
+    pub fn key_path(&self) -> &str {
+        self.key_path
+    }
+
+    pub fn kind(&self) -> ComponentKind {
+        self.kind
+    }
+
+    pub fn decode_default<'c>(&mut self, component: &'c str) -> Result<Cow<'c, str>, ParseError> {
+        self.fallback.decode_default(component)
+    }
+}
+
+#[cfg(test)]
+#[path = "hooks_test.rs"]
+mod hooks_test;
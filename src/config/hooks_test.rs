@@ -0,0 +1,60 @@
+use super::*;
+
+struct UppercaseFallback;
+
+impl DefaultDecode for UppercaseFallback {
+    fn decode_default<'c>(&mut self, component: &'c str) -> Result<Cow<'c, str>, ParseError> {
+        Ok(Cow::Owned(component.to_ascii_uppercase()))
+    }
+}
+
+mod encode_context {
+    use super::*;
+
+    #[test]
+    fn should_expose_key_path_and_kind_when_constructed_then_return_given_values() {
+        let fallback = |component: &str, output: &mut String| output.push_str(component);
+        let context = EncodeContext::new("user[name]", ComponentKind::Value, &fallback);
+
+        assert_eq!(context.key_path(), "user[name]");
+        assert_eq!(context.kind(), ComponentKind::Value);
+    }
+
+    #[test]
+    fn should_delegate_to_fallback_when_encoding_default_then_append_fallback_output() {
+        let fallback = |component: &str, output: &mut String| {
+            output.push('<');
+            output.push_str(component);
+            output.push('>');
+        };
+        let context = EncodeContext::new("key", ComponentKind::Key, &fallback);
+        let mut output = String::from("a=");
+
+        context.encode_default("x", &mut output);
+
+        assert_eq!(output, "a=<x>");
+    }
+}
+
+mod decode_context {
+    use super::*;
+
+    #[test]
+    fn should_expose_key_path_and_kind_when_constructed_then_return_given_values() {
+        let mut fallback = UppercaseFallback;
+        let context = DecodeContext::new("tags", ComponentKind::Key, &mut fallback);
+
+        assert_eq!(context.key_path(), "tags");
+        assert_eq!(context.kind(), ComponentKind::Key);
+    }
+
+    #[test]
+    fn should_delegate_to_fallback_when_decoding_default_then_return_fallback_result() {
+        let mut fallback = UppercaseFallback;
+        let mut context = DecodeContext::new("tags", ComponentKind::Value, &mut fallback);
+
+        let decoded = context.decode_default("abc").expect("fallback succeeds");
+
+        assert_eq!(decoded, "ABC");
+    }
+}
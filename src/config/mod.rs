@@ -1,3 +1,12 @@
+mod hooks;
 mod options;
 
-pub use options::{DuplicateKeyBehavior, OptionsValidationError, ParseOptions, StringifyOptions};
+pub(crate) use hooks::DefaultDecode;
+pub use hooks::{ComponentKind, DecodeContext, Decoder, EncodeContext, Encoder};
+
+pub use options::{
+    ArrayFormat, ArrayLimitBehavior, BoolFormat, BoolLiterals, Charset, Delimiters, DepthOverflow,
+    DuplicateKeyBehavior, EncodingProfile, KeySyntax, MalformedPairBehavior, MixedContainerPolicy,
+    OptionsValidationError, ParseOptions, SparseArrayBehavior, StringifyOptions,
+    UnknownFieldPolicy,
+};
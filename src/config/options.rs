@@ -1,20 +1,329 @@
+use smallvec::{SmallVec, smallvec};
+use std::sync::Arc;
 use thiserror::Error;
 
+use super::hooks::{Decoder, Encoder};
+use crate::stringify::encode::profile_encodes;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum DuplicateKeyBehavior {
     #[default]
     Reject,
     FirstWins,
     LastWins,
+    Combine,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayLimitBehavior {
+    Reject,
+    #[default]
+    ConvertToObject,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DepthOverflow {
+    #[default]
+    Error,
+    CollapseRemainder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SparseArrayBehavior {
+    #[default]
+    FillEmpty,
+    KeepHoles,
+    Compact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MixedContainerPolicy {
+    #[default]
+    Reject,
+    PromoteToObject,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeySyntax {
+    #[default]
+    Strict,
+    Lenient,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MalformedPairBehavior {
+    #[default]
+    Reject,
+    Skip,
+    KeepRaw,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Charset {
+    #[default]
+    Utf8,
+    Iso88591,
+    Utf8Lossy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownFieldPolicy {
+    #[default]
+    Reject,
+    Ignore,
+    Collect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoolFormat {
+    #[default]
+    TrueFalse,
+    OneZero,
+    YesNo,
+    OnOff,
+}
+
+impl BoolFormat {
+    pub fn literals(self) -> (&'static str, &'static str) {
+        match self {
+            BoolFormat::TrueFalse => ("true", "false"),
+            BoolFormat::OneZero => ("1", "0"),
+            BoolFormat::YesNo => ("yes", "no"),
+            BoolFormat::OnOff => ("on", "off"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoolLiterals {
+    truthy: Vec<String>,
+    falsy: Vec<String>,
+    case_insensitive: bool,
+    bare_key: Option<bool>,
+}
+
+impl Default for BoolLiterals {
+    fn default() -> Self {
+        Self {
+            truthy: vec!["true".to_string()],
+            falsy: vec!["false".to_string()],
+            case_insensitive: false,
+            bare_key: None,
+        }
+    }
+}
+
+impl BoolLiterals {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn format(mut self, format: BoolFormat) -> Self {
+        let (truthy, falsy) = format.literals();
+        self.truthy = vec![truthy.to_string()];
+        self.falsy = vec![falsy.to_string()];
+        self
+    }
+
+    pub fn truthy(mut self, literal: impl Into<String>) -> Self {
+        let literal = literal.into();
+        if !self.truthy.contains(&literal) {
+            self.truthy.push(literal);
+        }
+        self
+    }
+
+    pub fn falsy(mut self, literal: impl Into<String>) -> Self {
+        let literal = literal.into();
+        if !self.falsy.contains(&literal) {
+            self.falsy.push(literal);
+        }
+        self
+    }
+
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    pub fn bare_key(mut self, value: bool) -> Self {
+        self.bare_key = Some(value);
+        self
+    }
+
+    pub fn parse(&self, text: &str) -> Option<bool> {
+        if self
+            .truthy
+            .iter()
+            .any(|literal| self.matches(literal, text))
+        {
+            Some(true)
+        } else if self.falsy.iter().any(|literal| self.matches(literal, text)) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn parse_bare_key(&self) -> Option<bool> {
+        self.bare_key.or_else(|| self.parse(""))
+    }
+
+    fn matches(&self, literal: &str, text: &str) -> bool {
+        if self.case_insensitive {
+            literal.eq_ignore_ascii_case(text)
+        } else {
+            literal == text
+        }
+    }
+
+    fn validate(&self) -> Result<(), OptionsValidationError> {
+        match self
+            .falsy
+            .iter()
+            .find(|literal| self.parse(literal) == Some(true))
+        {
+            Some(literal) => Err(OptionsValidationError::AmbiguousBoolLiteral {
+                literal: literal.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+const DEFAULT_ARRAY_LIMIT: usize = 20;
+
+const ALLOWED_DELIMITERS: [char; 8] = ['&', ';', ',', '|', ':', '/', '@', '='];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delimiters {
+    pairs: SmallVec<[char; 2]>,
+    key_value: char,
+}
+
+impl Default for Delimiters {
+    fn default() -> Self {
+        Self {
+            pairs: smallvec!['&'],
+            key_value: '=',
+        }
+    }
+}
+
+impl Delimiters {
+    pub fn pairs(&self) -> &[char] {
+        &self.pairs
+    }
+
+    pub fn pair(&self) -> char {
+        self.pairs.first().copied().unwrap_or('&')
+    }
+
+    pub fn key_value(&self) -> char {
+        self.key_value
+    }
+
+    pub(crate) fn pair_bytes(&self) -> SmallVec<[u8; 2]> {
+        self.pairs
+            .iter()
+            .filter_map(|ch| ch.is_ascii().then_some(*ch as u8))
+            .collect()
+    }
+
+    pub(crate) fn key_value_byte(&self) -> u8 {
+        if self.key_value.is_ascii() {
+            self.key_value as u8
+        } else {
+            b'='
+        }
+    }
+
+    fn validate(&self) -> Result<(), OptionsValidationError> {
+        if self.pairs.is_empty() {
+            return Err(OptionsValidationError::NonEmptyRequired {
+                field: "pair_delimiters",
+            });
+        }
+        for &character in &self.pairs {
+            if !ALLOWED_DELIMITERS.contains(&character) {
+                return Err(OptionsValidationError::InvalidDelimiter {
+                    field: "pair_delimiters",
+                    character,
+                });
+            }
+            if character == self.key_value {
+                return Err(OptionsValidationError::DelimiterConflict { character });
+            }
+        }
+        if !ALLOWED_DELIMITERS.contains(&self.key_value) {
+            return Err(OptionsValidationError::InvalidDelimiter {
+                field: "key_value_separator",
+                character: self.key_value,
+            });
+        }
+        Ok(())
+    }
+
+    fn contains(&self, character: char) -> bool {
+        self.key_value == character || self.pairs.contains(&character)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct ParseOptions {
     pub space_as_plus: bool,
     pub duplicate_keys: DuplicateKeyBehavior,
     pub max_params: Option<usize>,
     pub max_length: Option<usize>,
     pub max_depth: Option<usize>,
+    pub depth_overflow: DepthOverflow,
+    pub array_limit: Option<usize>,
+    pub array_limit_behavior: ArrayLimitBehavior,
+    pub sparse_arrays: SparseArrayBehavior,
+    pub mixed_containers: MixedContainerPolicy,
+    pub comma: bool,
+    pub allow_dots: bool,
+    pub key_syntax: KeySyntax,
+    pub unknown_fields: UnknownFieldPolicy,
+    pub malformed_pairs: MalformedPairBehavior,
+    pub delimiters: Delimiters,
+    pub strict_null_handling: bool,
+    pub empty_as_none: bool,
+    pub bool_literals: BoolLiterals,
+    pub infer_scalars: bool,
+    pub charset: Charset,
+    pub charset_sentinel: bool,
+    pub decoder: Option<Arc<dyn Decoder>>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            space_as_plus: false,
+            duplicate_keys: DuplicateKeyBehavior::default(),
+            max_params: None,
+            max_length: None,
+            max_depth: None,
+            depth_overflow: DepthOverflow::default(),
+            array_limit: Some(DEFAULT_ARRAY_LIMIT),
+            array_limit_behavior: ArrayLimitBehavior::default(),
+            sparse_arrays: SparseArrayBehavior::default(),
+            mixed_containers: MixedContainerPolicy::default(),
+            comma: false,
+            allow_dots: false,
+            key_syntax: KeySyntax::default(),
+            unknown_fields: UnknownFieldPolicy::default(),
+            malformed_pairs: MalformedPairBehavior::default(),
+            delimiters: Delimiters::default(),
+            strict_null_handling: false,
+            empty_as_none: false,
+            bool_literals: BoolLiterals::default(),
+            infer_scalars: false,
+            charset: Charset::default(),
+            charset_sentinel: false,
+            decoder: None,
+        }
+    }
 }
 
 impl ParseOptions {
@@ -47,6 +356,101 @@ impl ParseOptions {
         self
     }
 
+    pub fn depth_overflow(mut self, behavior: DepthOverflow) -> Self {
+        self.depth_overflow = behavior;
+        self
+    }
+
+    pub fn array_limit(mut self, limit: usize) -> Self {
+        self.array_limit = Some(limit);
+        self
+    }
+
+    pub fn array_limit_behavior(mut self, behavior: ArrayLimitBehavior) -> Self {
+        self.array_limit_behavior = behavior;
+        self
+    }
+
+    pub fn sparse_arrays(mut self, behavior: SparseArrayBehavior) -> Self {
+        self.sparse_arrays = behavior;
+        self
+    }
+
+    pub fn mixed_containers(mut self, policy: MixedContainerPolicy) -> Self {
+        self.mixed_containers = policy;
+        self
+    }
+
+    pub fn comma(mut self, enabled: bool) -> Self {
+        self.comma = enabled;
+        self
+    }
+
+    pub fn allow_dots(mut self, enabled: bool) -> Self {
+        self.allow_dots = enabled;
+        self
+    }
+
+    pub fn key_syntax(mut self, syntax: KeySyntax) -> Self {
+        self.key_syntax = syntax;
+        self
+    }
+
+    pub fn unknown_fields(mut self, policy: UnknownFieldPolicy) -> Self {
+        self.unknown_fields = policy;
+        self
+    }
+
+    pub fn malformed_pairs(mut self, behavior: MalformedPairBehavior) -> Self {
+        self.malformed_pairs = behavior;
+        self
+    }
+
+    pub fn pair_delimiters(mut self, delimiters: &str) -> Self {
+        self.delimiters.pairs = delimiters.chars().collect();
+        self
+    }
+
+    pub fn key_value_separator(mut self, separator: char) -> Self {
+        self.delimiters.key_value = separator;
+        self
+    }
+
+    pub fn strict_null_handling(mut self, enabled: bool) -> Self {
+        self.strict_null_handling = enabled;
+        self
+    }
+
+    pub fn empty_as_none(mut self, enabled: bool) -> Self {
+        self.empty_as_none = enabled;
+        self
+    }
+
+    pub fn bool_literals(mut self, literals: BoolLiterals) -> Self {
+        self.bool_literals = literals;
+        self
+    }
+
+    pub fn infer_scalars(mut self, enabled: bool) -> Self {
+        self.infer_scalars = enabled;
+        self
+    }
+
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    pub fn charset_sentinel(mut self, enabled: bool) -> Self {
+        self.charset_sentinel = enabled;
+        self
+    }
+
+    pub fn decoder<D: Decoder + 'static>(mut self, decoder: D) -> Self {
+        self.decoder = Some(Arc::new(decoder));
+        self
+    }
+
     pub fn validate(&self) -> Result<(), OptionsValidationError> {
         if matches!(self.max_params, Some(0)) {
             return Err(OptionsValidationError::NonZeroRequired {
@@ -61,13 +465,51 @@ impl ParseOptions {
         if matches!(self.max_depth, Some(0)) {
             return Err(OptionsValidationError::NonZeroRequired { field: "max_depth" });
         }
-        Ok(())
+        self.delimiters.validate()?;
+        if self.comma && self.delimiters.contains(',') {
+            return Err(OptionsValidationError::DelimiterConflict { character: ',' });
+        }
+        self.bool_literals.validate()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EncodingProfile {
+    #[default]
+    Component,
+    Rfc3986,
+    Rfc1738,
+    Whatwg,
+}
+
+impl EncodingProfile {
+    fn space_as_plus(self) -> bool {
+        matches!(self, EncodingProfile::Rfc1738 | EncodingProfile::Whatwg)
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayFormat {
+    #[default]
+    Indices,
+    Brackets,
+    Repeat,
+    Comma,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct StringifyOptions {
     pub space_as_plus: bool,
+    pub array_format: ArrayFormat,
+    pub allow_dots: bool,
+    pub literal_brackets: bool,
+    pub delimiters: Delimiters,
+    pub strict_null_handling: bool,
+    pub skip_empty: bool,
+    pub bool_format: BoolFormat,
+    pub charset: Charset,
+    pub encoding_profile: EncodingProfile,
+    pub encoder: Option<Arc<dyn Encoder>>,
 }
 
 impl StringifyOptions {
@@ -80,7 +522,76 @@ impl StringifyOptions {
         self
     }
 
+    pub fn array_format(mut self, format: ArrayFormat) -> Self {
+        self.array_format = format;
+        self
+    }
+
+    pub fn allow_dots(mut self, enabled: bool) -> Self {
+        self.allow_dots = enabled;
+        self
+    }
+
+    pub fn literal_brackets(mut self, enabled: bool) -> Self {
+        self.literal_brackets = enabled;
+        self
+    }
+
+    pub fn pair_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiters.pairs = smallvec![delimiter];
+        self
+    }
+
+    pub fn key_value_separator(mut self, separator: char) -> Self {
+        self.delimiters.key_value = separator;
+        self
+    }
+
+    pub fn strict_null_handling(mut self, enabled: bool) -> Self {
+        self.strict_null_handling = enabled;
+        self
+    }
+
+    pub fn skip_empty(mut self, enabled: bool) -> Self {
+        self.skip_empty = enabled;
+        self
+    }
+
+    pub fn bool_format(mut self, format: BoolFormat) -> Self {
+        self.bool_format = format;
+        self
+    }
+
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    pub fn encoding_profile(mut self, profile: EncodingProfile) -> Self {
+        self.encoding_profile = profile;
+        self.space_as_plus = profile.space_as_plus();
+        self
+    }
+
+    pub fn encoder<E: Encoder + 'static>(mut self, encoder: E) -> Self {
+        self.encoder = Some(Arc::new(encoder));
+        self
+    }
+
     pub fn validate(&self) -> Result<(), OptionsValidationError> {
+        self.delimiters.validate()?;
+        if let Some(&character) = self
+            .delimiters
+            .pairs
+            .iter()
+            .chain(std::iter::once(&self.delimiters.key_value))
+            .find(|&&character| !profile_encodes(self.encoding_profile, character))
+        {
+            return Err(OptionsValidationError::UnencodedDelimiter { character });
+        }
+        if self.array_format == ArrayFormat::Comma && self.delimiters.contains(',') {
+            return Err(OptionsValidationError::DelimiterConflict { character: ',' });
+        }
         Ok(())
     }
 }
@@ -89,6 +600,19 @@ impl StringifyOptions {
 pub enum OptionsValidationError {
     #[error("{field} must be greater than 0 when specified")]
     NonZeroRequired { field: &'static str },
+    #[error("{field} must not be empty")]
+    NonEmptyRequired { field: &'static str },
+    #[error("{field} cannot use `{character}` as a delimiter")]
+    InvalidDelimiter {
+        field: &'static str,
+        character: char,
+    },
+    #[error("delimiter `{character}` is used for more than one purpose")]
+    DelimiterConflict { character: char },
+    #[error("delimiter `{character}` is left unencoded by the selected encoding profile")]
+    UnencodedDelimiter { character: char },
+    #[error("boolean literal `{literal}` is listed as both true and false")]
+    AmbiguousBoolLiteral { literal: String },
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
 use super::*;
+use crate::config::EncodeContext;
 
 mod parse_options_builder {
     use super::*;
@@ -74,6 +75,310 @@ mod parse_options_builder {
             OptionsValidationError::NonZeroRequired { field: "max_depth" }
         );
     }
+
+    #[test]
+    fn should_store_array_limit_when_configuring_policy_then_keep_limit_and_behavior() {
+        let options = ParseOptions::new()
+            .array_limit(0)
+            .array_limit_behavior(ArrayLimitBehavior::Reject);
+        options
+            .validate()
+            .expect("zero array_limit should be valid");
+
+        assert_eq!(options.array_limit, Some(0));
+        assert_eq!(options.array_limit_behavior, ArrayLimitBehavior::Reject);
+        assert_eq!(ParseOptions::new().array_limit, Some(20));
+        assert_eq!(
+            ParseOptions::new().array_limit_behavior,
+            ArrayLimitBehavior::ConvertToObject
+        );
+    }
+
+    #[test]
+    fn should_store_policy_when_setting_unknown_fields_then_keep_selected_policy() {
+        let options = ParseOptions::new().unknown_fields(UnknownFieldPolicy::Collect);
+        options
+            .validate()
+            .expect("unknown field policy should be valid");
+
+        assert_eq!(options.unknown_fields, UnknownFieldPolicy::Collect);
+    }
+
+    #[test]
+    fn should_store_behavior_when_setting_malformed_pairs_then_keep_selected_behavior() {
+        let options = ParseOptions::new().malformed_pairs(MalformedPairBehavior::KeepRaw);
+        options
+            .validate()
+            .expect("malformed pair behavior should be valid");
+
+        assert_eq!(options.malformed_pairs, MalformedPairBehavior::KeepRaw);
+    }
+
+    #[test]
+    fn should_store_flag_when_enabling_strict_null_handling_then_set_flag_true() {
+        let options = ParseOptions::new().strict_null_handling(true);
+        options
+            .validate()
+            .expect("strict_null_handling flag should be valid");
+
+        assert!(options.strict_null_handling);
+    }
+
+    #[test]
+    fn should_store_flag_when_enabling_empty_as_none_then_set_flag_true() {
+        let options = ParseOptions::new().empty_as_none(true);
+        options
+            .validate()
+            .expect("empty_as_none flag should be valid");
+
+        assert!(options.empty_as_none);
+    }
+
+    #[test]
+    fn should_store_flag_when_enabling_infer_scalars_then_set_flag_true() {
+        let options = ParseOptions::new().infer_scalars(true);
+        options
+            .validate()
+            .expect("infer_scalars flag should be valid");
+
+        assert!(options.infer_scalars);
+    }
+
+    #[test]
+    fn should_store_syntax_when_configuring_key_syntax_then_keep_selection() {
+        let options = ParseOptions::new().key_syntax(KeySyntax::Lenient);
+        options.validate().expect("key syntax should be valid");
+
+        assert_eq!(options.key_syntax, KeySyntax::Lenient);
+        assert_eq!(ParseOptions::new().key_syntax, KeySyntax::Strict);
+    }
+
+    #[test]
+    fn should_store_behavior_when_configuring_depth_overflow_then_keep_selection() {
+        let options = ParseOptions::new()
+            .max_depth(3)
+            .depth_overflow(DepthOverflow::CollapseRemainder);
+        options
+            .validate()
+            .expect("depth overflow behavior should be valid");
+
+        assert_eq!(options.depth_overflow, DepthOverflow::CollapseRemainder);
+        assert_eq!(ParseOptions::new().depth_overflow, DepthOverflow::Error);
+    }
+
+    #[test]
+    fn should_store_policy_when_configuring_mixed_containers_then_keep_selection() {
+        let options = ParseOptions::new().mixed_containers(MixedContainerPolicy::PromoteToObject);
+        options
+            .validate()
+            .expect("mixed container policy should be valid");
+
+        assert_eq!(
+            options.mixed_containers,
+            MixedContainerPolicy::PromoteToObject
+        );
+        assert_eq!(
+            ParseOptions::new().mixed_containers,
+            MixedContainerPolicy::Reject
+        );
+    }
+
+    #[test]
+    fn should_store_behavior_when_configuring_sparse_arrays_then_keep_selection() {
+        let options = ParseOptions::new().sparse_arrays(SparseArrayBehavior::Compact);
+        options
+            .validate()
+            .expect("sparse array behavior should be valid");
+
+        assert_eq!(options.sparse_arrays, SparseArrayBehavior::Compact);
+        assert_eq!(
+            ParseOptions::new().sparse_arrays,
+            SparseArrayBehavior::FillEmpty
+        );
+    }
+
+    #[test]
+    fn should_store_charset_when_configuring_legacy_forms_then_keep_charset_and_sentinel() {
+        let options = ParseOptions::new()
+            .charset(Charset::Iso88591)
+            .charset_sentinel(true);
+        options.validate().expect("charset options should be valid");
+
+        assert_eq!(options.charset, Charset::Iso88591);
+        assert!(options.charset_sentinel);
+        assert_eq!(ParseOptions::new().charset, Charset::Utf8);
+    }
+
+    #[test]
+    fn should_store_flag_when_enabling_allow_dots_then_set_allow_dots_true() {
+        let options = ParseOptions::new().allow_dots(true);
+        options.validate().expect("allow_dots flag should be valid");
+
+        assert!(options.allow_dots);
+    }
+
+    #[test]
+    fn should_store_flag_when_enabling_comma_then_set_comma_true() {
+        let options = ParseOptions::new().comma(true);
+        options.validate().expect("comma flag should be valid");
+
+        assert!(options.comma);
+    }
+
+    #[test]
+    fn should_store_delimiters_when_setting_pair_delimiters_then_keep_each_delimiter() {
+        let options = ParseOptions::new()
+            .pair_delimiters("&;")
+            .key_value_separator(':');
+        options.validate().expect("delimiters should be valid");
+
+        assert_eq!(options.delimiters.pairs(), &['&', ';']);
+        assert_eq!(options.delimiters.key_value(), ':');
+    }
+
+    #[test]
+    fn should_fail_when_pair_delimiters_are_empty_then_return_non_empty_error() {
+        let error = ParseOptions::new()
+            .pair_delimiters("")
+            .validate()
+            .expect_err("empty delimiters should be rejected");
+
+        assert_eq!(
+            error,
+            OptionsValidationError::NonEmptyRequired {
+                field: "pair_delimiters"
+            }
+        );
+    }
+
+    #[test]
+    fn should_fail_when_pair_delimiter_is_unsupported_then_return_invalid_delimiter() {
+        let error = ParseOptions::new()
+            .pair_delimiters("&%")
+            .validate()
+            .expect_err("percent sign should be rejected");
+
+        assert_eq!(
+            error,
+            OptionsValidationError::InvalidDelimiter {
+                field: "pair_delimiters",
+                character: '%'
+            }
+        );
+    }
+
+    #[test]
+    fn should_fail_when_pair_delimiter_matches_separator_then_return_conflict() {
+        let error = ParseOptions::new()
+            .pair_delimiters(";")
+            .key_value_separator(';')
+            .validate()
+            .expect_err("shared delimiter should be rejected");
+
+        assert_eq!(
+            error,
+            OptionsValidationError::DelimiterConflict { character: ';' }
+        );
+    }
+
+    #[test]
+    fn should_fail_when_comma_splitting_uses_comma_delimiter_then_return_conflict() {
+        let error = ParseOptions::new()
+            .comma(true)
+            .pair_delimiters(",")
+            .validate()
+            .expect_err("comma delimiter should conflict with comma splitting");
+
+        assert_eq!(
+            error,
+            OptionsValidationError::DelimiterConflict { character: ',' }
+        );
+    }
+
+    #[test]
+    fn should_accept_extra_literals_when_bool_literals_are_configured_then_parse_each_spelling() {
+        let literals = BoolLiterals::new()
+            .truthy("1")
+            .falsy("0")
+            .truthy("on")
+            .falsy("off")
+            .case_insensitive(true);
+
+        let options = ParseOptions::new().bool_literals(literals);
+        options
+            .validate()
+            .expect("distinct literals should be valid");
+
+        let parsed: Vec<_> = ["true", "1", "ON", "off", "0", "maybe"]
+            .iter()
+            .map(|text| options.bool_literals.parse(text))
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                Some(true),
+                Some(true),
+                Some(true),
+                Some(false),
+                Some(false),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn should_replace_default_literals_when_format_is_applied_then_reject_true() {
+        let literals = BoolLiterals::new().format(BoolFormat::OneZero);
+
+        assert_eq!(literals.parse("1"), Some(true));
+        assert_eq!(literals.parse("0"), Some(false));
+        assert_eq!(literals.parse("true"), None);
+        assert_eq!(literals.parse("false"), None);
+    }
+
+    #[test]
+    fn should_extend_format_literals_when_truthy_and_falsy_follow_then_accept_both_sets() {
+        let literals = BoolLiterals::new()
+            .format(BoolFormat::OnOff)
+            .truthy("1")
+            .falsy("0");
+
+        assert_eq!(literals.parse("on"), Some(true));
+        assert_eq!(literals.parse("1"), Some(true));
+        assert_eq!(literals.parse("0"), Some(false));
+        assert_eq!(literals.parse("true"), None);
+    }
+
+    #[test]
+    fn should_resolve_bare_key_when_configured_then_prefer_it_over_empty_literal() {
+        let unset = BoolLiterals::new();
+        let empty_truthy = BoolLiterals::new().truthy("");
+        let explicit = BoolLiterals::new().truthy("").bare_key(false);
+
+        assert_eq!(unset.parse_bare_key(), None);
+        assert_eq!(empty_truthy.parse_bare_key(), Some(true));
+        assert_eq!(explicit.parse_bare_key(), Some(false));
+    }
+
+    #[test]
+    fn should_fail_when_literal_is_both_truthy_and_falsy_then_return_ambiguous_literal() {
+        let error = ParseOptions::new()
+            .bool_literals(
+                BoolLiterals::new()
+                    .truthy("Yes")
+                    .falsy("yes")
+                    .case_insensitive(true),
+            )
+            .validate()
+            .expect_err("overlapping literals should be rejected");
+
+        assert_eq!(
+            error,
+            OptionsValidationError::AmbiguousBoolLiteral {
+                literal: "yes".into()
+            }
+        );
+    }
 }
 
 mod stringify_options_builder {
@@ -86,6 +391,7 @@ mod stringify_options_builder {
         options.validate().expect("defaults should be valid");
 
         assert!(!options.space_as_plus);
+        assert_eq!(options.array_format, ArrayFormat::Indices);
     }
 
     #[test]
@@ -95,4 +401,167 @@ mod stringify_options_builder {
 
         assert!(options.space_as_plus);
     }
+
+    #[test]
+    fn should_store_flag_when_enabling_stringify_strict_null_handling_then_set_flag_true() {
+        let options = StringifyOptions::new().strict_null_handling(true);
+        options
+            .validate()
+            .expect("strict_null_handling flag should be valid");
+
+        assert!(options.strict_null_handling);
+    }
+
+    #[test]
+    fn should_store_flag_when_enabling_skip_empty_then_set_flag_true() {
+        let options = StringifyOptions::new().skip_empty(true);
+        options.validate().expect("skip_empty flag should be valid");
+
+        assert!(options.skip_empty);
+    }
+
+    #[test]
+    fn should_store_flag_when_enabling_literal_brackets_then_set_literal_brackets_true() {
+        let options = StringifyOptions::new().literal_brackets(true);
+        options
+            .validate()
+            .expect("literal_brackets flag should be valid");
+
+        assert!(options.literal_brackets);
+    }
+
+    #[test]
+    fn should_store_flag_when_enabling_stringify_allow_dots_then_set_allow_dots_true() {
+        let options = StringifyOptions::new().allow_dots(true);
+        options.validate().expect("allow_dots flag should be valid");
+
+        assert!(options.allow_dots);
+    }
+
+    #[test]
+    fn should_store_format_when_setting_array_format_then_keep_selected_format() {
+        let options = StringifyOptions::new().array_format(ArrayFormat::Brackets);
+        options.validate().expect("array format should be valid");
+
+        assert_eq!(options.array_format, ArrayFormat::Brackets);
+    }
+
+    #[test]
+    fn should_store_delimiter_when_setting_pair_delimiter_then_replace_default() {
+        let options = StringifyOptions::new()
+            .pair_delimiter(';')
+            .key_value_separator(':');
+        options.validate().expect("delimiters should be valid");
+
+        assert_eq!(options.delimiters.pairs(), &[';']);
+        assert_eq!(options.delimiters.key_value(), ':');
+    }
+
+    #[test]
+    fn should_fail_when_separator_is_unsupported_then_return_invalid_delimiter() {
+        let error = StringifyOptions::new()
+            .key_value_separator('~')
+            .validate()
+            .expect_err("tilde should be rejected");
+
+        assert_eq!(
+            error,
+            OptionsValidationError::InvalidDelimiter {
+                field: "key_value_separator",
+                character: '~'
+            }
+        );
+    }
+
+    #[test]
+    fn should_fail_when_comma_format_uses_comma_delimiter_then_return_conflict() {
+        let error = StringifyOptions::new()
+            .array_format(ArrayFormat::Comma)
+            .pair_delimiter(',')
+            .validate()
+            .expect_err("comma delimiter should conflict with comma format");
+
+        assert_eq!(
+            error,
+            OptionsValidationError::DelimiterConflict { character: ',' }
+        );
+    }
+
+    #[test]
+    fn should_store_bool_format_when_configured_then_expose_literals() {
+        let options = StringifyOptions::new().bool_format(BoolFormat::YesNo);
+        options.validate().expect("bool format should be valid");
+
+        assert_eq!(options.bool_format.literals(), ("yes", "no"));
+    }
+
+    #[test]
+    fn should_store_charset_when_configured_then_keep_latin1_choice() {
+        let options = StringifyOptions::new().charset(Charset::Iso88591);
+        options.validate().expect("charset should be valid");
+
+        assert_eq!(options.charset, Charset::Iso88591);
+    }
+
+    #[test]
+    fn should_default_space_as_plus_when_selecting_profile_then_follow_profile_convention() {
+        let whatwg = StringifyOptions::new().encoding_profile(EncodingProfile::Whatwg);
+        let rfc3986 = StringifyOptions::new()
+            .space_as_plus(true)
+            .encoding_profile(EncodingProfile::Rfc3986);
+        let overridden = StringifyOptions::new()
+            .encoding_profile(EncodingProfile::Rfc1738)
+            .space_as_plus(false);
+
+        assert_eq!(whatwg.encoding_profile, EncodingProfile::Whatwg);
+        assert!(whatwg.space_as_plus);
+        assert!(!rfc3986.space_as_plus);
+        assert!(!overridden.space_as_plus);
+        assert_eq!(
+            StringifyOptions::new().encoding_profile,
+            EncodingProfile::Component
+        );
+    }
+
+    #[test]
+    fn should_share_encoder_hook_when_cloning_options_then_keep_same_instance() {
+        #[derive(Debug)]
+        struct Verbatim;
+
+        impl Encoder for Verbatim {
+            fn encode(&self, component: &str, _: &EncodeContext<'_>, output: &mut String) {
+                output.push_str(component);
+            }
+        }
+
+        let options = StringifyOptions::new().encoder(Verbatim);
+        let cloned = options.clone();
+
+        let (original, copy) = (
+            options.encoder.as_ref().expect("encoder installed"),
+            cloned.encoder.as_ref().expect("encoder cloned"),
+        );
+        assert!(std::sync::Arc::ptr_eq(original, copy));
+        assert!(format!("{options:?}").contains("Verbatim"));
+        assert!(StringifyOptions::new().encoder.is_none());
+    }
+
+    #[test]
+    fn should_fail_when_profile_leaves_delimiter_unencoded_then_return_unencoded_delimiter() {
+        let error = StringifyOptions::new()
+            .encoding_profile(EncodingProfile::Rfc3986)
+            .key_value_separator(':')
+            .validate()
+            .expect_err("rfc3986 leaves colon unencoded");
+
+        assert_eq!(
+            error,
+            OptionsValidationError::UnencodedDelimiter { character: ':' }
+        );
+        StringifyOptions::new()
+            .encoding_profile(EncodingProfile::Whatwg)
+            .key_value_separator(':')
+            .validate()
+            .expect("whatwg encodes colon");
+    }
 }
@@ -10,7 +10,14 @@ mod serde_adapter;
 pub mod stringify;
 mod util;
 
-pub use config::{DuplicateKeyBehavior, OptionsValidationError, ParseOptions, StringifyOptions};
+pub use config::{
+    ArrayFormat, ArrayLimitBehavior, BoolFormat, BoolLiterals, Charset, ComponentKind,
+    DecodeContext, Decoder, Delimiters, DepthOverflow, DuplicateKeyBehavior, EncodeContext,
+    Encoder, EncodingProfile, KeySyntax, MalformedPairBehavior, MixedContainerPolicy,
+    OptionsValidationError, ParseOptions, SparseArrayBehavior, StringifyOptions,
+    UnknownFieldPolicy,
+};
+pub use model::{OrderedMap, QueryMap, Value};
 pub use qs::{Qs, QsParseError, QsStringifyError};
 
 #[cfg(test)]
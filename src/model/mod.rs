@@ -1,5 +1,6 @@
 mod map;
 mod value;
+mod value_serde;
 
 pub use map::OrderedMap;
 pub use value::{QueryMap, Value};
@@ -11,6 +11,7 @@ use serde::de::DeserializeOwned;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Value {
+    Null,
     String(String),
     Array(Vec<Value>),
     Object(OrderedMap<String, Value>),
@@ -41,6 +42,11 @@ impl Value {
         }
     }
 
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
     #[inline]
     pub fn is_string(&self) -> bool {
         matches!(self, Value::String(_))
@@ -116,6 +122,7 @@ pub(crate) fn clone_value_into_arena<'arena>(
     value: &Value,
 ) -> ArenaValue<'arena> {
     match value {
+        Value::Null => ArenaValue::Null,
         Value::String(text) => ArenaValue::string(arena.alloc_str(text)),
         Value::Array(items) => {
             let mut seq = arena.alloc_vec();
@@ -0,0 +1,195 @@
+use ahash::RandomState;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use std::fmt;
+
+use super::{OrderedMap, QueryMap, Value};
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Value::Null => serializer.serialize_unit(),
+            Value::String(text) => serializer.serialize_str(text),
+            Value::Array(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            }
+            Value::Object(map) => serialize_entries(serializer, map),
+        }
+    }
+}
+
+impl Serialize for QueryMap {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_entries(serializer, self)
+    }
+}
+
+fn serialize_entries<S>(serializer: S, map: &OrderedMap<String, Value>) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut out = serializer.serialize_map(Some(map.len()))?;
+    for (key, value) in map {
+        out.serialize_entry(key, value)?;
+    }
+    out.end()
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for QueryMap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(QueryMapVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a query string value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::String(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_unit()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        collect_entries(map).map(Value::Object)
+    }
+}
+
+struct QueryMapVisitor;
+
+impl<'de> Visitor<'de> for QueryMapVisitor {
+    type Value = QueryMap;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a map of query parameters")
+    }
+
+    fn visit_unit<E>(self) -> Result<QueryMap, E>
+    where
+        E: de::Error,
+    {
+        Ok(QueryMap::new())
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<QueryMap, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        collect_entries(map).map(QueryMap::from)
+    }
+}
+
+fn collect_entries<'de, A>(mut map: A) -> Result<OrderedMap<String, Value>, A::Error>
+where
+    A: MapAccess<'de>,
+{
+    let mut entries =
+        OrderedMap::with_capacity_and_hasher(map.size_hint().unwrap_or(0), RandomState::default());
+    while let Some((key, value)) = map.next_entry::<String, Value>()? {
+        entries.insert(key, value);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+#[path = "value_serde_test.rs"]
+mod value_serde_test;
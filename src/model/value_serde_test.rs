@@ -0,0 +1,83 @@
+use super::*;
+use serde_json::json;
+
+fn sample_map() -> QueryMap {
+    let profile: OrderedMap<String, Value> = OrderedMap::from_iter([
+        ("name".into(), Value::from("Alice")),
+        (
+            "tags".into(),
+            Value::Array(vec![Value::from("admin"), Value::from("ops")]),
+        ),
+    ]);
+    QueryMap::from_iter([
+        ("profile", Value::Object(profile)),
+        ("page", Value::from("2")),
+    ])
+}
+
+mod serialize {
+    use super::*;
+
+    #[test]
+    fn should_serialize_query_map_as_nested_json_when_map_contains_objects_and_arrays_then_preserve_shape()
+     {
+        let map = sample_map();
+
+        let json_value = serde_json::to_value(&map).expect("serialization should succeed");
+
+        assert_eq!(
+            json_value,
+            json!({
+                "profile": { "name": "Alice", "tags": ["admin", "ops"] },
+                "page": "2"
+            })
+        );
+    }
+}
+
+mod deserialize {
+    use super::*;
+
+    #[test]
+    fn should_roundtrip_query_map_through_json_when_serialized_then_restore_identical_map() {
+        let map = sample_map();
+        let json_value = serde_json::to_value(&map).expect("serialization should succeed");
+
+        let restored: QueryMap =
+            serde_json::from_value(json_value).expect("deserialization should succeed");
+
+        assert_eq!(restored, map);
+    }
+
+    #[test]
+    fn should_stringify_scalars_when_json_contains_numbers_and_booleans_then_store_text_values() {
+        let restored: Value =
+            serde_json::from_value(json!({ "count": 3, "active": true, "none": null }))
+                .expect("deserialization should succeed");
+
+        let object = restored.as_object().expect("object expected");
+        assert_eq!(object.get("count"), Some(&Value::from("3")));
+        assert_eq!(object.get("active"), Some(&Value::from("true")));
+        assert_eq!(object.get("none"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn should_roundtrip_null_through_json_when_value_is_null_then_keep_null() {
+        let mut map = QueryMap::new();
+        map.insert("debug".into(), Value::Null);
+        map.insert("page".into(), Value::from("3"));
+        let json_value = serde_json::to_value(&map).expect("serialization should succeed");
+
+        let restored: QueryMap =
+            serde_json::from_value(json_value).expect("deserialization should succeed");
+
+        assert_eq!(restored, map);
+    }
+
+    #[test]
+    fn should_reject_top_level_string_when_deserializing_query_map_then_return_error() {
+        let result: Result<QueryMap, _> = serde_json::from_value(json!("plain"));
+
+        assert!(result.is_err());
+    }
+}
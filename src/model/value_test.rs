@@ -178,6 +178,16 @@ mod conversions {
 mod value_accessors {
     use super::*;
 
+    #[test]
+    fn should_report_null_when_value_is_null_then_reject_other_accessors() {
+        let value = Value::Null;
+
+        assert!(value.is_null());
+        assert!(value.as_str().is_none());
+        assert!(!value.is_string());
+        assert!(!Value::from("").is_null());
+    }
+
     #[test]
     fn should_return_borrowed_str_when_value_is_string_then_expose_slice() {
         let value = Value::from("access");
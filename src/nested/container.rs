@@ -1,6 +1,8 @@
+use crate::config::SparseArrayBehavior;
 use crate::parsing::ParseError;
 use crate::parsing::arena::{ArenaValue, ParseArena};
 
+use super::insertion::arena_is_placeholder;
 use super::segment::ContainerType;
 
 pub(crate) fn arena_initial_container<'arena>(
@@ -19,7 +21,13 @@ pub(crate) fn arena_ensure_container<'arena>(
     value: &mut ArenaValue<'arena>,
     expected: ContainerType,
     root_key: &str,
+    sparse: SparseArrayBehavior,
 ) -> Result<(), ParseError> {
+    if matches!(value, ArenaValue::Hole) {
+        *value = arena_initial_container(arena, expected, 0);
+        return Ok(());
+    }
+
     match expected {
         ContainerType::Array => match value {
             ArenaValue::Seq(_) => Ok(()),
@@ -27,23 +35,99 @@ pub(crate) fn arena_ensure_container<'arena>(
                 *value = ArenaValue::seq_with_capacity(arena, 0);
                 Ok(())
             }
-            ArenaValue::String(_) => Err(ParseError::NestedValueConflict {
-                parent: root_key.to_string(),
-            }),
+            ArenaValue::String(_) | ArenaValue::Null | ArenaValue::Hole => {
+                Err(ParseError::NestedValueConflict {
+                    parent: root_key.to_string(),
+                })
+            }
         },
         ContainerType::Object => match value {
             ArenaValue::Map { .. } => Ok(()),
             ArenaValue::Seq(_) => {
-                *value = ArenaValue::map_with_capacity(arena, 0);
+                arena_seq_into_map(arena, value, sparse);
                 Ok(())
             }
-            ArenaValue::String(_) => Err(ParseError::NestedValueConflict {
-                parent: root_key.to_string(),
-            }),
+            ArenaValue::String(_) | ArenaValue::Null | ArenaValue::Hole => {
+                Err(ParseError::NestedValueConflict {
+                    parent: root_key.to_string(),
+                })
+            }
         },
     }
 }
 
+pub(crate) fn arena_seq_into_map<'arena>(
+    arena: &'arena ParseArena,
+    value: &mut ArenaValue<'arena>,
+    sparse: SparseArrayBehavior,
+) {
+    if !matches!(value, ArenaValue::Seq(_)) {
+        return;
+    }
+    let ArenaValue::Seq(items) = std::mem::replace(value, ArenaValue::map(arena)) else {
+        return;
+    };
+
+    let mut object = ArenaValue::map_with_capacity(arena, items.len());
+    let (entries, index) = object
+        .map_parts_mut()
+        .expect("ArenaValue::map should produce map variant");
+
+    for (position, item) in items.into_iter().enumerate() {
+        if arena_is_placeholder(&item, sparse) {
+            continue;
+        }
+        let key_ref = arena.alloc_str(&position.to_string());
+        index.insert(key_ref, entries.len());
+        entries.push((key_ref, item));
+    }
+
+    *value = object;
+}
+
+pub(crate) fn arena_map_into_seq<'arena>(
+    arena: &'arena ParseArena,
+    value: &mut ArenaValue<'arena>,
+    len: usize,
+    kept: usize,
+    sparse: SparseArrayBehavior,
+) {
+    let ArenaValue::Map { entries, .. } = std::mem::replace(value, ArenaValue::Hole) else {
+        return;
+    };
+
+    let placeholder = arena.alloc_str("");
+    let mut items = arena.alloc_vec();
+    while items.len() < len {
+        items.push(match sparse {
+            SparseArrayBehavior::FillEmpty => ArenaValue::string(placeholder),
+            SparseArrayBehavior::KeepHoles | SparseArrayBehavior::Compact => ArenaValue::Hole,
+        });
+    }
+    for (key, item) in entries.into_iter().take(kept) {
+        if let Some(slot) = key.parse::<usize>().ok().and_then(|idx| items.get_mut(idx)) {
+            *slot = item;
+        }
+    }
+
+    *value = ArenaValue::Seq(items);
+}
+
+pub(crate) fn arena_compact_holes(value: &mut ArenaValue<'_>) {
+    match value {
+        ArenaValue::Seq(items) => {
+            items.retain(|item| !matches!(item, ArenaValue::Hole));
+            items.iter_mut().for_each(arena_compact_holes);
+        }
+        ArenaValue::Map { entries, .. } => {
+            entries
+                .iter_mut()
+                .for_each(|(_, item)| arena_compact_holes(item));
+        }
+        ArenaValue::Null | ArenaValue::Hole | ArenaValue::String(_) => {}
+    }
+}
+
 #[cfg(test)]
 #[path = "container_test.rs"]
 mod container_test;
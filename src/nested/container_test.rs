@@ -1,4 +1,8 @@
-use super::{arena_ensure_container, arena_initial_container};
+use super::{
+    arena_compact_holes, arena_ensure_container, arena_initial_container, arena_map_into_seq,
+    arena_seq_into_map,
+};
+use crate::config::SparseArrayBehavior;
 use crate::nested::segment::ContainerType;
 use crate::parsing::arena::{ArenaValue, ParseArena};
 use crate::parsing_helpers::{expect_duplicate_key, make_sequence, make_string};
@@ -50,8 +54,14 @@ mod arena_ensure_container {
         let arena = ParseArena::new();
         let mut value = make_sequence(&arena, &["existing"]);
 
-        arena_ensure_container(&arena, &mut value, ContainerType::Array, "profile")
-            .expect("sequence should satisfy expectation");
+        arena_ensure_container(
+            &arena,
+            &mut value,
+            ContainerType::Array,
+            "profile",
+            SparseArrayBehavior::FillEmpty,
+        )
+        .expect("sequence should satisfy expectation");
 
         assert_sequence_matches(&value, &["existing"]);
     }
@@ -61,8 +71,14 @@ mod arena_ensure_container {
         let arena = ParseArena::new();
         let mut value = ArenaValue::map(&arena);
 
-        arena_ensure_container(&arena, &mut value, ContainerType::Array, "profile")
-            .expect("map should convert to sequence");
+        arena_ensure_container(
+            &arena,
+            &mut value,
+            ContainerType::Array,
+            "profile",
+            SparseArrayBehavior::FillEmpty,
+        )
+        .expect("map should convert to sequence");
 
         assert_sequence_matches(&value, &[]);
     }
@@ -72,8 +88,14 @@ mod arena_ensure_container {
         let arena = ParseArena::new();
         let mut value = make_sequence(&arena, &[]);
 
-        arena_ensure_container(&arena, &mut value, ContainerType::Object, "profile")
-            .expect("sequence should convert to map");
+        arena_ensure_container(
+            &arena,
+            &mut value,
+            ContainerType::Object,
+            "profile",
+            SparseArrayBehavior::FillEmpty,
+        )
+        .expect("sequence should convert to map");
         assert_empty_map(&value);
     }
 
@@ -82,8 +104,14 @@ mod arena_ensure_container {
         let arena = ParseArena::new();
         let mut value = make_string(&arena, "leaf");
 
-        let error = arena_ensure_container(&arena, &mut value, ContainerType::Array, "profile")
-            .expect_err("string should conflict with array expectation");
+        let error = arena_ensure_container(
+            &arena,
+            &mut value,
+            ContainerType::Array,
+            "profile",
+            SparseArrayBehavior::FillEmpty,
+        )
+        .expect_err("string should conflict with array expectation");
 
         expect_duplicate_key(error, "profile");
     }
@@ -93,9 +121,111 @@ mod arena_ensure_container {
         let arena = ParseArena::new();
         let mut value = make_string(&arena, "leaf");
 
-        let error = arena_ensure_container(&arena, &mut value, ContainerType::Object, "settings")
-            .expect_err("string should conflict with object expectation");
+        let error = arena_ensure_container(
+            &arena,
+            &mut value,
+            ContainerType::Object,
+            "settings",
+            SparseArrayBehavior::FillEmpty,
+        )
+        .expect_err("string should conflict with object expectation");
 
         expect_duplicate_key(error, "settings");
     }
 }
+
+mod arena_seq_into_map {
+    use super::*;
+
+    #[test]
+    fn should_key_items_by_index_when_sequence_is_promoted_then_skip_placeholders() {
+        let arena = ParseArena::new();
+        let mut value = make_sequence(&arena, &["first", "", "third"]);
+
+        arena_seq_into_map(&arena, &mut value, SparseArrayBehavior::FillEmpty);
+
+        assert_matches!(&value, ArenaValue::Map { entries, index } => {
+            let keys: Vec<&str> = entries.iter().map(|(key, _)| *key).collect();
+            assert_eq!(keys, vec!["0", "2"]);
+            assert_eq!(index.get("2"), Some(&1));
+            assert_matches!(&entries[1].1, ArenaValue::String(text) if *text == "third");
+        });
+    }
+
+    #[test]
+    fn should_leave_value_untouched_when_value_is_not_sequence_then_keep_string() {
+        let arena = ParseArena::new();
+        let mut value = make_string(&arena, "leaf");
+
+        arena_seq_into_map(&arena, &mut value, SparseArrayBehavior::FillEmpty);
+
+        assert_matches!(value, ArenaValue::String("leaf"));
+    }
+}
+
+mod arena_map_into_seq {
+    use super::*;
+
+    #[test]
+    fn should_restore_sequence_when_promoted_map_is_reverted_then_drop_later_entries() {
+        let arena = ParseArena::new();
+        let mut value = make_sequence(&arena, &["first", "", "third"]);
+        arena_seq_into_map(&arena, &mut value, SparseArrayBehavior::FillEmpty);
+        let (entries, index) = value.map_parts_mut().expect("promoted map");
+        let key = arena.alloc_str("name");
+        index.insert(key, entries.len());
+        entries.push((key, make_string(&arena, "added")));
+
+        arena_map_into_seq(&arena, &mut value, 3, 2, SparseArrayBehavior::FillEmpty);
+
+        assert_sequence_matches(&value, &["first", "", "third"]);
+    }
+
+    #[test]
+    fn should_refill_holes_when_keeping_holes_then_keep_original_positions() {
+        let arena = ParseArena::new();
+        let mut items = arena.alloc_vec();
+        items.push(make_string(&arena, "first"));
+        items.push(ArenaValue::Hole);
+        items.push(make_string(&arena, "third"));
+        let mut value = ArenaValue::Seq(items);
+        arena_seq_into_map(&arena, &mut value, SparseArrayBehavior::KeepHoles);
+
+        arena_map_into_seq(&arena, &mut value, 3, 2, SparseArrayBehavior::KeepHoles);
+
+        assert_matches!(&value, ArenaValue::Seq(items) => {
+            assert_eq!(items.len(), 3);
+            assert_matches!(items[1], ArenaValue::Hole);
+            assert_matches!(items[2], ArenaValue::String("third"));
+        });
+    }
+}
+
+mod arena_compact_holes {
+    use super::*;
+
+    #[test]
+    fn should_remove_holes_recursively_when_compacting_then_keep_value_order() {
+        let arena = ParseArena::new();
+        let mut inner = arena.alloc_vec();
+        inner.push(ArenaValue::Hole);
+        inner.push(make_string(&arena, "deep"));
+        let mut items = arena.alloc_vec();
+        items.push(ArenaValue::Hole);
+        items.push(make_string(&arena, "x"));
+        items.push(ArenaValue::Seq(inner));
+        items.push(ArenaValue::Hole);
+        let mut value = ArenaValue::Seq(items);
+
+        arena_compact_holes(&mut value);
+
+        let items = value.as_seq_slice().expect("sequence should remain");
+        assert_eq!(items.len(), 2);
+        assert!(matches!(items[0], ArenaValue::String("x")));
+        let inner = items[1]
+            .as_seq_slice()
+            .expect("nested sequence should remain");
+        assert_eq!(inner.len(), 1);
+        assert!(matches!(inner[0], ArenaValue::String("deep")));
+    }
+}
@@ -1,21 +1,79 @@
-use crate::DuplicateKeyBehavior;
+use crate::config::{
+    DuplicateKeyBehavior, MalformedPairBehavior, MixedContainerPolicy, ParseOptions,
+    SparseArrayBehavior,
+};
 use crate::parsing::ParseError;
 use crate::parsing::arena::{ArenaQueryMap, ArenaValue, ArenaVec, ParseArena};
 use hashbrown::hash_map::RawEntryMut;
 use smallvec::SmallVec;
 
-use super::container::{arena_ensure_container, arena_initial_container};
+use super::container::{
+    arena_ensure_container, arena_initial_container, arena_map_into_seq, arena_seq_into_map,
+};
 use super::key_path::KEY_PATH_INLINE_SEGMENTS;
 use super::pattern_state::PatternState;
 use super::segment::{ContainerType, ResolvedSegment, SegmentKind};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 
 thread_local! {
     static STRING_PROMOTION_SUPPRESSED: Cell<bool> = const { Cell::new(false) };
 }
 
-fn arena_is_placeholder(value: &ArenaValue<'_>) -> bool {
-    matches!(value, ArenaValue::String(s) if s.is_empty())
+pub(crate) fn arena_is_placeholder(value: &ArenaValue<'_>, sparse: SparseArrayBehavior) -> bool {
+    match value {
+        ArenaValue::Hole => true,
+        ArenaValue::String(s) => s.is_empty() && sparse == SparseArrayBehavior::FillEmpty,
+        _ => false,
+    }
+}
+
+pub(crate) fn arena_combine_value<'arena>(
+    arena: &'arena ParseArena,
+    existing: &mut ArenaValue<'arena>,
+    value: ArenaValue<'arena>,
+) -> bool {
+    match existing {
+        ArenaValue::String(_) | ArenaValue::Null | ArenaValue::Hole => {
+            let previous = std::mem::replace(existing, ArenaValue::Null);
+            let mut items = arena.alloc_vec();
+            items.push(previous);
+            arena_append_combined(&mut items, value);
+            *existing = ArenaValue::Seq(items);
+            true
+        }
+        ArenaValue::Seq(items) => {
+            arena_append_combined(items, value);
+            true
+        }
+        ArenaValue::Map { .. } => false,
+    }
+}
+
+fn arena_wrap_combined_scalar<'arena>(
+    arena: &'arena ParseArena,
+    value: &mut ArenaValue<'arena>,
+    expected: ContainerType,
+    options: &ParseOptions,
+) {
+    if options.duplicate_keys == DuplicateKeyBehavior::Combine
+        && expected == ContainerType::Array
+        && matches!(value, ArenaValue::String(_) | ArenaValue::Null)
+    {
+        let previous = std::mem::replace(value, ArenaValue::Null);
+        let mut items = arena.alloc_vec();
+        items.push(previous);
+        *value = ArenaValue::Seq(items);
+    }
+}
+
+fn arena_append_combined<'arena>(
+    items: &mut ArenaVec<'arena, ArenaValue<'arena>>,
+    value: ArenaValue<'arena>,
+) {
+    match value {
+        ArenaValue::Seq(values) => items.extend(values),
+        other => items.push(other),
+    }
 }
 
 const MAX_CHILD_CAPACITY_HINT: usize = 64;
@@ -24,9 +82,9 @@ pub(crate) fn insert_nested_value_arena<'arena>(
     arena: &'arena ParseArena,
     map: &mut ArenaQueryMap<'arena>,
     segments: &[&str],
-    value: &'arena str,
+    value: ArenaValue<'arena>,
     state: &mut PatternState,
-    duplicate_keys: DuplicateKeyBehavior,
+    options: &ParseOptions,
 ) -> Result<(), ParseError> {
     if segments.is_empty() {
         return Ok(());
@@ -36,47 +94,144 @@ pub(crate) fn insert_nested_value_arena<'arena>(
 
     if segments.len() == 1 {
         if let Some(existing) = map.get_mut(root_key) {
-            return match duplicate_keys {
+            return match options.duplicate_keys {
                 DuplicateKeyBehavior::Reject => Err(ParseError::DuplicateRootKey {
                     key: root_key.to_string(),
                 }),
                 DuplicateKeyBehavior::FirstWins => Ok(()),
                 DuplicateKeyBehavior::LastWins => {
-                    *existing = ArenaValue::string(value);
+                    *existing = value;
                     Ok(())
                 }
+                DuplicateKeyBehavior::Combine => {
+                    if arena_combine_value(arena, existing, value) {
+                        Ok(())
+                    } else {
+                        Err(ParseError::NestedValueConflict {
+                            parent: root_key.to_string(),
+                        })
+                    }
+                }
             };
         }
 
-        try_insert_or_duplicate(root_key, || {
-            map.try_insert_str(arena, root_key, ArenaValue::string(value))
-        })?;
+        try_insert_or_duplicate(root_key, || map.try_insert_str(arena, root_key, value))?;
         return Ok(());
     }
 
-    let resolved_segments = resolve_segments(state, segments)?;
-    arena_build_nested_path(
-        arena,
-        map,
-        &resolved_segments,
-        value,
-        state,
-        root_key,
-        duplicate_keys,
-    )
+    if options.malformed_pairs == MalformedPairBehavior::Reject {
+        let resolved_segments = resolve_segments(state, segments, options)?;
+        return arena_build_nested_path(
+            arena,
+            map,
+            &resolved_segments,
+            value,
+            state,
+            None,
+            root_key,
+            options,
+        );
+    }
+
+    state.begin_transaction();
+    let journal = RefCell::new(Vec::new());
+    let result = resolve_segments(state, segments, options).and_then(|resolved_segments| {
+        arena_build_nested_path(
+            arena,
+            map,
+            &resolved_segments,
+            value,
+            state,
+            Some(&journal),
+            root_key,
+            options,
+        )
+        .inspect_err(|_| {
+            arena_undo_rewrites(arena, map, &resolved_segments, journal.take(), options);
+        })
+    });
+    if result.is_ok() {
+        state.commit_transaction();
+    } else {
+        state.rollback_transaction();
+    }
+    result
+}
+
+enum RewriteUndo<'arena> {
+    Restore {
+        depth: usize,
+        previous: ArenaValue<'arena>,
+    },
+    MapIntoSeq {
+        depth: usize,
+        len: usize,
+        kept: usize,
+    },
 }
 
+fn arena_undo_rewrites<'arena>(
+    arena: &'arena ParseArena,
+    map: &mut ArenaQueryMap<'arena>,
+    segments: &[ResolvedSegment<'_>],
+    journal: Vec<RewriteUndo<'arena>>,
+    options: &ParseOptions,
+) {
+    for undo in journal.into_iter().rev() {
+        match undo {
+            RewriteUndo::Restore { depth, previous } => {
+                if let Some(node) = arena_node_at(map, &segments[..depth]) {
+                    *node = previous;
+                }
+            }
+            RewriteUndo::MapIntoSeq { depth, len, kept } => {
+                if let Some(node) = arena_node_at(map, &segments[..depth]) {
+                    arena_map_into_seq(arena, node, len, kept, options.sparse_arrays);
+                }
+            }
+        }
+    }
+}
+
+fn arena_node_at<'arena, 'map>(
+    map: &'map mut ArenaQueryMap<'arena>,
+    path: &[ResolvedSegment<'_>],
+) -> Option<&'map mut ArenaValue<'arena>> {
+    let (root, rest) = path.split_first()?;
+    let mut node = map.get_mut(root.as_str())?;
+    for segment in rest {
+        node = match node {
+            ArenaValue::Map { entries, index } => {
+                let idx = *index.get(segment.as_str())?;
+                &mut entries[idx].1
+            }
+            ArenaValue::Seq(items) => items.get_mut(segment.as_str().parse::<usize>().ok()?)?,
+            ArenaValue::String(_) | ArenaValue::Null | ArenaValue::Hole => return None,
+        };
+    }
+    Some(node)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn arena_build_nested_path<'arena>(
     arena: &'arena ParseArena,
     map: &mut ArenaQueryMap<'arena>,
     segments: &[ResolvedSegment<'_>],
-    final_value: &'arena str,
+    final_value: ArenaValue<'arena>,
     state: &PatternState,
+    journal: Option<&RefCell<Vec<RewriteUndo<'arena>>>>,
     root_key: &str,
-    duplicate_keys: DuplicateKeyBehavior,
+    options: &ParseOptions,
 ) -> Result<(), ParseError> {
     let root_segment = segments[0].as_str();
     let root_path = [root_segment];
+    let ctx = ArenaSetContext {
+        arena,
+        state,
+        journal,
+        root_key,
+        options,
+    };
 
     let container_type = state
         .container_type(&root_path)
@@ -87,7 +242,7 @@ fn arena_build_nested_path<'arena>(
         .min(MAX_CHILD_CAPACITY_HINT);
 
     if let Some(existing) = map.get_mut(root_segment) {
-        arena_ensure_container(arena, existing, container_type, root_key)?;
+        ctx.ensure_container(existing, container_type, root_path.len())?;
     } else {
         let initial = arena_initial_container(arena, container_type, capacity_hint);
         try_insert_or_duplicate(root_key, || {
@@ -96,20 +251,87 @@ fn arena_build_nested_path<'arena>(
     }
 
     let root_value = get_root_value(map, root_segment, root_key)?;
-    let ctx = ArenaSetContext {
-        arena,
-        state,
-        root_key,
-        duplicate_keys,
-    };
     arena_set_nested_value(&ctx, root_value, segments, 1, final_value)
 }
 
 struct ArenaSetContext<'arena, 'pattern> {
     arena: &'arena ParseArena,
     state: &'pattern PatternState,
+    journal: Option<&'pattern RefCell<Vec<RewriteUndo<'arena>>>>,
     root_key: &'pattern str,
-    duplicate_keys: DuplicateKeyBehavior,
+    options: &'pattern ParseOptions,
+}
+
+impl<'arena> ArenaSetContext<'arena, '_> {
+    fn ensure_container(
+        &self,
+        node: &mut ArenaValue<'arena>,
+        expected: ContainerType,
+        depth: usize,
+    ) -> Result<(), ParseError> {
+        self.record_container_rewrite(node, expected, depth);
+        arena_wrap_combined_scalar(self.arena, node, expected, self.options);
+        arena_ensure_container(
+            self.arena,
+            node,
+            expected,
+            self.root_key,
+            self.options.sparse_arrays,
+        )
+    }
+
+    fn record_container_rewrite(
+        &self,
+        node: &mut ArenaValue<'arena>,
+        expected: ContainerType,
+        depth: usize,
+    ) {
+        if self.journal.is_none() {
+            return;
+        }
+        let wraps_scalar = self.options.duplicate_keys == DuplicateKeyBehavior::Combine
+            && expected == ContainerType::Array;
+        let undo = match node {
+            ArenaValue::Hole => RewriteUndo::Restore {
+                depth,
+                previous: ArenaValue::Hole,
+            },
+            ArenaValue::String(text) if wraps_scalar => RewriteUndo::Restore {
+                depth,
+                previous: ArenaValue::String(text),
+            },
+            ArenaValue::Null if wraps_scalar => RewriteUndo::Restore {
+                depth,
+                previous: ArenaValue::Null,
+            },
+            ArenaValue::Map { .. } if expected == ContainerType::Array => RewriteUndo::Restore {
+                depth,
+                previous: std::mem::replace(node, ArenaValue::Hole),
+            },
+            ArenaValue::Seq(items) if expected == ContainerType::Object => {
+                self.seq_into_map_undo(items, depth)
+            }
+            _ => return,
+        };
+        self.record(undo);
+    }
+
+    fn seq_into_map_undo(&self, items: &[ArenaValue<'arena>], depth: usize) -> RewriteUndo<'arena> {
+        RewriteUndo::MapIntoSeq {
+            depth,
+            len: items.len(),
+            kept: items
+                .iter()
+                .filter(|item| !arena_is_placeholder(item, self.options.sparse_arrays))
+                .count(),
+        }
+    }
+
+    fn record(&self, undo: RewriteUndo<'arena>) {
+        if let Some(journal) = self.journal {
+            journal.borrow_mut().push(undo);
+        }
+    }
 }
 
 fn arena_set_nested_value<'arena>(
@@ -117,7 +339,7 @@ fn arena_set_nested_value<'arena>(
     current: &mut ArenaValue<'arena>,
     segments: &[ResolvedSegment<'_>],
     mut depth: usize,
-    final_value: &'arena str,
+    final_value: ArenaValue<'arena>,
 ) -> Result<(), ParseError> {
     if depth >= segments.len() {
         return Ok(());
@@ -130,7 +352,9 @@ fn arena_set_nested_value<'arena>(
     path.extend(segments[..depth].iter().map(|segment| segment.as_str()));
 
     loop {
-        if let NodePreparation::NeedsRetry = prepare_current_node(ctx, node, &path)? {
+        if let NodePreparation::NeedsRetry =
+            prepare_current_node(ctx, node, &path, segments[depth].kind)?
+        {
             continue;
         }
 
@@ -173,7 +397,7 @@ fn arena_set_nested_value<'arena>(
                     path.push(segment);
                 }
             },
-            ArenaValue::String(_) => {
+            ArenaValue::String(_) | ArenaValue::Null | ArenaValue::Hole => {
                 return Err(unexpected_nested_string(ctx.root_key, &path));
             }
         }
@@ -201,15 +425,31 @@ fn prepare_current_node<'arena>(
     ctx: &ArenaSetContext<'arena, '_>,
     node: &mut ArenaValue<'arena>,
     path: &[&str],
+    segment_kind: SegmentKind,
 ) -> Result<NodePreparation, ParseError> {
     let container_hint = ctx.state.container_type(path);
     if let Some(expected) = container_hint {
-        arena_ensure_container(ctx.arena, node, expected, ctx.root_key)?;
+        ctx.ensure_container(node, expected, path.len())?;
     }
 
-    if matches!(node, ArenaValue::String(_)) && should_promote_string_node() {
+    if matches!(node, ArenaValue::String(_) | ArenaValue::Null) && should_promote_string_node() {
         let container = container_hint.unwrap_or(ContainerType::Object);
-        *node = arena_initial_container(ctx.arena, container, 0);
+        let previous = std::mem::replace(node, arena_initial_container(ctx.arena, container, 0));
+        ctx.record(RewriteUndo::Restore {
+            depth: path.len(),
+            previous,
+        });
+        return Ok(NodePreparation::NeedsRetry);
+    }
+
+    if let ArenaValue::Seq(items) = node
+        && segment_kind == SegmentKind::Other
+        && ctx.options.mixed_containers == MixedContainerPolicy::PromoteToObject
+    {
+        if ctx.journal.is_some() {
+            ctx.record(ctx.seq_into_map_undo(items, path.len()));
+        }
+        arena_seq_into_map(ctx.arena, node, ctx.options.sparse_arrays);
         return Ok(NodePreparation::NeedsRetry);
     }
 
@@ -226,7 +466,7 @@ fn handle_map_segment<'arena, S>(
     depth: usize,
     segment: &str,
     is_last: bool,
-    value_to_set: &mut Option<&'arena str>,
+    value_to_set: &mut Option<ArenaValue<'arena>>,
 ) -> Result<StepOutcome, ParseError>
 where
     S: std::hash::BuildHasher,
@@ -235,7 +475,7 @@ where
         let parent = format_parent_path(ctx.root_key, path);
         match index.raw_entry_mut().from_key(segment) {
             RawEntryMut::Occupied(entry) => {
-                return match ctx.duplicate_keys {
+                return match ctx.options.duplicate_keys {
                     DuplicateKeyBehavior::Reject => Err(ParseError::DuplicateMapEntry {
                         parent: parent.clone(),
                         segment: segment.to_string(),
@@ -250,9 +490,26 @@ where
                                     parent: parent.clone(),
                                     segment: segment.to_string(),
                                 })?;
-                        entries[idx].1 = ArenaValue::string(value);
+                        entries[idx].1 = value;
                         Ok(StepOutcome::Complete)
                     }
+                    DuplicateKeyBehavior::Combine => {
+                        let idx = *entry.get();
+                        let value =
+                            value_to_set
+                                .take()
+                                .ok_or_else(|| ParseError::DuplicateMapEntry {
+                                    parent: parent.clone(),
+                                    segment: segment.to_string(),
+                                })?;
+                        if arena_combine_value(ctx.arena, &mut entries[idx].1, value) {
+                            Ok(StepOutcome::Complete)
+                        } else {
+                            Err(ParseError::NestedValueConflict {
+                                parent: format_child_path(ctx.root_key, path, segment),
+                            })
+                        }
+                    }
                 };
             }
             RawEntryMut::Vacant(vacant) => {
@@ -264,7 +521,7 @@ where
                         parent: parent.clone(),
                         segment: segment.to_string(),
                     })?;
-                entries.push((key_ref, ArenaValue::string(value)));
+                entries.push((key_ref, value));
                 vacant.insert(key_ref, idx);
                 return Ok(StepOutcome::Complete);
             }
@@ -308,7 +565,7 @@ fn visit_map_node<'arena, 'node, S>(
     depth: usize,
     segment: &str,
     is_last: bool,
-    value_to_set: &mut Option<&'arena str>,
+    value_to_set: &mut Option<ArenaValue<'arena>>,
 ) -> Result<TraversalStep<'node, 'arena>, ParseError>
 where
     S: std::hash::BuildHasher,
@@ -340,7 +597,7 @@ fn handle_seq_segment<'arena>(
     depth: usize,
     segment: &str,
     is_last: bool,
-    value_to_set: &mut Option<&'arena str>,
+    value_to_set: &mut Option<ArenaValue<'arena>>,
 ) -> Result<StepOutcome, ParseError> {
     let parent = format_parent_path(ctx.root_key, path);
     let idx = match segments[depth].kind {
@@ -361,9 +618,18 @@ fn handle_seq_segment<'arena>(
     };
 
     if idx > items.len() {
-        let placeholder = ctx.arena.alloc_str("");
-        while items.len() < idx {
-            items.push(ArenaValue::string(placeholder));
+        match ctx.options.sparse_arrays {
+            SparseArrayBehavior::FillEmpty => {
+                let placeholder = ctx.arena.alloc_str("");
+                while items.len() < idx {
+                    items.push(ArenaValue::string(placeholder));
+                }
+            }
+            SparseArrayBehavior::KeepHoles | SparseArrayBehavior::Compact => {
+                while items.len() < idx {
+                    items.push(ArenaValue::Hole);
+                }
+            }
         }
     }
 
@@ -375,11 +641,11 @@ fn handle_seq_segment<'arena>(
                     parent: parent.clone(),
                     index: idx,
                 })?;
-            items.push(ArenaValue::string(value));
+            items.push(value);
             return Ok(StepOutcome::Complete);
         }
-        if !arena_is_placeholder(&items[idx]) {
-            return match ctx.duplicate_keys {
+        if !arena_is_placeholder(&items[idx], ctx.options.sparse_arrays) {
+            return match ctx.options.duplicate_keys {
                 DuplicateKeyBehavior::Reject => Err(ParseError::DuplicateSequenceIndex {
                     parent: parent.clone(),
                     index: idx,
@@ -393,9 +659,28 @@ fn handle_seq_segment<'arena>(
                                 parent: parent.clone(),
                                 index: idx,
                             })?;
-                    items[idx] = ArenaValue::string(value);
+                    items[idx] = value;
                     Ok(StepOutcome::Complete)
                 }
+                DuplicateKeyBehavior::Combine => {
+                    let value =
+                        value_to_set
+                            .take()
+                            .ok_or_else(|| ParseError::DuplicateSequenceIndex {
+                                parent: parent.clone(),
+                                index: idx,
+                            })?;
+                    if segments[depth].kind == SegmentKind::Empty {
+                        arena_append_combined(items, value);
+                        Ok(StepOutcome::Complete)
+                    } else if arena_combine_value(ctx.arena, &mut items[idx], value) {
+                        Ok(StepOutcome::Complete)
+                    } else {
+                        Err(ParseError::NestedValueConflict {
+                            parent: format_child_path(ctx.root_key, path, segment),
+                        })
+                    }
+                }
             };
         }
         let value = value_to_set
@@ -404,14 +689,14 @@ fn handle_seq_segment<'arena>(
                 parent: parent.clone(),
                 index: idx,
             })?;
-        items[idx] = ArenaValue::string(value);
+        items[idx] = value;
         return Ok(StepOutcome::Complete);
     }
 
     let next_kind = segments[depth + 1].kind;
     let next_is_numeric = matches!(next_kind, SegmentKind::Numeric | SegmentKind::Empty);
 
-    if idx == items.len() {
+    if idx == items.len() || matches!(items[idx], ArenaValue::Hole) {
         let capacity_hint = child_capacity_hint(ctx.state, path, segment)
             .saturating_add(1)
             .min(MAX_CHILD_CAPACITY_HINT);
@@ -420,7 +705,11 @@ fn handle_seq_segment<'arena>(
         } else {
             ArenaValue::map_with_capacity(ctx.arena, capacity_hint)
         };
-        items.push(child);
+        if idx == items.len() {
+            items.push(child);
+        } else {
+            items[idx] = child;
+        }
     }
 
     if idx < items.len() && matches!(&items[idx], ArenaValue::String(s) if !s.is_empty()) {
@@ -441,7 +730,7 @@ fn visit_seq_node<'arena, 'node>(
     depth: usize,
     segment: &str,
     is_last: bool,
-    value_to_set: &mut Option<&'arena str>,
+    value_to_set: &mut Option<ArenaValue<'arena>>,
 ) -> Result<TraversalStep<'node, 'arena>, ParseError> {
     match handle_seq_segment(
         ctx,
@@ -540,6 +829,7 @@ where
 pub(crate) fn resolve_segments<'a>(
     state: &mut PatternState,
     original: &[&'a str],
+    options: &ParseOptions,
 ) -> Result<SmallVec<[ResolvedSegment<'a>; KEY_PATH_INLINE_SEGMENTS]>, ParseError> {
     if original.len() <= 1 {
         let mut out: SmallVec<[ResolvedSegment<'a>; KEY_PATH_INLINE_SEGMENTS]> =
@@ -558,8 +848,12 @@ pub(crate) fn resolve_segments<'a>(
     )));
 
     for &segment in &original[1..] {
-        let resolved_segment = state.resolve(&resolved, segment, original[0])?;
-        resolved.push(ResolvedSegment::new(resolved_segment));
+        let resolved_segment = state.resolve(&resolved, segment, original[0], options)?;
+        resolved.push(if segment.is_empty() {
+            ResolvedSegment::appended(resolved_segment)
+        } else {
+            ResolvedSegment::new(resolved_segment)
+        });
     }
 
     Ok(resolved)
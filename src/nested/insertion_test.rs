@@ -1,14 +1,16 @@
 use super::{
-    ArenaSetContext, NodePreparation, TraversalStep, arena_build_nested_path, arena_is_placeholder,
-    arena_set_nested_value, child_capacity_hint, get_root_value, handle_map_segment,
-    handle_seq_segment, insert_nested_value_arena, prepare_current_node, resolve_segments,
-    try_insert_or_duplicate, unexpected_nested_string, visit_map_node, visit_seq_node,
-    with_string_promotion_suppressed,
+    ArenaSetContext, NodePreparation, TraversalStep, arena_build_nested_path, arena_combine_value,
+    arena_is_placeholder, arena_set_nested_value, child_capacity_hint, get_root_value,
+    handle_map_segment, handle_seq_segment, insert_nested_value_arena, prepare_current_node,
+    resolve_segments, try_insert_or_duplicate, unexpected_nested_string, visit_map_node,
+    visit_seq_node, with_string_promotion_suppressed,
 };
-use crate::DuplicateKeyBehavior;
 use crate::arena_helpers::map_with_capacity;
+use crate::config::{
+    DuplicateKeyBehavior, MixedContainerPolicy, ParseOptions, SparseArrayBehavior,
+};
 use crate::nested::pattern_state::{PatternStateGuard, acquire_pattern_state};
-use crate::nested::segment::{ContainerType, ResolvedSegment};
+use crate::nested::segment::{ContainerType, ResolvedSegment, SegmentKind};
 use crate::parsing::ParseError;
 use crate::parsing::arena::{ArenaQueryMap, ArenaValue, ParseArena};
 use crate::parsing_helpers::expect_duplicate_key;
@@ -30,9 +32,9 @@ fn insert_value<'arena>(
         arena,
         map,
         path,
-        arena.alloc_str(value),
+        ArenaValue::string(arena.alloc_str(value)),
         state,
-        duplicate_keys,
+        &ParseOptions::new().duplicate_keys(duplicate_keys),
     )
 }
 
@@ -86,13 +88,14 @@ fn make_ctx<'arena, 'pattern>(
     arena: &'arena ParseArena,
     state: &'pattern PatternStateGuard,
     root: &'pattern str,
-    duplicate_keys: DuplicateKeyBehavior,
+    options: &'pattern ParseOptions,
 ) -> ArenaSetContext<'arena, 'pattern> {
     ArenaSetContext {
         arena,
         state,
+        journal: None,
         root_key: root,
-        duplicate_keys,
+        options,
     }
 }
 
@@ -105,8 +108,113 @@ mod arena_is_placeholder {
         let empty = ArenaValue::string(arena.alloc_str(""));
         let filled = ArenaValue::string(arena.alloc_str("value"));
 
-        assert!(arena_is_placeholder(&empty));
-        assert!(!arena_is_placeholder(&filled));
+        assert!(arena_is_placeholder(&empty, SparseArrayBehavior::FillEmpty));
+        assert!(!arena_is_placeholder(
+            &filled,
+            SparseArrayBehavior::FillEmpty
+        ));
+    }
+
+    #[test]
+    fn should_only_treat_holes_as_placeholders_when_holes_are_kept_then_keep_explicit_empty_values()
+    {
+        let arena = ParseArena::new();
+        let empty = ArenaValue::string(arena.alloc_str(""));
+
+        assert!(!arena_is_placeholder(
+            &empty,
+            SparseArrayBehavior::KeepHoles
+        ));
+        assert!(arena_is_placeholder(
+            &ArenaValue::Hole,
+            SparseArrayBehavior::KeepHoles
+        ));
+    }
+}
+
+mod arena_combine_value {
+    use super::*;
+
+    #[test]
+    fn should_wrap_null_into_sequence_when_existing_value_is_null_then_keep_null_first() {
+        let arena = ParseArena::new();
+        let mut value = ArenaValue::Null;
+
+        let combined =
+            arena_combine_value(&arena, &mut value, ArenaValue::string(arena.alloc_str("x")));
+
+        assert!(combined);
+        assert_matches!(value, ArenaValue::Seq(items) => {
+            assert_eq!(items.len(), 2);
+            assert_matches!(&items[0], ArenaValue::Null);
+            assert_matches!(&items[1], ArenaValue::String(text) if *text == "x");
+        });
+    }
+
+    #[test]
+    fn should_wrap_scalar_into_sequence_when_existing_value_is_string_then_keep_both_values() {
+        let arena = ParseArena::new();
+        let mut value = ArenaValue::string(arena.alloc_str("first"));
+
+        let combined = arena_combine_value(
+            &arena,
+            &mut value,
+            ArenaValue::string(arena.alloc_str("second")),
+        );
+
+        assert!(combined);
+        assert_matches!(value, ArenaValue::Seq(items) => {
+            assert_eq!(items.len(), 2);
+            assert_matches!(&items[0], ArenaValue::String(text) if *text == "first");
+            assert_matches!(&items[1], ArenaValue::String(text) if *text == "second");
+        });
+    }
+
+    #[test]
+    fn should_append_to_sequence_when_existing_value_is_sequence_then_extend_items() {
+        let arena = ParseArena::new();
+        let mut value = ArenaValue::seq_with_capacity(&arena, 0);
+        arena_combine_value(&arena, &mut value, ArenaValue::string(arena.alloc_str("a")));
+
+        let combined =
+            arena_combine_value(&arena, &mut value, ArenaValue::string(arena.alloc_str("b")));
+
+        assert!(combined);
+        assert_matches!(value, ArenaValue::Seq(items) => {
+            assert_eq!(items.len(), 2);
+            assert_matches!(&items[1], ArenaValue::String(text) if *text == "b");
+        });
+    }
+
+    #[test]
+    fn should_refuse_when_existing_value_is_map_then_leave_map_untouched() {
+        let arena = ParseArena::new();
+        let mut value = ArenaValue::map(&arena);
+
+        let combined =
+            arena_combine_value(&arena, &mut value, ArenaValue::string(arena.alloc_str("x")));
+
+        assert!(!combined);
+        assert_matches!(value, ArenaValue::Map { .. });
+    }
+
+    #[test]
+    fn should_flatten_incoming_sequence_when_combining_then_append_each_item() {
+        let arena = ParseArena::new();
+        let mut value = ArenaValue::string(arena.alloc_str("first"));
+        let mut incoming = ArenaValue::seq_with_capacity(&arena, 2);
+        if let ArenaValue::Seq(items) = &mut incoming {
+            items.push(ArenaValue::string(arena.alloc_str("second")));
+            items.push(ArenaValue::string(arena.alloc_str("third")));
+        }
+
+        let combined = arena_combine_value(&arena, &mut value, incoming);
+
+        assert!(combined);
+        assert_matches!(value, ArenaValue::Seq(items) => {
+            assert_eq!(items.len(), 3);
+            assert_matches!(&items[2], ArenaValue::String(text) if *text == "third");
+        });
     }
 }
 
@@ -123,9 +231,9 @@ mod insert_nested_value_arena {
             &arena,
             &mut map,
             &[],
-            arena.alloc_str("ignored"),
+            ArenaValue::string(arena.alloc_str("ignored")),
             &mut state,
-            DuplicateKeyBehavior::Reject,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject),
         )
         .expect("empty path should be ignored");
 
@@ -334,6 +442,61 @@ mod insert_nested_value_arena {
         );
     }
 
+    #[test]
+    fn should_collect_nested_map_values_when_combine_enabled_then_store_sequence() {
+        let arena = ParseArena::new();
+        let mut map = map_with_capacity(&arena, 0);
+        let mut state = acquire_pattern_state();
+        insert_sequence_values(
+            &arena,
+            &mut map,
+            &["user", "tag"],
+            &["red", "blue"],
+            &mut state,
+            DuplicateKeyBehavior::Combine,
+        );
+
+        let entries = map.entries_slice();
+        let nested = entries[0]
+            .1
+            .as_map_slice()
+            .expect("expected nested map for combined values");
+        assert_eq!(nested.len(), 1);
+        assert_matches!(&nested[0].1, ArenaValue::Seq(items) => {
+            assert_eq!(items.len(), 2);
+            assert_matches!(&items[0], ArenaValue::String(text) if *text == "red");
+            assert_matches!(&items[1], ArenaValue::String(text) if *text == "blue");
+        });
+    }
+
+    #[test]
+    fn should_reject_combine_when_nested_entry_is_map_then_report_conflict() {
+        let arena = ParseArena::new();
+        let mut map = map_with_capacity(&arena, 0);
+        let mut state = acquire_pattern_state();
+        insert_value(
+            &arena,
+            &mut map,
+            &["user", "name", "first"],
+            "neo",
+            &mut state,
+            DuplicateKeyBehavior::Combine,
+        )
+        .expect("initial nested insert");
+
+        let error = insert_value(
+            &arena,
+            &mut map,
+            &["user", "name"],
+            "anderson",
+            &mut state,
+            DuplicateKeyBehavior::Combine,
+        )
+        .expect_err("combining scalar with map should fail");
+
+        assert_matches!(error, ParseError::NestedValueConflict { .. });
+    }
+
     #[test]
     fn should_keep_nested_map_value_when_first_wins_then_preserve_existing_value() {
         let arena = ParseArena::new();
@@ -486,7 +649,10 @@ mod insert_nested_value_arena {
             .as_seq_slice()
             .expect("expected sequence container for sparse insert");
         assert_eq!(sequence.len(), 2);
-        assert!(arena_is_placeholder(&sequence[0]));
+        assert!(arena_is_placeholder(
+            &sequence[0],
+            SparseArrayBehavior::FillEmpty
+        ));
         assert!(matches!(sequence[1], ArenaValue::String(text) if text == "late"));
     }
 
@@ -573,16 +739,18 @@ mod arena_build_nested_path {
         let arena = ParseArena::new();
         let mut map = map_with_capacity(&arena, 0);
         let mut state = acquire_pattern_state();
-        let resolved = resolve_segments(&mut state, &["profile", "name"]).expect("resolve");
+        let resolved = resolve_segments(&mut state, &["profile", "name"], &ParseOptions::default())
+            .expect("resolve");
 
         arena_build_nested_path(
             &arena,
             &mut map,
             &resolved,
-            arena.alloc_str("neo"),
+            ArenaValue::string(arena.alloc_str("neo")),
             &state,
+            None,
             "profile",
-            DuplicateKeyBehavior::LastWins,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins),
         )
         .expect("should build nested path");
 
@@ -609,16 +777,19 @@ mod arena_build_nested_path {
         map.try_insert_str(&arena, "profile", ArenaValue::map(&arena))
             .expect("seed profile container");
         let mut state = acquire_pattern_state();
-        let resolved = resolve_segments(&mut state, &["profile", "email"]).expect("resolve");
+        let resolved =
+            resolve_segments(&mut state, &["profile", "email"], &ParseOptions::default())
+                .expect("resolve");
 
         arena_build_nested_path(
             &arena,
             &mut map,
             &resolved,
-            arena.alloc_str("primary@example.com"),
+            ArenaValue::string(arena.alloc_str("primary@example.com")),
             &state,
+            None,
             "profile",
-            DuplicateKeyBehavior::LastWins,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins),
         )
         .expect("should insert into existing root container");
 
@@ -645,8 +816,9 @@ mod arena_set_nested_value {
         let ctx = ArenaSetContext {
             arena: &arena,
             state: &state,
+            journal: None,
             root_key: "root",
-            duplicate_keys: DuplicateKeyBehavior::LastWins,
+            options: &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins),
         };
 
         arena_set_nested_value(
@@ -654,7 +826,7 @@ mod arena_set_nested_value {
             &mut current,
             &segments,
             segments.len(),
-            arena.alloc_str("ignored"),
+            ArenaValue::string(arena.alloc_str("ignored")),
         )
         .expect("depth exhaustion should succeed");
 
@@ -677,12 +849,19 @@ mod arena_set_nested_value {
         let ctx = ArenaSetContext {
             arena: &arena,
             state: &state,
+            journal: None,
             root_key: "root",
-            duplicate_keys: DuplicateKeyBehavior::LastWins,
+            options: &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins),
         };
 
-        arena_set_nested_value(&ctx, &mut current, &segments, 1, arena.alloc_str("leaf"))
-            .expect("string node should promote to map");
+        arena_set_nested_value(
+            &ctx,
+            &mut current,
+            &segments,
+            1,
+            ArenaValue::string(arena.alloc_str("leaf")),
+        )
+        .expect("string node should promote to map");
 
         let entries = current
             .as_map_slice()
@@ -695,7 +874,8 @@ mod arena_set_nested_value {
     fn should_use_container_hint_to_convert_sequence_into_object_then_store_nested_value() {
         let arena = ParseArena::new();
         let mut state = acquire_pattern_state();
-        let resolved = resolve_segments(&mut state, &["items", "name"]).expect("resolve");
+        let resolved = resolve_segments(&mut state, &["items", "name"], &ParseOptions::default())
+            .expect("resolve");
         assert_eq!(
             state.container_type(&["items"]),
             Some(ContainerType::Object)
@@ -704,12 +884,19 @@ mod arena_set_nested_value {
         let ctx = ArenaSetContext {
             arena: &arena,
             state: &state,
+            journal: None,
             root_key: "items",
-            duplicate_keys: DuplicateKeyBehavior::LastWins,
+            options: &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins),
         };
 
-        arena_set_nested_value(&ctx, &mut current, &resolved, 1, arena.alloc_str("value"))
-            .expect("state hint should convert to map container");
+        arena_set_nested_value(
+            &ctx,
+            &mut current,
+            &resolved,
+            1,
+            ArenaValue::string(arena.alloc_str("value")),
+        )
+        .expect("state hint should convert to map container");
 
         let entries = current
             .as_map_slice()
@@ -723,17 +910,25 @@ mod arena_set_nested_value {
     {
         let arena = ParseArena::new();
         let mut state = acquire_pattern_state();
-        let resolved = resolve_segments(&mut state, &["root", "child"]).expect("resolve");
+        let resolved = resolve_segments(&mut state, &["root", "child"], &ParseOptions::default())
+            .expect("resolve");
         let mut current = ArenaValue::string(arena.alloc_str("leaf"));
         let ctx = ArenaSetContext {
             arena: &arena,
             state: &state,
+            journal: None,
             root_key: "root",
-            duplicate_keys: DuplicateKeyBehavior::Reject,
+            options: &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject),
         };
 
         let error = with_string_promotion_suppressed(|| {
-            arena_set_nested_value(&ctx, &mut current, &resolved, 1, arena.alloc_str("value"))
+            arena_set_nested_value(
+                &ctx,
+                &mut current,
+                &resolved,
+                1,
+                ArenaValue::string(arena.alloc_str("value")),
+            )
         })
         .expect_err("suppressed promotion should surface duplicate key error");
 
@@ -753,12 +948,19 @@ mod arena_set_nested_value {
         let ctx = ArenaSetContext {
             arena: &arena,
             state: &state,
+            journal: None,
             root_key: "root",
-            duplicate_keys: DuplicateKeyBehavior::Reject,
+            options: &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject),
         };
 
         let error = with_string_promotion_suppressed(|| {
-            arena_set_nested_value(&ctx, &mut current, &segments, 1, arena.alloc_str("value"))
+            arena_set_nested_value(
+                &ctx,
+                &mut current,
+                &segments,
+                1,
+                ArenaValue::string(arena.alloc_str("value")),
+            )
         })
         .expect_err("suppressed promotion without hints should report duplicate key");
 
@@ -773,11 +975,13 @@ mod prepare_current_node {
     fn should_promote_string_node_when_promotion_allowed_then_request_retry() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "root", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "root", &options);
         let mut node = ArenaValue::string(arena.alloc_str(""));
         let path = ["root"];
 
-        let outcome = prepare_current_node(&ctx, &mut node, &path).expect("prepare");
+        let outcome =
+            prepare_current_node(&ctx, &mut node, &path, SegmentKind::Other).expect("prepare");
 
         assert_matches!(outcome, NodePreparation::NeedsRetry);
         assert_matches!(node, ArenaValue::Map { .. });
@@ -787,13 +991,15 @@ mod prepare_current_node {
     fn should_keep_string_node_when_promotion_is_suppressed_then_mark_node_ready() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "root", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "root", &options);
         let mut node = ArenaValue::string(arena.alloc_str(""));
         let path = ["root"];
 
-        let outcome =
-            with_string_promotion_suppressed(|| prepare_current_node(&ctx, &mut node, &path))
-                .expect("prepare");
+        let outcome = with_string_promotion_suppressed(|| {
+            prepare_current_node(&ctx, &mut node, &path, SegmentKind::Other)
+        })
+        .expect("prepare");
 
         assert_matches!(outcome, NodePreparation::Ready);
         assert_matches!(node, ArenaValue::String(value) if value.is_empty());
@@ -803,13 +1009,49 @@ mod prepare_current_node {
     fn should_convert_node_to_sequence_when_array_hint_present_then_transform_container() {
         let arena = ParseArena::new();
         let mut state = acquire_pattern_state();
-        resolve_segments(&mut state, &["items", ""]).expect("resolve");
+        resolve_segments(&mut state, &["items", ""], &ParseOptions::default()).expect("resolve");
         assert_eq!(state.container_type(&["items"]), Some(ContainerType::Array));
-        let ctx = make_ctx(&arena, &state, "items", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "items", &options);
         let mut node = ArenaValue::map(&arena);
         let path = ["items"];
 
-        prepare_current_node(&ctx, &mut node, &path).expect("prepare");
+        prepare_current_node(&ctx, &mut node, &path, SegmentKind::Other).expect("prepare");
+        assert_matches!(node, ArenaValue::Seq(_));
+    }
+
+    #[test]
+    fn should_promote_sequence_to_map_when_named_segment_arrives_then_key_items_by_index() {
+        let arena = ParseArena::new();
+        let state = acquire_pattern_state();
+        let options = ParseOptions::new().mixed_containers(MixedContainerPolicy::PromoteToObject);
+        let ctx = make_ctx(&arena, &state, "items", &options);
+        let mut items = arena.alloc_vec();
+        items.push(ArenaValue::string(arena.alloc_str("x")));
+        let mut node = ArenaValue::Seq(items);
+        let path = ["items"];
+
+        let outcome =
+            prepare_current_node(&ctx, &mut node, &path, SegmentKind::Other).expect("prepare");
+
+        assert_matches!(outcome, NodePreparation::NeedsRetry);
+        let (entries, _) = node.map_parts_mut().expect("promoted map");
+        assert_eq!(entries[0].0, "0");
+    }
+
+    #[test]
+    fn should_keep_sequence_when_mixed_containers_rejected_then_mark_node_ready() {
+        let arena = ParseArena::new();
+        let state = acquire_pattern_state();
+        let options = ParseOptions::new();
+        let ctx = make_ctx(&arena, &state, "items", &options);
+        let mut node = ArenaValue::Seq(arena.alloc_vec());
+        let path = ["items"];
+
+        let outcome =
+            prepare_current_node(&ctx, &mut node, &path, SegmentKind::Other).expect("prepare");
+
+        assert_matches!(outcome, NodePreparation::Ready);
         assert_matches!(node, ArenaValue::Seq(_));
     }
 }
@@ -821,7 +1063,8 @@ mod visit_map_node {
     fn should_descend_into_new_map_child_when_entry_is_missing_then_create_child_container() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "root", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "root", &options);
         let mut entries = arena.alloc_vec();
         let mut index = HashMap::<&str, usize, RandomState>::with_capacity_and_hasher(
             0,
@@ -832,7 +1075,7 @@ mod visit_map_node {
             ResolvedSegment::new(Cow::Borrowed("child")),
         ];
         let mut path = SmallVec::<[&str; 16]>::new();
-        let mut value_to_set = Some(arena.alloc_str("pending"));
+        let mut value_to_set = Some(ArenaValue::string(arena.alloc_str("pending")));
 
         let step = visit_map_node(
             &ctx,
@@ -857,7 +1100,8 @@ mod visit_map_node {
     fn should_complete_traversal_when_leaf_value_inserted_then_store_scalar_value() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "root", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "root", &options);
         let mut entries = arena.alloc_vec();
         let mut index = HashMap::<&str, usize, RandomState>::with_capacity_and_hasher(
             0,
@@ -865,7 +1109,7 @@ mod visit_map_node {
         );
         let segments = [ResolvedSegment::new(Cow::Borrowed("root"))];
         let mut path = SmallVec::<[&str; 16]>::new();
-        let mut value_to_set = Some(arena.alloc_str("value"));
+        let mut value_to_set = Some(ArenaValue::string(arena.alloc_str("value")));
 
         let step = visit_map_node(
             &ctx,
@@ -897,7 +1141,8 @@ mod visit_seq_node {
     fn should_descend_into_sequence_slot_when_index_is_missing_then_allocate_container() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "items", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "items", &options);
         let mut items = arena.alloc_vec();
         let segments = [
             ResolvedSegment::new(Cow::Borrowed("items")),
@@ -906,7 +1151,7 @@ mod visit_seq_node {
         ];
         let mut path = SmallVec::<[&str; 16]>::new();
         path.push("items");
-        let mut value_to_set = Some(arena.alloc_str("leaf"));
+        let mut value_to_set = Some(ArenaValue::string(arena.alloc_str("leaf")));
 
         let step = visit_seq_node(
             &ctx,
@@ -930,7 +1175,8 @@ mod visit_seq_node {
     fn should_complete_sequence_visit_when_leaf_inserted_then_store_value() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "items", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "items", &options);
         let mut items = arena.alloc_vec();
         let segments = [
             ResolvedSegment::new(Cow::Borrowed("items")),
@@ -938,7 +1184,7 @@ mod visit_seq_node {
         ];
         let mut path = SmallVec::<[&str; 16]>::new();
         path.push("items");
-        let mut value_to_set = Some(arena.alloc_str("leaf"));
+        let mut value_to_set = Some(ArenaValue::string(arena.alloc_str("leaf")));
 
         let step = visit_seq_node(
             &ctx,
@@ -968,7 +1214,8 @@ mod child_capacity_hint {
         for idx in 0..70 {
             let segment = format!("field{idx}");
             let path = ["root", "branch", segment.as_str()];
-            resolve_segments(&mut state, &path).expect("resolve should succeed");
+            resolve_segments(&mut state, &path, &ParseOptions::default())
+                .expect("resolve should succeed");
         }
 
         let hint = child_capacity_hint(&state, &["root"], "branch");
@@ -984,7 +1231,8 @@ mod handle_map_segment {
      {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "root", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "root", &options);
         let mut entries = arena.alloc_vec();
         let mut index = HashMap::<&str, usize, RandomState>::with_capacity_and_hasher(
             0,
@@ -992,7 +1240,7 @@ mod handle_map_segment {
         );
         let segments = [ResolvedSegment::new(Cow::Borrowed("root"))];
         let mut path = SmallVec::<[&str; 16]>::new();
-        let mut missing: Option<&str> = None;
+        let mut missing: Option<ArenaValue<'_>> = None;
 
         let result = handle_map_segment(
             &ctx,
@@ -1015,7 +1263,8 @@ mod handle_map_segment {
     fn should_error_when_map_segment_updates_without_value_then_signal_duplicate() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "root", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "root", &options);
         let mut entries = arena.alloc_vec();
         let mut index = HashMap::<&str, usize, RandomState>::with_capacity_and_hasher(
             0,
@@ -1023,7 +1272,7 @@ mod handle_map_segment {
         );
         let segments = [ResolvedSegment::new(Cow::Borrowed("root"))];
         let mut path = SmallVec::<[&str; 16]>::new();
-        let mut initial = Some(arena.alloc_str("first"));
+        let mut initial = Some(ArenaValue::string(arena.alloc_str("first")));
         handle_map_segment(
             &ctx,
             &mut entries,
@@ -1037,7 +1286,7 @@ mod handle_map_segment {
         )
         .expect("initial insert should succeed");
 
-        let mut missing: Option<&str> = None;
+        let mut missing: Option<ArenaValue<'_>> = None;
         let result = handle_map_segment(
             &ctx,
             &mut entries,
@@ -1059,7 +1308,8 @@ mod handle_map_segment {
     fn should_replace_map_entry_when_last_wins_allows_duplicate_key_then_store_latest_value() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "root", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "root", &options);
         let mut entries = arena.alloc_vec();
         let mut index = HashMap::<&str, usize, RandomState>::with_capacity_and_hasher(
             0,
@@ -1067,7 +1317,7 @@ mod handle_map_segment {
         );
         let segments = [ResolvedSegment::new(Cow::Borrowed("root"))];
         let mut path = SmallVec::<[&str; 16]>::new();
-        let mut initial = Some(arena.alloc_str("first"));
+        let mut initial = Some(ArenaValue::string(arena.alloc_str("first")));
         handle_map_segment(
             &ctx,
             &mut entries,
@@ -1081,7 +1331,7 @@ mod handle_map_segment {
         )
         .expect("initial insert should record value");
 
-        let mut replacement = Some(arena.alloc_str("second"));
+        let mut replacement = Some(ArenaValue::string(arena.alloc_str("second")));
         handle_map_segment(
             &ctx,
             &mut entries,
@@ -1112,12 +1362,13 @@ mod handle_seq_segment {
     fn should_error_when_numeric_segment_overflows_then_report_duplicate_key() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "items", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "items", &options);
         let mut items = arena.alloc_vec();
         let overflow = "184467440737095516160";
         let segments = [ResolvedSegment::new(Cow::Borrowed(overflow))];
         let mut path = SmallVec::<[&str; 16]>::new();
-        let mut value_to_set = Some(arena.alloc_str("value"));
+        let mut value_to_set = Some(ArenaValue::string(arena.alloc_str("value")));
 
         let result = handle_seq_segment(
             &ctx,
@@ -1139,12 +1390,13 @@ mod handle_seq_segment {
     fn should_error_when_last_wins_sequence_missing_value_then_signal_duplicate() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "items", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "items", &options);
         let mut items = arena.alloc_vec();
         items.push(ArenaValue::string(arena.alloc_str("existing")));
         let segments = [ResolvedSegment::new(Cow::Borrowed("0"))];
         let mut path = SmallVec::<[&str; 16]>::new();
-        let mut missing: Option<&str> = None;
+        let mut missing: Option<ArenaValue<'_>> = None;
 
         let result = handle_seq_segment(
             &ctx,
@@ -1166,11 +1418,12 @@ mod handle_seq_segment {
     fn should_error_when_sequence_segment_is_non_numeric_then_signal_duplicate_key() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "items", DuplicateKeyBehavior::Reject);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject);
+        let ctx = make_ctx(&arena, &state, "items", &options);
         let mut items = arena.alloc_vec();
         let segments = [ResolvedSegment::new(Cow::Borrowed("alpha"))];
         let mut path = SmallVec::<[&str; 16]>::new();
-        let mut value_to_set = Some(arena.alloc_str("value"));
+        let mut value_to_set = Some(ArenaValue::string(arena.alloc_str("value")));
 
         let result = handle_seq_segment(
             &ctx,
@@ -1192,11 +1445,12 @@ mod handle_seq_segment {
     fn should_error_when_sequence_append_missing_value_then_report_duplicate_key() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "items", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "items", &options);
         let mut items = arena.alloc_vec();
         let segments = [ResolvedSegment::new(Cow::Borrowed("0"))];
         let mut path = SmallVec::<[&str; 16]>::new();
-        let mut value_to_set: Option<&str> = None;
+        let mut value_to_set: Option<ArenaValue<'_>> = None;
 
         let result = handle_seq_segment(
             &ctx,
@@ -1218,12 +1472,13 @@ mod handle_seq_segment {
     fn should_error_when_sequence_placeholder_missing_value_then_report_duplicate_key() {
         let arena = ParseArena::new();
         let state = acquire_pattern_state();
-        let ctx = make_ctx(&arena, &state, "items", DuplicateKeyBehavior::LastWins);
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins);
+        let ctx = make_ctx(&arena, &state, "items", &options);
         let mut items = arena.alloc_vec();
         items.push(ArenaValue::string(arena.alloc_str("")));
         let segments = [ResolvedSegment::new(Cow::Borrowed("0"))];
         let mut path = SmallVec::<[&str; 16]>::new();
-        let mut value_to_set: Option<&str> = None;
+        let mut value_to_set: Option<ArenaValue<'_>> = None;
 
         let result = handle_seq_segment(
             &ctx,
@@ -1293,8 +1548,10 @@ mod resolve_segments {
         let mut state = acquire_pattern_state();
         let path = ["items", "", "name"];
 
-        let first = resolve_segments(&mut state, &path).expect("first resolve");
-        let second = resolve_segments(&mut state, &path).expect("second resolve");
+        let first =
+            resolve_segments(&mut state, &path, &ParseOptions::default()).expect("first resolve");
+        let second =
+            resolve_segments(&mut state, &path, &ParseOptions::default()).expect("second resolve");
 
         assert_eq!(first[1].as_str(), "0");
         assert_eq!(second[1].as_str(), "1");
@@ -1305,7 +1562,8 @@ mod resolve_segments {
         let mut state = acquire_pattern_state();
         let path = ["profile", "name"];
 
-        let resolved = resolve_segments(&mut state, &path).expect("resolve");
+        let resolved =
+            resolve_segments(&mut state, &path, &ParseOptions::default()).expect("resolve");
 
         assert_eq!(resolved.len(), path.len());
         assert_eq!(resolved[0].as_str(), "profile");
@@ -1318,7 +1576,8 @@ mod resolve_segments {
         let mut state = acquire_pattern_state();
         let path = ["token"];
 
-        let resolved = resolve_segments(&mut state, &path).expect("resolve");
+        let resolved =
+            resolve_segments(&mut state, &path, &ParseOptions::default()).expect("resolve");
 
         assert_eq!(resolved.len(), 1);
         assert_eq!(resolved[0].as_str(), "token");
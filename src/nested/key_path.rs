@@ -3,17 +3,35 @@ use smallvec::SmallVec;
 
 pub(crate) const KEY_PATH_INLINE_SEGMENTS: usize = 16;
 
-pub fn parse_key_path(key: &str) -> SmallVec<[&str; KEY_PATH_INLINE_SEGMENTS]> {
+pub fn parse_key_path(
+    key: &str,
+    collapse_after: Option<usize>,
+) -> SmallVec<[&str; KEY_PATH_INLINE_SEGMENTS]> {
     let mut segments: SmallVec<[&str; KEY_PATH_INLINE_SEGMENTS]> = SmallVec::new();
     let bytes = key.as_bytes();
     let mut start = 0usize;
     let mut cursor = 0usize;
+    let mut depth = 0usize;
 
     while cursor < bytes.len() {
         if bytes[cursor] == b'[' {
             if start < cursor {
                 segments.push(&key[start..cursor]);
             }
+            if collapse_after.is_some_and(|limit| depth >= limit) {
+                let remainder = &key[cursor..];
+                match remainder.strip_suffix("[]") {
+                    Some(collapsed) => {
+                        if !collapsed.is_empty() {
+                            segments.push(collapsed);
+                        }
+                        segments.push("");
+                    }
+                    None => segments.push(remainder),
+                }
+                return segments;
+            }
+            depth += 1;
             cursor += 1;
             start = cursor;
             if cursor >= bytes.len() {
@@ -8,7 +8,7 @@ mod parse_key_path {
      {
         let input = "profile";
 
-        let segments = parse_key_path(input);
+        let segments = parse_key_path(input, None);
 
         assert_eq!(segments.as_slice(), ["profile"]);
     }
@@ -18,7 +18,7 @@ mod parse_key_path {
      {
         let input = "user[0][name]";
 
-        let segments = parse_key_path(input);
+        let segments = parse_key_path(input, None);
 
         assert_eq!(segments.as_slice(), ["user", "0", "name"]);
     }
@@ -28,7 +28,7 @@ mod parse_key_path {
      {
         let input = "items[42]status";
 
-        let segments = parse_key_path(input);
+        let segments = parse_key_path(input, None);
 
         assert_eq!(segments.as_slice(), ["items", "42", "status"]);
     }
@@ -38,7 +38,7 @@ mod parse_key_path {
      {
         let input = "flag[]";
 
-        let segments = parse_key_path(input);
+        let segments = parse_key_path(input, None);
 
         assert_eq!(segments.as_slice(), ["flag", ""]);
     }
@@ -47,7 +47,7 @@ mod parse_key_path {
     fn should_collect_remaining_text_when_bracket_is_unmatched_then_append_remaining_text() {
         let input = "foo[bar";
 
-        let segments = parse_key_path(input);
+        let segments = parse_key_path(input, None);
 
         assert_eq!(segments.as_slice(), ["foo", "bar"]);
     }
@@ -56,7 +56,7 @@ mod parse_key_path {
     fn should_stop_when_bracket_opens_at_end_then_ignore_incomplete_segment() {
         let input = std::hint::black_box("orphan[");
 
-        let segments = parse_key_path(input);
+        let segments = parse_key_path(input, None);
 
         assert_eq!(segments.as_slice(), ["orphan"]);
     }
@@ -65,8 +65,44 @@ mod parse_key_path {
     fn should_return_empty_segments_when_input_is_empty_then_return_empty_result() {
         let input = "";
 
-        let segments = parse_key_path(input);
+        let segments = parse_key_path(input, None);
 
         assert!(segments.is_empty());
     }
+
+    #[test]
+    fn should_collapse_remaining_brackets_when_depth_limit_reached_then_keep_literal_tail() {
+        let input = "a[b][c][d][e]";
+
+        let segments = parse_key_path(input, Some(2));
+
+        assert_eq!(segments.as_slice(), ["a", "b", "c", "[d][e]"]);
+    }
+
+    #[test]
+    fn should_keep_trailing_append_when_collapsing_then_collapse_only_preceding_brackets() {
+        let append_only = parse_key_path("a[b][c][]", Some(2));
+        let with_tail = parse_key_path("a[b][c][d][]", Some(2));
+
+        assert_eq!(append_only.as_slice(), ["a", "b", "c", ""]);
+        assert_eq!(with_tail.as_slice(), ["a", "b", "c", "[d]", ""]);
+    }
+
+    #[test]
+    fn should_keep_trailing_text_before_tail_when_collapsing_then_split_suffix_segment() {
+        let input = "items[0]status[x]";
+
+        let segments = parse_key_path(input, Some(1));
+
+        assert_eq!(segments.as_slice(), ["items", "0", "status", "[x]"]);
+    }
+
+    #[test]
+    fn should_parse_all_segments_when_depth_within_limit_then_leave_path_unchanged() {
+        let input = "a[b][c]";
+
+        let segments = parse_key_path(input, Some(2));
+
+        assert_eq!(segments.as_slice(), ["a", "b", "c"]);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::config::{ArrayLimitBehavior, MixedContainerPolicy, ParseOptions};
 use crate::parsing::ParseError;
 use ahash::AHashMap;
 use std::borrow::Cow;
@@ -59,6 +60,8 @@ pub(crate) struct PatternState {
     nodes: Vec<PathNode>,
     dirty_nodes: Vec<usize>,
     free_nodes: Vec<usize>,
+    journal: Vec<UndoEntry>,
+    journaling: bool,
 }
 
 impl Default for PatternState {
@@ -67,6 +70,8 @@ impl Default for PatternState {
             nodes: vec![PathNode::default()],
             dirty_nodes: Vec::new(),
             free_nodes: Vec::new(),
+            journal: Vec::new(),
+            journaling: false,
         }
     }
 }
@@ -75,10 +80,25 @@ impl Default for PatternState {
 struct PathNode {
     kind: Option<SegmentKind>,
     next_index: usize,
+    promoted: bool,
     children: AHashMap<SegmentKey, usize>,
     dirty: bool,
 }
 
+#[derive(Debug)]
+enum UndoEntry {
+    Node {
+        idx: usize,
+        kind: Option<SegmentKind>,
+        next_index: usize,
+        promoted: bool,
+    },
+    Child {
+        parent: usize,
+        key: SegmentKey,
+    },
+}
+
 impl PatternState {
     fn mark_tracked(&mut self, idx: usize) {
         let node = &mut self.nodes[idx];
@@ -93,6 +113,7 @@ impl PatternState {
             let node = &mut self.nodes[idx];
             node.kind = None;
             node.next_index = 0;
+            node.promoted = false;
             node.children.clear();
             node.dirty = false;
             idx
@@ -112,9 +133,59 @@ impl PatternState {
         self.nodes[parent_idx]
             .children
             .insert(SegmentKey::new(key), idx);
+        if self.journaling {
+            self.journal.push(UndoEntry::Child {
+                parent: parent_idx,
+                key: SegmentKey::new(key),
+            });
+        }
         idx
     }
 
+    fn record_node(&mut self, idx: usize) {
+        if self.journaling {
+            let node = &self.nodes[idx];
+            self.journal.push(UndoEntry::Node {
+                idx,
+                kind: node.kind,
+                next_index: node.next_index,
+                promoted: node.promoted,
+            });
+        }
+    }
+
+    pub(crate) fn begin_transaction(&mut self) {
+        self.journal.clear();
+        self.journaling = true;
+    }
+
+    pub(crate) fn commit_transaction(&mut self) {
+        self.journal.clear();
+        self.journaling = false;
+    }
+
+    pub(crate) fn rollback_transaction(&mut self) {
+        while let Some(entry) = self.journal.pop() {
+            match entry {
+                UndoEntry::Node {
+                    idx,
+                    kind,
+                    next_index,
+                    promoted,
+                } => {
+                    let node = &mut self.nodes[idx];
+                    node.kind = kind;
+                    node.next_index = next_index;
+                    node.promoted = promoted;
+                }
+                UndoEntry::Child { parent, key } => {
+                    self.nodes[parent].children.remove(&key);
+                }
+            }
+        }
+        self.journaling = false;
+    }
+
     fn descend_index(&self, path: &[&str]) -> Option<usize> {
         let mut idx = 0usize;
         for segment in path {
@@ -125,10 +196,13 @@ impl PatternState {
     }
 
     fn reset(&mut self) {
+        self.journal.clear();
+        self.journaling = false;
         while let Some(idx) = self.dirty_nodes.pop() {
             let node = &mut self.nodes[idx];
             node.kind = None;
             node.next_index = 0;
+            node.promoted = false;
             node.dirty = false;
             node.children.clear();
             if idx != 0 {
@@ -142,6 +216,7 @@ impl PatternState {
         container_path: &[ResolvedSegment<'_>],
         segment: &'a str,
         root_key: &str,
+        options: &ParseOptions,
     ) -> Result<Cow<'a, str>, ParseError> {
         let mut current = 0usize;
         self.mark_tracked(current);
@@ -153,27 +228,67 @@ impl PatternState {
         }
 
         let kind = SegmentKind::classify(segment);
+        self.record_node(current);
 
         let generated = {
             let node = &mut self.nodes[current];
-            match node.kind {
-                Some(existing) if existing != kind => {
-                    let parent = format_resolved_path(root_key, container_path);
-                    return Err(ParseError::KeyPatternConflict {
-                        parent,
-                        segment: segment.to_string(),
-                    });
+            if !node.promoted && exceeds_array_limit(node, kind, segment, options.array_limit) {
+                match options.array_limit_behavior {
+                    ArrayLimitBehavior::Reject => {
+                        let parent = format_resolved_path(root_key, container_path);
+                        let segment = match kind {
+                            SegmentKind::Empty => node.next_index.to_string(),
+                            _ => segment.to_string(),
+                        };
+                        return Err(ParseError::ArrayIndexTooLarge {
+                            parent,
+                            segment,
+                            limit: options.array_limit.unwrap_or_default(),
+                        });
+                    }
+                    ArrayLimitBehavior::ConvertToObject => {
+                        node.promoted = true;
+                        node.kind = Some(SegmentKind::Other);
+                    }
                 }
-                Some(_) => {}
-                None => node.kind = Some(kind),
             }
 
-            if let SegmentKind::Empty = kind {
-                let idx = node.next_index;
-                node.next_index += 1;
-                Some(idx.to_string())
-            } else {
-                None
+            if !node.promoted {
+                match node.kind {
+                    Some(existing)
+                        if existing != kind
+                            && options.mixed_containers
+                                == MixedContainerPolicy::PromoteToObject
+                            && (existing == SegmentKind::Other || kind == SegmentKind::Other) =>
+                    {
+                        node.promoted = true;
+                        node.kind = Some(SegmentKind::Other);
+                    }
+                    Some(existing) if existing != kind => {
+                        let parent = format_resolved_path(root_key, container_path);
+                        return Err(ParseError::KeyPatternConflict {
+                            parent,
+                            segment: segment.to_string(),
+                        });
+                    }
+                    Some(_) => {}
+                    None => node.kind = Some(kind),
+                }
+            }
+
+            match kind {
+                SegmentKind::Empty => {
+                    let idx = node.next_index;
+                    node.next_index += 1;
+                    Some(idx.to_string())
+                }
+                SegmentKind::Numeric => {
+                    if let Ok(index) = segment.parse::<usize>() {
+                        node.next_index = node.next_index.max(index.saturating_add(1));
+                    }
+                    None
+                }
+                SegmentKind::Other => None,
             }
         };
 
@@ -200,6 +315,23 @@ impl PatternState {
     }
 }
 
+fn exceeds_array_limit(
+    node: &PathNode,
+    kind: SegmentKind,
+    segment: &str,
+    limit: Option<usize>,
+) -> bool {
+    let Some(limit) = limit else {
+        return false;
+    };
+
+    match kind {
+        SegmentKind::Empty => node.next_index > limit,
+        SegmentKind::Numeric => segment.parse::<usize>().map_or(true, |index| index > limit),
+        SegmentKind::Other => false,
+    }
+}
+
 fn format_resolved_path(root: &str, path: &[ResolvedSegment<'_>]) -> String {
     if path.is_empty() {
         return root.to_string();
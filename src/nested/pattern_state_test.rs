@@ -1,5 +1,8 @@
 use super::{ContainerType, PatternStateGuard, ResolvedSegment, acquire_pattern_state};
+use crate::config::{ArrayLimitBehavior, MixedContainerPolicy, ParseOptions};
+use crate::parsing::ParseError;
 use crate::parsing_helpers::expect_duplicate_key;
+use assert_matches::assert_matches;
 use std::borrow::Cow;
 
 fn make_segments<'a>(parts: &'a [&'a str]) -> Vec<ResolvedSegment<'a>> {
@@ -15,7 +18,7 @@ fn resolve_numeric<'a>(
     alias: &str,
 ) -> Cow<'a, str> {
     guard
-        .resolve(path, "", alias)
+        .resolve(path, "", alias, &ParseOptions::default())
         .expect("numeric segment should resolve")
 }
 
@@ -41,13 +44,152 @@ mod resolve {
         resolve_numeric(&mut guard, &path, "items");
 
         let error = guard
-            .resolve(&path, "field", "items")
+            .resolve(&path, "field", "items", &ParseOptions::default())
             .expect_err("conflict");
 
         expect_duplicate_key(error, "field");
     }
 }
 
+mod resolve_with_array_limit {
+    use super::*;
+
+    fn limited(behavior: ArrayLimitBehavior) -> ParseOptions {
+        ParseOptions::new()
+            .array_limit(2)
+            .array_limit_behavior(behavior)
+    }
+
+    #[test]
+    fn should_reject_index_when_limit_exceeded_then_return_array_index_too_large() {
+        let mut guard = acquire_pattern_state();
+        let path = make_segments(&["items"]);
+        let options = limited(ArrayLimitBehavior::Reject);
+
+        let error = guard
+            .resolve(&path, "99999999", "items", &options)
+            .expect_err("index above limit should be rejected");
+
+        assert_matches!(
+            error,
+            ParseError::ArrayIndexTooLarge { parent, segment, limit }
+                if parent == "items[items]" && segment == "99999999" && limit == 2
+        );
+    }
+
+    #[test]
+    fn should_reject_generated_index_when_appends_exceed_limit_then_report_generated_index() {
+        let mut guard = acquire_pattern_state();
+        let path = make_segments(&["items"]);
+        let options = limited(ArrayLimitBehavior::Reject);
+        for _ in 0..3 {
+            guard
+                .resolve(&path, "", "items", &options)
+                .expect("within limit");
+        }
+
+        let error = guard
+            .resolve(&path, "", "items", &options)
+            .expect_err("fourth append should exceed limit");
+
+        assert_matches!(error, ParseError::ArrayIndexTooLarge { segment, .. } if segment == "3");
+    }
+
+    #[test]
+    fn should_promote_container_when_converting_to_object_then_accept_numeric_and_named_keys() {
+        let mut guard = acquire_pattern_state();
+        let path = make_segments(&["items"]);
+        let options = limited(ArrayLimitBehavior::ConvertToObject);
+        guard.resolve(&path, "0", "items", &options).expect("index");
+        assert_eq!(guard.container_type(&["items"]), Some(ContainerType::Array));
+
+        let promoted = guard
+            .resolve(&path, "500", "items", &options)
+            .expect("overflow should promote");
+        let named = guard
+            .resolve(&path, "name", "items", &options)
+            .expect("promoted container accepts names");
+
+        assert_eq!(promoted, "500");
+        assert_eq!(named, "name");
+        assert_eq!(
+            guard.container_type(&["items"]),
+            Some(ContainerType::Object)
+        );
+    }
+}
+
+mod resolve_with_mixed_containers {
+    use super::*;
+
+    #[test]
+    fn should_reject_named_segment_when_policy_rejects_then_return_key_pattern_conflict() {
+        let mut guard = acquire_pattern_state();
+        let path = make_segments(&["items"]);
+        let options = ParseOptions::new();
+        guard.resolve(&path, "0", "items", &options).expect("index");
+
+        let error = guard
+            .resolve(&path, "name", "items", &options)
+            .expect_err("mixed segments should conflict");
+
+        assert_matches!(error, ParseError::KeyPatternConflict { segment, .. } if segment == "name");
+    }
+
+    #[test]
+    fn should_promote_container_when_named_segment_follows_index_then_select_object() {
+        let mut guard = acquire_pattern_state();
+        let path = make_segments(&["items"]);
+        let options = ParseOptions::new().mixed_containers(MixedContainerPolicy::PromoteToObject);
+        guard.resolve(&path, "0", "items", &options).expect("index");
+
+        let named = guard
+            .resolve(&path, "name", "items", &options)
+            .expect("named segment should promote");
+        let index = guard
+            .resolve(&path, "1", "items", &options)
+            .expect("promoted container accepts indices");
+
+        assert_eq!(named, "name");
+        assert_eq!(index, "1");
+        assert_eq!(
+            guard.container_type(&["items"]),
+            Some(ContainerType::Object)
+        );
+    }
+
+    #[test]
+    fn should_keep_rejecting_append_after_index_when_promoting_then_return_key_pattern_conflict() {
+        let mut guard = acquire_pattern_state();
+        let path = make_segments(&["items"]);
+        let options = ParseOptions::new().mixed_containers(MixedContainerPolicy::PromoteToObject);
+        guard.resolve(&path, "0", "items", &options).expect("index");
+
+        let error = guard
+            .resolve(&path, "", "items", &options)
+            .expect_err("append after explicit index stays a conflict");
+
+        assert_matches!(error, ParseError::KeyPatternConflict { .. });
+    }
+
+    #[test]
+    fn should_append_after_highest_explicit_index_when_promoted_then_avoid_reusing_taken_keys() {
+        let mut guard = acquire_pattern_state();
+        let path = make_segments(&["items"]);
+        let options = ParseOptions::new().mixed_containers(MixedContainerPolicy::PromoteToObject);
+        guard.resolve(&path, "0", "items", &options).expect("index");
+        guard
+            .resolve(&path, "name", "items", &options)
+            .expect("named segment should promote");
+
+        let appended = guard
+            .resolve(&path, "", "items", &options)
+            .expect("append after promotion");
+
+        assert_eq!(appended, "1");
+    }
+}
+
 mod container_type {
     use super::*;
 
@@ -66,7 +208,9 @@ mod container_type {
     fn should_return_object_when_string_segments_are_seen_then_select_object_container() {
         let mut guard = acquire_pattern_state();
         let path = make_segments(&["props"]);
-        guard.resolve(&path, "field", "props").expect("string");
+        guard
+            .resolve(&path, "field", "props", &ParseOptions::default())
+            .expect("string");
 
         let container = guard.container_type(&["props"]);
 
@@ -91,6 +235,37 @@ mod child_capacity {
     }
 }
 
+mod transaction {
+    use super::*;
+
+    #[test]
+    fn should_restore_indices_and_children_when_rolled_back_then_forget_failed_resolution() {
+        let mut guard = acquire_pattern_state();
+        let path = make_segments(&["items"]);
+        resolve_numeric(&mut guard, &path, "items");
+
+        guard.begin_transaction();
+        resolve_numeric(&mut guard, &path, "items");
+        guard.rollback_transaction();
+
+        assert_eq!(guard.child_capacity(&["items"]), 1);
+        assert_eq!(resolve_numeric(&mut guard, &path, "items"), "1");
+    }
+
+    #[test]
+    fn should_keep_resolutions_when_committed_then_continue_from_new_state() {
+        let mut guard = acquire_pattern_state();
+        let path = make_segments(&["items"]);
+
+        guard.begin_transaction();
+        resolve_numeric(&mut guard, &path, "items");
+        guard.commit_transaction();
+        guard.rollback_transaction();
+
+        assert_eq!(resolve_numeric(&mut guard, &path, "items"), "1");
+    }
+}
+
 mod acquire_pattern_state {
     use super::*;
 
@@ -98,7 +273,9 @@ mod acquire_pattern_state {
     fn should_reset_state_when_guard_is_returned_to_pool_then_clear_cached_metadata() {
         {
             let mut guard = acquire_pattern_state();
-            guard.resolve(&[], "foo", "root").expect("prime pool");
+            guard
+                .resolve(&[], "foo", "root", &ParseOptions::default())
+                .expect("prime pool");
         }
 
         let guard = acquire_pattern_state();
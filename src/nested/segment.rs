@@ -57,6 +57,13 @@ impl<'a> ResolvedSegment<'a> {
         Self { text, kind }
     }
 
+    pub(crate) fn appended(text: Cow<'a, str>) -> Self {
+        Self {
+            text,
+            kind: SegmentKind::Empty,
+        }
+    }
+
     pub(crate) fn as_str(&self) -> &str {
         self.text.as_ref()
     }
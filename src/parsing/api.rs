@@ -1,19 +1,56 @@
 use std::any::TypeId;
 use std::mem::ManuallyDrop;
 
-use serde::de::DeserializeOwned;
+use serde::de::{Deserialize, DeserializeOwned};
 use serde_json::Value as JsonValue;
 
 use crate::config::ParseOptions;
-use crate::serde_adapter::{arena_map_to_json_value, deserialize_from_arena_map};
+use crate::model::QueryMap;
+use crate::serde_adapter::{
+    arena_map_to_json_value, arena_map_to_query_map, deserialize_from_arena_entries,
+};
 
-use super::builder::with_arena_query_map;
-use super::errors::ParseError;
+use super::arena::ArenaValue;
+
+use super::builder::{with_arena_query_map, with_arena_query_map_diagnostics};
+use super::errors::{ParseDiagnostic, ParseError};
 use super::preflight::preflight;
 
 pub type ParseResult<T> = Result<T, ParseError>;
 
+#[derive(Debug, Clone)]
+pub struct ParseOutcome<T> {
+    pub value: T,
+    pub unknown_fields: Vec<String>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl<T> ParseOutcome<T> {
+    fn value(value: T) -> Self {
+        Self {
+            value,
+            unknown_fields: Vec::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn with_diagnostics(mut self, diagnostics: Vec<ParseDiagnostic>) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+}
+
 pub fn parse<T>(input: impl AsRef<str>, options: &ParseOptions) -> ParseResult<T>
+where
+    T: DeserializeOwned + Default + 'static,
+{
+    parse_detailed(input, options).map(|outcome| outcome.value)
+}
+
+pub fn parse_detailed<T>(
+    input: impl AsRef<str>,
+    options: &ParseOptions,
+) -> ParseResult<ParseOutcome<T>>
 where
     T: DeserializeOwned + Default + 'static,
 {
@@ -21,23 +58,115 @@ where
     let (trimmed, offset) = preflight(raw, options)?;
 
     if trimmed.is_empty() {
-        return Ok(T::default());
+        return Ok(ParseOutcome::value(T::default()));
     }
 
-    with_arena_query_map(trimmed, offset, options, |_, arena_map| {
+    with_arena_query_map_diagnostics(trimmed, offset, options, |_, arena_map, diagnostics| {
         if arena_map.is_empty() {
-            Ok(T::default())
+            Ok(ParseOutcome::value(T::default()).with_diagnostics(diagnostics))
         } else {
             if TypeId::of::<T>() == TypeId::of::<JsonValue>() {
                 let json_value = arena_map_to_json_value(arena_map);
                 let value = unsafe { assume_json_value::<T>(json_value) };
-                return Ok(value);
+                return Ok(ParseOutcome::value(value).with_diagnostics(diagnostics));
             }
-            deserialize_from_arena_map::<T>(arena_map).map_err(ParseError::from)
+            if TypeId::of::<T>() == TypeId::of::<QueryMap>() {
+                let query_map = arena_map_to_query_map(arena_map);
+                let value = unsafe { assume_query_map::<T>(query_map) };
+                return Ok(ParseOutcome::value(value).with_diagnostics(diagnostics));
+            }
+            let (value, unknown_fields) =
+                deserialize_from_arena_entries::<T>(arena_map.entries_slice(), options)?;
+            Ok(ParseOutcome {
+                value,
+                unknown_fields,
+                diagnostics,
+            })
         }
     })
 }
 
+pub fn parse_to_query_map(input: impl AsRef<str>, options: &ParseOptions) -> ParseResult<QueryMap> {
+    let raw = input.as_ref();
+    let (trimmed, offset) = preflight(raw, options)?;
+
+    if trimmed.is_empty() {
+        return Ok(QueryMap::new());
+    }
+
+    with_arena_query_map(trimmed, offset, options, |_, arena_map| {
+        Ok(arena_map_to_query_map(arena_map))
+    })
+}
+
+#[derive(Clone, Copy)]
+pub struct ParsedQuery<'q> {
+    entries: &'q [(&'q str, ArenaValue<'q>)],
+    diagnostics: &'q [ParseDiagnostic],
+    options: &'q ParseOptions,
+}
+
+impl<'q> ParsedQuery<'q> {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn diagnostics(&self) -> &'q [ParseDiagnostic] {
+        self.diagnostics
+    }
+
+    pub fn deserialize<T>(&self) -> ParseResult<T>
+    where
+        T: Deserialize<'q> + Default,
+    {
+        self.deserialize_detailed().map(|outcome| outcome.value)
+    }
+
+    pub fn deserialize_detailed<T>(&self) -> ParseResult<ParseOutcome<T>>
+    where
+        T: Deserialize<'q> + Default,
+    {
+        let diagnostics = self.diagnostics.to_vec();
+        if self.entries.is_empty() {
+            return Ok(ParseOutcome::value(T::default()).with_diagnostics(diagnostics));
+        }
+        let (value, unknown_fields) =
+            deserialize_from_arena_entries::<T>(self.entries, self.options)?;
+        Ok(ParseOutcome {
+            value,
+            unknown_fields,
+            diagnostics,
+        })
+    }
+}
+
+pub fn with_parsed<R, F>(input: &str, options: &ParseOptions, view: F) -> ParseResult<R>
+where
+    F: for<'q> FnOnce(ParsedQuery<'q>) -> ParseResult<R>,
+{
+    let (trimmed, offset) = preflight(input, options)?;
+
+    if trimmed.is_empty() {
+        return view(ParsedQuery {
+            entries: &[],
+            diagnostics: &[],
+            options,
+        });
+    }
+
+    with_arena_query_map_diagnostics(trimmed, offset, options, |_, arena_map, diagnostics| {
+        view(ParsedQuery {
+            entries: arena_map.entries_slice(),
+            diagnostics: &diagnostics,
+            options,
+        })
+    })
+}
+
 #[inline]
 unsafe fn assume_json_value<T>(value: JsonValue) -> T
 where
@@ -49,6 +178,17 @@ where
     unsafe { ptr.read() }
 }
 
+#[inline]
+unsafe fn assume_query_map<T>(value: QueryMap) -> T
+where
+    T: 'static,
+{
+    debug_assert_eq!(TypeId::of::<T>(), TypeId::of::<QueryMap>());
+    let value = ManuallyDrop::new(value);
+    let ptr = (&*value) as *const QueryMap as *const T;
+    unsafe { ptr.read() }
+}
+
 #[cfg(test)]
 #[path = "api_test.rs"]
 mod api_test;
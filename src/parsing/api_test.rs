@@ -1,8 +1,11 @@
-use super::{assume_json_value, parse};
-use crate::ParseOptions;
+use super::{
+    assume_json_value, assume_query_map, parse, parse_detailed, parse_to_query_map, with_parsed,
+};
+use crate::model::{QueryMap, Value as QueryValue};
 use crate::parsing::ParseError;
 use crate::parsing::errors::ParseLocation;
 use crate::serde_adapter::DeserializeErrorKind;
+use crate::{MalformedPairBehavior, ParseOptions, UnknownFieldPolicy};
 use assert_matches::assert_matches;
 use serde::Deserialize;
 use serde::de::DeserializeOwned;
@@ -133,6 +136,308 @@ mod parse {
                 if limit == 4 && actual == 8
         );
     }
+
+    #[test]
+    fn given_query_map_target_when_parse_then_builds_dynamic_tree() {
+        let parsed: QueryMap = parse_with_defaults("user[name]=alice&tags[]=a&tags[]=b")
+            .expect("parse should succeed");
+
+        let user = parsed
+            .get("user")
+            .and_then(QueryValue::as_object)
+            .expect("user should be an object");
+        assert_eq!(user.get("name"), Some(&QueryValue::from("alice")));
+        assert_eq!(
+            parsed.get("tags"),
+            Some(&QueryValue::Array(vec![
+                QueryValue::from("a"),
+                QueryValue::from("b")
+            ]))
+        );
+    }
+}
+
+mod parse_detailed {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq, Eq, Default)]
+    struct Nested {
+        account: Credentials,
+    }
+
+    #[test]
+    fn given_empty_input_when_parse_detailed_then_returns_default_without_unknown_fields() {
+        let outcome = parse_detailed::<Credentials>("", &ParseOptions::default())
+            .expect("parse should succeed");
+
+        assert_eq!(outcome.value, Credentials::default());
+        assert!(outcome.unknown_fields.is_empty());
+    }
+
+    #[test]
+    fn given_collect_policy_when_nested_struct_has_extra_keys_then_report_nested_paths() {
+        let options = ParseOptions::new().unknown_fields(UnknownFieldPolicy::Collect);
+
+        let outcome = parse_detailed::<Nested>(
+            "account[username]=neo&account[password]=pw&account[otp]=1&ref=home",
+            &options,
+        )
+        .expect("parse should succeed");
+
+        assert_eq!(outcome.value.account.username, "neo");
+        assert_eq!(
+            outcome.unknown_fields,
+            vec!["account.otp".to_string(), "ref".to_string()]
+        );
+    }
+
+    #[test]
+    fn given_ignore_policy_when_with_parsed_deserializes_then_skip_extra_keys() {
+        let options = ParseOptions::new().unknown_fields(UnknownFieldPolicy::Ignore);
+
+        let username = with_parsed("username=neo&password=pw&cb=123", &options, |parsed| {
+            parsed
+                .deserialize::<Credentials>()
+                .map(|credentials| credentials.username)
+        })
+        .expect("unknown fields should be ignored");
+
+        assert_eq!(username, "neo");
+    }
+
+    #[test]
+    fn given_skip_behavior_when_pairs_are_malformed_then_report_diagnostics() {
+        let options = ParseOptions::new().malformed_pairs(MalformedPairBehavior::Skip);
+
+        let outcome = parse_detailed::<Value>("ok=1&bad=%ZZ&x[=2", &options)
+            .expect("malformed pairs should be skipped");
+
+        assert_eq!(outcome.value, json!({ "ok": "1" }));
+        assert_eq!(outcome.diagnostics.len(), 2);
+
+        let escape = &outcome.diagnostics[0];
+        assert_eq!(escape.key, "bad");
+        assert_eq!(escape.offset, 5);
+        assert_eq!(escape.location(), ParseLocation::Value);
+        assert_eq!(escape.index(), 9);
+        assert_matches!(escape.error, ParseError::InvalidPercentEncoding { .. });
+
+        let bracket = &outcome.diagnostics[1];
+        assert_eq!(bracket.key, "x[");
+        assert_eq!(bracket.offset, 13);
+        assert_eq!(bracket.location(), ParseLocation::Key);
+        assert_eq!(bracket.index(), 13);
+        assert_matches!(bracket.error, ParseError::UnmatchedBracket { .. });
+    }
+
+    #[test]
+    fn given_skip_behavior_when_pair_contains_question_mark_then_drop_only_that_pair() {
+        let options = ParseOptions::new().malformed_pairs(MalformedPairBehavior::Skip);
+
+        let outcome = parse_detailed::<Value>("a=1&b=?x&c=3", &options)
+            .expect("question mark should only affect its own pair");
+
+        assert_eq!(outcome.value, json!({ "a": "1", "c": "3" }));
+        assert_eq!(outcome.diagnostics.len(), 1);
+        assert_eq!(outcome.diagnostics[0].key, "b");
+        assert_matches!(
+            outcome.diagnostics[0].error,
+            ParseError::UnexpectedQuestionMark { index: 6, .. }
+        );
+    }
+
+    #[test]
+    fn given_reject_behavior_when_pair_is_malformed_then_return_error() {
+        let error = parse_detailed::<Value>("ok=1&bad=%ZZ", &ParseOptions::default())
+            .expect_err("malformed pair should fail");
+
+        assert_matches!(
+            error,
+            ParseError::InvalidPercentEncoding {
+                index: 9,
+                location: ParseLocation::Value
+            }
+        );
+    }
+}
+
+mod parse_to_query_map {
+    use super::*;
+
+    #[test]
+    fn given_empty_input_when_parse_to_query_map_then_returns_empty_map() {
+        let parsed =
+            parse_to_query_map("?", &ParseOptions::default()).expect("empty input should parse");
+
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn given_flat_pairs_when_parse_to_query_map_then_preserves_input_order() {
+        let parsed =
+            parse_to_query_map("b=2&a=1", &ParseOptions::default()).expect("parse should succeed");
+
+        let keys: Vec<&str> = parsed.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["b", "a"]);
+    }
+
+    #[test]
+    fn given_invalid_query_when_parse_to_query_map_then_returns_parse_error() {
+        let result = parse_to_query_map("a=%zz", &ParseOptions::default());
+
+        assert_matches!(result, Err(ParseError::InvalidPercentEncoding { .. }));
+    }
+}
+
+mod with_parsed {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize, Default)]
+    struct BorrowedCredentials<'a> {
+        username: &'a str,
+        password: &'a str,
+    }
+
+    fn points_into(input: &str, value: &str) -> bool {
+        let range = input.as_bytes().as_ptr_range();
+        range.contains(&value.as_ptr())
+    }
+
+    #[test]
+    fn given_undecoded_values_when_with_parsed_then_borrows_slices_from_input() {
+        let input = "username=neo&password=matrix";
+
+        let borrowed =
+            with_parsed(input, &ParseOptions::default(), |view| {
+                let credentials: BorrowedCredentials<'_> = view.deserialize()?;
+                assert_eq!(credentials.username, "neo");
+                assert_eq!(credentials.password, "matrix");
+                Ok(points_into(input, credentials.username)
+                    && points_into(input, credentials.password))
+            })
+            .expect("with_parsed should succeed");
+
+        assert!(borrowed);
+    }
+
+    #[test]
+    fn given_percent_encoded_values_when_with_parsed_then_borrows_decoded_text() {
+        let input = "username=neo%20anderson&password=matrix";
+
+        let username = with_parsed(input, &ParseOptions::default(), |view| {
+            let credentials: BorrowedCredentials<'_> = view.deserialize()?;
+            assert!(!points_into(input, credentials.username));
+            Ok(credentials.username.to_owned())
+        })
+        .expect("with_parsed should succeed");
+
+        assert_eq!(username, "neo anderson");
+    }
+
+    #[test]
+    fn given_nested_map_when_with_parsed_then_borrows_keys_and_values() {
+        let count = with_parsed(
+            "filter[color]=red&filter[size]=xl",
+            &ParseOptions::default(),
+            |view| {
+                let parsed: HashMap<&str, HashMap<&str, &str>> = view.deserialize()?;
+                assert_eq!(parsed["filter"]["color"], "red");
+                assert_eq!(parsed["filter"]["size"], "xl");
+                Ok(parsed["filter"].len())
+            },
+        )
+        .expect("with_parsed should succeed");
+
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn given_empty_input_when_with_parsed_then_exposes_empty_view() {
+        let (is_empty, credentials) = with_parsed("", &ParseOptions::default(), |view| {
+            let credentials: BorrowedCredentials<'_> = view.deserialize()?;
+            Ok((view.is_empty(), credentials.username.is_empty()))
+        })
+        .expect("empty input should succeed");
+
+        assert!(is_empty);
+        assert!(credentials);
+    }
+
+    #[test]
+    fn given_parse_failure_when_with_parsed_then_skips_closure_and_returns_error() {
+        let options = ParseOptions::new().max_params(1);
+
+        let error = with_parsed("a=1&b=2", &options, |_| -> Result<(), ParseError> {
+            panic!("closure should not run when parsing fails")
+        })
+        .expect_err("param limit should fail");
+
+        assert_matches!(
+            error,
+            ParseError::TooManyParameters {
+                limit: 1,
+                actual: 2
+            }
+        );
+    }
+
+    #[test]
+    fn given_type_mismatch_when_view_deserializes_then_returns_serde_error() {
+        let error = with_parsed("username[first]=neo", &ParseOptions::default(), |view| {
+            view.deserialize::<BorrowedCredentials<'_>>().map(|_| ())
+        })
+        .expect_err("nested username should not match &str field");
+
+        assert_matches!(error, ParseError::Serde(_));
+    }
+
+    #[test]
+    fn given_collect_policy_when_view_deserializes_detailed_then_report_unknown_fields() {
+        let options = ParseOptions::new().unknown_fields(UnknownFieldPolicy::Collect);
+
+        let (username, unknown_fields) =
+            with_parsed("username=neo&password=pw&cb=123", &options, |view| {
+                view.deserialize_detailed::<BorrowedCredentials<'_>>()
+                    .map(|outcome| (outcome.value.username.to_string(), outcome.unknown_fields))
+            })
+            .expect("unknown fields should be collected");
+
+        assert_eq!(username, "neo");
+        assert_eq!(unknown_fields, vec!["cb".to_string()]);
+    }
+
+    #[test]
+    fn given_skip_behavior_when_pairs_are_malformed_then_view_exposes_diagnostics() {
+        let options = ParseOptions::new().malformed_pairs(MalformedPairBehavior::Skip);
+
+        let (keys, detailed) = with_parsed("ok=1&bad=%ZZ&x[=2", &options, |view| {
+            let keys: Vec<String> = view
+                .diagnostics()
+                .iter()
+                .map(|diagnostic| diagnostic.key.clone())
+                .collect();
+            let outcome = view.deserialize_detailed::<HashMap<String, String>>()?;
+            Ok((keys, outcome.diagnostics.len()))
+        })
+        .expect("malformed pairs should be skipped");
+
+        assert_eq!(keys, vec!["bad".to_string(), "x[".to_string()]);
+        assert_eq!(detailed, 2);
+    }
+}
+
+mod assume_query_map {
+    use super::*;
+
+    #[test]
+    fn given_matching_type_when_assume_query_map_then_transfers_without_copy() {
+        let map = QueryMap::from_iter([("name", "neo")]);
+
+        let transferred: QueryMap = unsafe { assume_query_map::<QueryMap>(map.clone()) };
+
+        assert_eq!(transferred, map);
+    }
 }
 
 mod assume_json_value {
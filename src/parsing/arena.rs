@@ -206,6 +206,27 @@ impl<'arena> ArenaQueryMap<'arena> {
         }
     }
 
+    pub(crate) fn try_insert_value(
+        &mut self,
+        arena: &'arena ParseArena,
+        key: &str,
+        value: ArenaValue<'arena>,
+    ) -> Result<(), (&mut ArenaValue<'arena>, ArenaValue<'arena>)> {
+        match self.index.raw_entry_mut().from_key(key) {
+            RawEntryMut::Occupied(entry) => {
+                let idx = *entry.get();
+                Err((&mut self.entries[idx].1, value))
+            }
+            RawEntryMut::Vacant(vacant) => {
+                let key_ref = arena.alloc_str(key);
+                let idx = self.entries.len();
+                self.entries.push((key_ref, value));
+                vacant.insert(key_ref, idx);
+                Ok(())
+            }
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.entries.len()
     }
@@ -217,9 +238,15 @@ impl<'arena> ArenaQueryMap<'arena> {
     pub fn entries_slice(&self) -> &[(&'arena str, ArenaValue<'arena>)] {
         self.entries.as_slice()
     }
+
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut ArenaValue<'arena>> {
+        self.entries.iter_mut().map(|(_, value)| value)
+    }
 }
 
 pub enum ArenaValue<'arena> {
+    Null,
+    Hole,
     String(&'arena str),
     Seq(ArenaVec<'arena, ArenaValue<'arena>>),
     Map {
@@ -231,6 +258,8 @@ pub enum ArenaValue<'arena> {
 impl<'arena> std::fmt::Debug for ArenaValue<'arena> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            ArenaValue::Null => f.write_str("Null"),
+            ArenaValue::Hole => f.write_str("Hole"),
             ArenaValue::String(text) => f.debug_tuple("String").field(text).finish(),
             ArenaValue::Seq(items) => f.debug_tuple("Seq").field(&items.as_slice()).finish(),
             ArenaValue::Map { entries, .. } => f
@@ -1,13 +1,18 @@
-use crate::config::ParseOptions;
+use crate::config::{Charset, MalformedPairBehavior, ParseOptions, SparseArrayBehavior};
 use crate::memory::acquire_bytes;
+use crate::nested::container::arena_compact_holes;
 use crate::nested::pattern_state::{PatternState, acquire_pattern_state};
-use crate::parsing::{ParseError, ParseResult};
-use memchr::{memchr, memchr2};
+use crate::parsing::{ParseDiagnostic, ParseError, ParseResult};
+use memchr::{memchr, memchr_iter, memchr2, memchr3};
+use smallvec::SmallVec;
 
 use super::arena::{ArenaQueryMap, ParseArena};
 use super::key_path::estimate_param_capacity;
-use super::pair_decoder::decode_pair;
-use super::pair_inserter::insert_pair_arena;
+use super::pair_decoder::{decode_comma_values, decode_key, decode_pair};
+use super::pair_inserter::{
+    insert_comma_pair_arena, insert_null_pair_arena, insert_pair_arena, insert_raw_pair_arena,
+};
+use super::preflight::check_characters;
 use super::state::ArenaLease;
 
 struct ParseContext<'arena, 'options, 'map, 'pattern, 'scratch> {
@@ -15,10 +20,12 @@ struct ParseContext<'arena, 'options, 'map, 'pattern, 'scratch> {
     arena_map: &'map mut ArenaQueryMap<'arena>,
     pattern_state: &'pattern mut PatternState,
     options: &'options ParseOptions,
-    trimmed: &'options str,
+    charset: Charset,
+    trimmed: &'arena str,
     offset: usize,
     decode_scratch: &'scratch mut Vec<u8>,
     pairs: usize,
+    diagnostics: Vec<ParseDiagnostic>,
 }
 
 impl<'arena, 'options, 'map, 'pattern, 'scratch>
@@ -29,6 +36,11 @@ impl<'arena, 'options, 'map, 'pattern, 'scratch>
         check_param_limit(self.options.max_params, self.pairs)
     }
 
+    fn add_comma_params(&mut self, commas: usize) -> ParseResult<()> {
+        self.pairs = self.pairs.saturating_add(commas);
+        check_param_limit(self.options.max_params, self.pairs)
+    }
+
     fn process_segment(
         &mut self,
         cursor: usize,
@@ -36,6 +48,9 @@ impl<'arena, 'options, 'map, 'pattern, 'scratch>
         eq_index: Option<usize>,
     ) -> ParseResult<()> {
         let trimmed = self.trimmed;
+        if self.options.malformed_pairs != MalformedPairBehavior::Reject {
+            check_characters(&trimmed[cursor..segment_end], self.offset + cursor)?;
+        }
         let raw_key_end = eq_index.unwrap_or(segment_end);
         let raw_key = &trimmed[cursor..raw_key_end];
         let raw_value = eq_index
@@ -47,12 +62,34 @@ impl<'arena, 'options, 'map, 'pattern, 'scratch>
             .map(|idx| self.offset + idx + 1)
             .unwrap_or(self.offset + cursor + raw_key.len());
 
+        if eq_index.is_none() && self.options.strict_null_handling {
+            let key = decode_key(
+                raw_key,
+                key_start,
+                self.options,
+                self.charset,
+                self.decode_scratch,
+            )?;
+            return insert_null_pair_arena(
+                self.arena,
+                self.arena_map,
+                self.pattern_state,
+                key,
+                self.options,
+            );
+        }
+
+        if self.options.comma && memchr(b',', raw_value.as_bytes()).is_some() {
+            return self.process_comma_segment(raw_key, raw_value, key_start, value_offset);
+        }
+
         let (key, value) = decode_pair(
             raw_key,
             raw_value,
             key_start,
             value_offset,
             self.options,
+            self.charset,
             self.decode_scratch,
         )?;
 
@@ -62,7 +99,82 @@ impl<'arena, 'options, 'map, 'pattern, 'scratch>
             self.pattern_state,
             key,
             value,
-            self.options.duplicate_keys,
+            self.options,
+        )
+    }
+
+    fn recover_segment(
+        &mut self,
+        error: ParseError,
+        cursor: usize,
+        segment_end: usize,
+        eq_index: Option<usize>,
+    ) -> ParseResult<()> {
+        let behavior = self.options.malformed_pairs;
+        if behavior == MalformedPairBehavior::Reject
+            || matches!(error, ParseError::TooManyParameters { .. })
+        {
+            return Err(error);
+        }
+
+        let trimmed = self.trimmed;
+        let raw_key = &trimmed[cursor..eq_index.unwrap_or(segment_end)];
+        self.diagnostics.push(ParseDiagnostic {
+            error,
+            key: raw_key.to_string(),
+            offset: self.offset + cursor,
+        });
+
+        if behavior == MalformedPairBehavior::KeepRaw {
+            let raw_value = eq_index
+                .map(|idx| &trimmed[idx + 1..segment_end])
+                .unwrap_or("");
+            if let Err(error) =
+                insert_raw_pair_arena(self.arena, self.arena_map, raw_key, raw_value, self.options)
+            {
+                self.diagnostics.push(ParseDiagnostic {
+                    error,
+                    key: raw_key.to_string(),
+                    offset: self.offset + cursor,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_comma_segment(
+        &mut self,
+        raw_key: &str,
+        raw_value: &'arena str,
+        key_start: usize,
+        value_offset: usize,
+    ) -> ParseResult<()> {
+        self.add_comma_params(memchr_iter(b',', raw_value.as_bytes()).count())?;
+
+        let key = decode_key(
+            raw_key,
+            key_start,
+            self.options,
+            self.charset,
+            self.decode_scratch,
+        )?;
+        let values = decode_comma_values(
+            raw_value,
+            value_offset,
+            &key,
+            self.options,
+            self.charset,
+            self.decode_scratch,
+        )?;
+
+        insert_comma_pair_arena(
+            self.arena,
+            self.arena_map,
+            self.pattern_state,
+            key,
+            values,
+            self.options,
         )
     }
 }
@@ -75,29 +187,66 @@ pub fn with_arena_query_map<R, F>(
 ) -> ParseResult<R>
 where
     F: for<'arena> FnOnce(&'arena ParseArena, &ArenaQueryMap<'arena>) -> ParseResult<R>,
+{
+    with_arena_query_map_diagnostics(trimmed, offset, options, |arena, arena_map, _| {
+        finalize(arena, arena_map)
+    })
+}
+
+pub fn with_arena_query_map_diagnostics<R, F>(
+    trimmed: &str,
+    offset: usize,
+    options: &ParseOptions,
+    finalize: F,
+) -> ParseResult<R>
+where
+    F: for<'arena> FnOnce(
+        &'arena ParseArena,
+        &ArenaQueryMap<'arena>,
+        Vec<ParseDiagnostic>,
+    ) -> ParseResult<R>,
 {
     let arena_capacity = trimmed.len().saturating_mul(2);
     let arena_lease = ArenaLease::acquire(arena_capacity);
     let arena: &ParseArena = &arena_lease;
-    let estimated_pairs = estimate_param_capacity(trimmed);
+    let scanner = PairScanner::new(options);
+    let estimated_pairs = estimate_param_capacity(trimmed, &scanner.pairs);
     let mut arena_map = ArenaQueryMap::with_capacity(arena, estimated_pairs);
     let mut pattern_state = acquire_pattern_state();
     let mut decode_scratch = acquire_bytes();
     let bytes = trimmed.as_bytes();
+    let sentinel = if options.charset_sentinel {
+        find_charset_sentinel(&scanner, bytes)
+    } else {
+        None
+    };
     let mut context = ParseContext {
         arena,
         arena_map: &mut arena_map,
         pattern_state: &mut pattern_state,
         options,
+        charset: sentinel
+            .and_then(|sentinel| sentinel.charset)
+            .unwrap_or(options.charset),
         trimmed,
         offset,
         decode_scratch: decode_scratch.as_mut(),
         pairs: 0,
+        diagnostics: Vec::new(),
     };
 
-    parse_segments_into_map(&mut context, bytes)?;
+    parse_segments_into_map(
+        &mut context,
+        &scanner,
+        bytes,
+        sentinel.map(|sentinel| sentinel.start),
+    )?;
+    let diagnostics = std::mem::take(&mut context.diagnostics);
+    if options.sparse_arrays == SparseArrayBehavior::Compact {
+        arena_map.values_mut().for_each(arena_compact_holes);
+    }
 
-    finalize(arena, &arena_map)
+    finalize(arena, &arena_map, diagnostics)
 }
 
 fn check_param_limit(limit: Option<usize>, current: usize) -> ParseResult<()> {
@@ -113,32 +262,50 @@ fn check_param_limit(limit: Option<usize>, current: usize) -> ParseResult<()> {
     }
 }
 
-fn parse_segments_into_map(
-    context: &mut ParseContext<'_, '_, '_, '_, '_>,
-    bytes: &[u8],
-) -> ParseResult<()> {
-    let mut cursor = 0usize;
+struct PairScanner {
+    pairs: SmallVec<[u8; 2]>,
+    separator: u8,
+}
 
-    while cursor < bytes.len() {
+impl PairScanner {
+    fn new(options: &ParseOptions) -> Self {
+        Self {
+            pairs: options.delimiters.pair_bytes(),
+            separator: options.delimiters.key_value_byte(),
+        }
+    }
+
+    #[inline]
+    fn find(&self, haystack: &[u8], with_separator: bool) -> Option<usize> {
+        match (self.pairs.as_slice(), with_separator) {
+            ([pair], false) => memchr(*pair, haystack),
+            ([pair], true) => memchr2(self.separator, *pair, haystack),
+            ([first, second], false) => memchr2(*first, *second, haystack),
+            ([first, second], true) => memchr3(self.separator, *first, *second, haystack),
+            (pairs, with_separator) => haystack.iter().position(|byte| {
+                pairs.contains(byte) || (with_separator && *byte == self.separator)
+            }),
+        }
+    }
+
+    #[inline]
+    fn is_pair_delimiter(&self, byte: u8) -> bool {
+        self.pairs.contains(&byte)
+    }
+
+    #[inline]
+    fn segment(&self, bytes: &[u8], cursor: usize) -> (usize, Option<usize>) {
         let mut search = cursor;
-        let mut segment_end = bytes.len();
         let mut eq_index: Option<usize> = None;
 
         while search < bytes.len() {
-            let rel = if eq_index.is_some() {
-                memchr(b'&', &bytes[search..])
-            } else {
-                memchr2(b'=', b'&', &bytes[search..])
-            };
-
-            let Some(rel) = rel else {
+            let Some(rel) = self.find(&bytes[search..], eq_index.is_none()) else {
                 break;
             };
 
             let idx = search + rel;
-            if bytes[idx] == b'&' {
-                segment_end = idx;
-                break;
+            if self.is_pair_delimiter(bytes[idx]) {
+                return (idx, eq_index);
             }
 
             if eq_index.is_none() {
@@ -147,9 +314,65 @@ fn parse_segments_into_map(
             search = idx + 1;
         }
 
-        if segment_end > cursor {
+        (bytes.len(), eq_index)
+    }
+}
+
+const UTF8_SENTINEL_KEY: &[u8] = b"utf8";
+const UTF8_SENTINEL_VALUES: [&[u8]; 2] = [b"%E2%9C%93", "\u{2713}".as_bytes()];
+const ISO_8859_1_SENTINEL_VALUE: &[u8] = b"%26%2310003%3B";
+
+#[derive(Clone, Copy)]
+struct CharsetSentinel {
+    start: usize,
+    charset: Option<Charset>,
+}
+
+fn find_charset_sentinel(scanner: &PairScanner, bytes: &[u8]) -> Option<CharsetSentinel> {
+    let mut cursor = 0usize;
+
+    while cursor < bytes.len() {
+        let (segment_end, eq_index) = scanner.segment(bytes, cursor);
+        let key = &bytes[cursor..eq_index.unwrap_or(segment_end)];
+        if key == UTF8_SENTINEL_KEY {
+            let value = eq_index.map_or(&[][..], |idx| &bytes[idx + 1..segment_end]);
+            let charset = if UTF8_SENTINEL_VALUES
+                .iter()
+                .any(|sentinel| value.eq_ignore_ascii_case(sentinel))
+            {
+                Some(Charset::Utf8)
+            } else if value.eq_ignore_ascii_case(ISO_8859_1_SENTINEL_VALUE) {
+                Some(Charset::Iso88591)
+            } else {
+                None
+            };
+            return Some(CharsetSentinel {
+                start: cursor,
+                charset,
+            });
+        }
+        cursor = segment_end.saturating_add(1);
+    }
+
+    None
+}
+
+fn parse_segments_into_map(
+    context: &mut ParseContext<'_, '_, '_, '_, '_>,
+    scanner: &PairScanner,
+    bytes: &[u8],
+    skip_segment: Option<usize>,
+) -> ParseResult<()> {
+    let mut cursor = 0usize;
+
+    while cursor < bytes.len() {
+        let (segment_end, eq_index) = scanner.segment(bytes, cursor);
+
+        if segment_end > cursor && skip_segment != Some(cursor) {
             context.increment_pairs()?;
-            context.process_segment(cursor, segment_end, eq_index)?;
+            if let Err(error) = context.process_segment(cursor, segment_end, eq_index) {
+                context.recover_segment(error, cursor, segment_end, eq_index)?;
+            }
         }
 
         cursor = segment_end.saturating_add(1);
@@ -254,6 +254,156 @@ mod with_arena_query_map {
     }
 }
 
+mod with_strict_null_handling {
+    use super::*;
+
+    #[test]
+    fn should_store_null_when_key_has_no_separator_then_distinguish_from_empty_value() {
+        let options = ParseOptions::new().strict_null_handling(true);
+
+        let result = with_arena_query_map("debug&empty=&a[b]", 0, &options, |_, map| {
+            let entries = map.entries_slice();
+            assert_matches!(&entries[0].1, ArenaValue::Null);
+            assert_matches!(&entries[1].1, ArenaValue::String(""));
+            assert_matches!(&entries[2].1, ArenaValue::Map { entries, .. } => {
+                assert_matches!(&entries[0].1, ArenaValue::Null);
+            });
+            Ok(())
+        });
+
+        result.expect("bare keys should parse");
+    }
+
+    #[test]
+    fn should_store_empty_string_when_strict_null_handling_is_disabled_then_keep_legacy_behavior() {
+        let options = ParseOptions::default();
+
+        let result = with_arena_query_map("debug", 0, &options, |_, map| {
+            assert_matches!(&map.entries_slice()[0].1, ArenaValue::String(""));
+            Ok(())
+        });
+
+        result.expect("bare key should parse");
+    }
+}
+
+mod with_custom_delimiters {
+    use super::*;
+
+    fn collect_pairs(trimmed: &str, options: &ParseOptions) -> Vec<(String, String)> {
+        with_arena_query_map(trimmed, 0, options, |_, map| {
+            Ok(map
+                .entries_slice()
+                .iter()
+                .map(|(key, value)| match value {
+                    ArenaValue::String(text) => (key.to_string(), text.to_string()),
+                    _ => panic!("expected string value"),
+                })
+                .collect())
+        })
+        .expect("custom delimiters should parse")
+    }
+
+    #[test]
+    fn should_split_on_semicolon_when_single_custom_delimiter_is_configured_then_keep_ampersand_in_value()
+     {
+        let options = ParseOptions::new().pair_delimiters(";");
+
+        let pairs = collect_pairs("a=1&2;b=3", &options);
+
+        assert_eq!(
+            pairs,
+            vec![("a".into(), "1&2".into()), ("b".into(), "3".into())]
+        );
+    }
+
+    #[test]
+    fn should_split_on_both_delimiters_when_two_delimiters_are_configured_then_collect_every_pair()
+    {
+        let options = ParseOptions::new().pair_delimiters("&;");
+
+        let pairs = collect_pairs("a=1;b=2&c=3", &options);
+
+        assert_eq!(
+            pairs,
+            vec![
+                ("a".into(), "1".into()),
+                ("b".into(), "2".into()),
+                ("c".into(), "3".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn should_split_on_every_delimiter_when_more_than_two_are_configured_then_use_fallback_scan() {
+        let options = ParseOptions::new().pair_delimiters("&;|");
+
+        let pairs = collect_pairs("a=1|b=2;c=3&d=4", &options);
+
+        assert_eq!(pairs.len(), 4);
+        assert_eq!(pairs[3], ("d".into(), "4".into()));
+    }
+
+    #[test]
+    fn should_split_key_and_value_on_custom_separator_when_configured_then_keep_equals_in_value() {
+        let options = ParseOptions::new()
+            .pair_delimiters(";")
+            .key_value_separator(':');
+
+        let pairs = collect_pairs("a:x=y;b:2", &options);
+
+        assert_eq!(
+            pairs,
+            vec![("a".into(), "x=y".into()), ("b".into(), "2".into())]
+        );
+    }
+}
+
+mod with_arena_query_map_diagnostics {
+    use super::*;
+    use crate::config::MalformedPairBehavior;
+
+    #[test]
+    fn should_collect_diagnostics_when_pairs_are_malformed_then_keep_valid_pairs() {
+        let options = ParseOptions::new().malformed_pairs(MalformedPairBehavior::Skip);
+
+        let result =
+            with_arena_query_map_diagnostics("a=%G&b=2", 1, &options, |_, map, diagnostics| {
+                assert_eq!(map.len(), 1);
+                assert_eq!(diagnostics.len(), 1);
+                assert_eq!(diagnostics[0].key, "a");
+                assert_eq!(diagnostics[0].offset, 1);
+                Ok(())
+            });
+
+        result.expect("malformed pair should be skipped");
+    }
+
+    #[test]
+    fn should_report_duplicate_when_keep_raw_key_repeats_then_keep_first_value() {
+        let options = ParseOptions::new().malformed_pairs(MalformedPairBehavior::KeepRaw);
+
+        let result = with_arena_query_map_diagnostics(
+            "a[=1&a[=2",
+            0,
+            &options,
+            |_, map, diagnostics| {
+                let entries = map.entries_slice();
+                assert_eq!(entries.len(), 1);
+                assert_eq!(entries[0].0, "a[");
+                assert_matches!(&entries[0].1, ArenaValue::String(text) if *text == "1");
+                assert_eq!(diagnostics.len(), 3);
+                assert_eq!(diagnostics[2].key, "a[");
+                assert_eq!(diagnostics[2].offset, 5);
+                assert_matches!(&diagnostics[2].error, ParseError::DuplicateRootKey { key } if key == "a[");
+                Ok(())
+            },
+        );
+
+        result.expect("raw pairs should be kept");
+    }
+}
+
 mod parse_segments_into_map {
     use super::*;
 
@@ -272,14 +422,21 @@ mod parse_segments_into_map {
                 arena_map: &mut map,
                 pattern_state: &mut pattern_state,
                 options: &options,
+                charset: options.charset,
                 trimmed,
                 offset: 0,
                 decode_scratch: &mut scratch,
                 pairs: 0,
+                diagnostics: Vec::new(),
             };
 
-            parse_segments_into_map(&mut context, trimmed.as_bytes())
-                .expect("parse should succeed");
+            parse_segments_into_map(
+                &mut context,
+                &PairScanner::new(&options),
+                trimmed.as_bytes(),
+                None,
+            )
+            .expect("parse should succeed");
         }
 
         let entries = map.entries_slice();
@@ -304,14 +461,21 @@ mod parse_segments_into_map {
                 arena_map: &mut map,
                 pattern_state: &mut pattern_state,
                 options: &options,
+                charset: options.charset,
                 trimmed,
                 offset: 0,
                 decode_scratch: &mut scratch,
                 pairs: 0,
+                diagnostics: Vec::new(),
             };
 
-            parse_segments_into_map(&mut context, trimmed.as_bytes())
-                .expect_err("limit should trigger error")
+            parse_segments_into_map(
+                &mut context,
+                &PairScanner::new(&options),
+                trimmed.as_bytes(),
+                None,
+            )
+            .expect_err("limit should trigger error")
         };
 
         assert_matches!(
@@ -323,3 +487,69 @@ mod parse_segments_into_map {
         );
     }
 }
+
+mod charset_sentinel {
+    use super::*;
+    use crate::config::Charset;
+
+    fn collect_pairs(trimmed: &str, options: &ParseOptions) -> Vec<(String, String)> {
+        with_arena_query_map(trimmed, 0, options, |_, map| {
+            Ok(map
+                .entries_slice()
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        ArenaValue::String(text) => text.to_string(),
+                        _ => panic!("expected string value"),
+                    };
+                    (key.to_string(), value)
+                })
+                .collect())
+        })
+        .expect("parse should succeed")
+    }
+
+    #[test]
+    fn should_switch_to_latin1_when_iso_sentinel_is_present_then_drop_sentinel_pair() {
+        let options = ParseOptions::new().charset_sentinel(true);
+
+        let pairs = collect_pairs("name=Jos%E9&utf8=%26%2310003%3B", &options);
+
+        assert_eq!(pairs, vec![("name".to_string(), "Jos\u{e9}".to_string())]);
+    }
+
+    #[test]
+    fn should_switch_to_utf8_when_check_mark_sentinel_is_present_then_override_default_charset() {
+        let options = ParseOptions::new()
+            .charset(Charset::Iso88591)
+            .charset_sentinel(true);
+
+        let pairs = collect_pairs("utf8=%E2%9C%93&name=Jos%C3%A9", &options);
+
+        assert_eq!(pairs, vec![("name".to_string(), "Jos\u{e9}".to_string())]);
+    }
+
+    #[test]
+    fn should_keep_sentinel_pair_when_detection_is_disabled_then_store_as_regular_pair() {
+        let options = ParseOptions::new();
+
+        let pairs = collect_pairs("utf8=%E2%9C%93&a=1", &options);
+
+        assert_eq!(
+            pairs,
+            vec![
+                ("utf8".to_string(), "\u{2713}".to_string()),
+                ("a".to_string(), "1".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn should_exclude_sentinel_from_param_limit_when_detected_then_count_remaining_pairs() {
+        let options = ParseOptions::new().charset_sentinel(true).max_params(1);
+
+        let pairs = collect_pairs("utf8=%E2%9C%93&a=1", &options);
+
+        assert_eq!(pairs, vec![("a".to_string(), "1".to_string())]);
+    }
+}
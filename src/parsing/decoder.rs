@@ -1,3 +1,4 @@
+use crate::config::Charset;
 use crate::parsing::ParseError;
 use crate::parsing::errors::ParseLocation;
 use memchr::{memchr, memchr2};
@@ -6,6 +7,7 @@ use std::borrow::Cow;
 pub(crate) fn decode_component<'a>(
     raw: &'a str,
     space_as_plus: bool,
+    charset: Charset,
     offset: usize,
     location: ParseLocation,
     scratch: &mut Vec<u8>,
@@ -25,7 +27,15 @@ pub(crate) fn decode_component<'a>(
         return fast_path_ascii(raw, bytes, offset, location);
     }
 
-    decode_with_special_chars(raw, bytes, space_as_plus, offset, location, scratch)
+    decode_with_special_chars(
+        raw,
+        bytes,
+        space_as_plus,
+        charset,
+        offset,
+        location,
+        scratch,
+    )
 }
 
 pub(crate) fn fast_path_ascii<'a>(
@@ -48,6 +58,7 @@ pub(crate) fn decode_with_special_chars<'a>(
     raw: &'a str,
     bytes: &[u8],
     space_as_plus: bool,
+    charset: Charset,
     offset: usize,
     location: ParseLocation,
     scratch: &mut Vec<u8>,
@@ -61,7 +72,11 @@ pub(crate) fn decode_with_special_chars<'a>(
         cursor = match bytes[cursor] {
             b'%' => {
                 modified = true;
-                decode_percent_sequence(bytes, cursor, offset, location, scratch)?
+                let next = decode_percent_sequence(bytes, cursor, offset, location, scratch)?;
+                if charset == Charset::Iso88591 {
+                    widen_latin1_byte(scratch);
+                }
+                next
             }
             b'+' if space_as_plus => {
                 modified = true;
@@ -79,7 +94,7 @@ pub(crate) fn decode_with_special_chars<'a>(
         return Ok(Cow::Borrowed(raw));
     }
 
-    finalize_decoded(location, scratch)
+    finalize_decoded(location, charset, scratch)
 }
 
 pub(crate) fn hex_value(byte: u8) -> Option<u8> {
@@ -121,6 +136,16 @@ pub(crate) fn decode_percent_sequence(
     Ok(cursor + 3)
 }
 
+pub(crate) fn widen_latin1_byte(scratch: &mut Vec<u8>) {
+    if let Some(&byte) = scratch.last()
+        && byte >= 0x80
+    {
+        scratch.pop();
+        let mut buffer = [0u8; 2];
+        scratch.extend_from_slice(char::from(byte).encode_utf8(&mut buffer).as_bytes());
+    }
+}
+
 pub(crate) fn decode_plus(cursor: usize, scratch: &mut Vec<u8>) -> usize {
     scratch.push(b' ');
     cursor + 1
@@ -171,6 +196,7 @@ pub(crate) fn decode_utf8_cluster(
 
 pub(crate) fn finalize_decoded<'a>(
     location: ParseLocation,
+    charset: Charset,
     scratch: &mut Vec<u8>,
 ) -> Result<Cow<'a, str>, ParseError> {
     let decoded_len = scratch.len();
@@ -181,6 +207,12 @@ pub(crate) fn finalize_decoded<'a>(
             scratch.reserve(decoded_len);
             Ok(Cow::Owned(decoded))
         }
+        Err(err) if charset == Charset::Utf8Lossy => {
+            let decoded = String::from_utf8_lossy(err.as_bytes()).into_owned();
+            *scratch = err.into_bytes();
+            scratch.clear();
+            Ok(Cow::Owned(decoded))
+        }
         Err(err) => {
             *scratch = err.into_bytes();
             Err(ParseError::InvalidUtf8 { location })
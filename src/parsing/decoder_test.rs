@@ -14,8 +14,15 @@ mod decode_component {
         let raw = "simple";
         let mut scratch = super::scratch_vec();
 
-        let result = decode_component(raw, false, 0, ParseLocation::Key, &mut scratch)
-            .expect("decode ascii");
+        let result = decode_component(
+            raw,
+            false,
+            Charset::Utf8,
+            0,
+            ParseLocation::Key,
+            &mut scratch,
+        )
+        .expect("decode ascii");
 
         assert_matches!(result, Cow::Borrowed("simple"));
     }
@@ -26,8 +33,15 @@ mod decode_component {
         let raw = "one+two";
         let mut scratch = super::scratch_vec();
 
-        let result =
-            decode_component(raw, true, 5, ParseLocation::Key, &mut scratch).expect("decode plus");
+        let result = decode_component(
+            raw,
+            true,
+            Charset::Utf8,
+            5,
+            ParseLocation::Key,
+            &mut scratch,
+        )
+        .expect("decode plus");
 
         assert_matches!(result, Cow::Owned(string) if string == "one two");
     }
@@ -37,8 +51,15 @@ mod decode_component {
         let raw = "%2G";
         let mut scratch = super::scratch_vec();
 
-        let error = decode_component(raw, false, 12, ParseLocation::Key, &mut scratch)
-            .expect_err("invalid second hex digit should fail");
+        let error = decode_component(
+            raw,
+            false,
+            Charset::Utf8,
+            12,
+            ParseLocation::Key,
+            &mut scratch,
+        )
+        .expect_err("invalid second hex digit should fail");
 
         assert_matches!(
             error,
@@ -55,8 +76,15 @@ mod decode_component {
         let raw = "bad\u{0007}";
         let mut scratch = super::scratch_vec();
 
-        let error = decode_component(raw, false, 3, ParseLocation::Key, &mut scratch)
-            .expect_err("control char");
+        let error = decode_component(
+            raw,
+            false,
+            Charset::Utf8,
+            3,
+            ParseLocation::Key,
+            &mut scratch,
+        )
+        .expect_err("control char");
 
         assert_matches!(
             error,
@@ -118,6 +146,7 @@ mod decode_with_special_chars {
             raw,
             raw.as_bytes(),
             true,
+            Charset::Utf8,
             0,
             ParseLocation::Key,
             &mut scratch,
@@ -137,6 +166,7 @@ mod decode_with_special_chars {
             raw,
             raw.as_bytes(),
             false,
+            Charset::Utf8,
             4,
             ParseLocation::Key,
             &mut scratch,
@@ -161,6 +191,7 @@ mod decode_with_special_chars {
             raw,
             raw.as_bytes(),
             false,
+            Charset::Utf8,
             0,
             ParseLocation::Key,
             &mut scratch,
@@ -327,8 +358,8 @@ mod finalize_decoded {
     fn should_return_owned_string_when_bytes_are_valid_utf8_then_collect_string() {
         let mut scratch = b"hello".to_vec();
 
-        let result =
-            finalize_decoded_for_test(ParseLocation::Key, &mut scratch).expect("valid utf8");
+        let result = finalize_decoded_for_test(ParseLocation::Key, Charset::Utf8, &mut scratch)
+            .expect("valid utf8");
 
         assert_matches!(result, Cow::Owned(text) if text == "hello");
         assert!(scratch.capacity() >= 5);
@@ -338,8 +369,8 @@ mod finalize_decoded {
     fn should_return_invalid_utf8_error_when_bytes_are_invalid_utf8_then_restore_cursor_state() {
         let mut scratch = vec![0xF0, 0x28, 0x8C, 0x28];
 
-        let err =
-            finalize_decoded_for_test(ParseLocation::Key, &mut scratch).expect_err("invalid utf8");
+        let err = finalize_decoded_for_test(ParseLocation::Key, Charset::Utf8, &mut scratch)
+            .expect_err("invalid utf8");
 
         assert_matches!(err, ParseError::InvalidUtf8 { location } if location == ParseLocation::Key);
         assert_eq!(scratch, vec![0xF0, 0x28, 0x8C, 0x28]);
@@ -390,3 +421,80 @@ mod hex_value {
         assert_eq!(hex_value_for_test(b'G'), None);
     }
 }
+
+mod charset {
+    use super::*;
+
+    #[test]
+    fn should_map_each_byte_to_code_point_when_charset_is_latin1_then_decode_without_utf8_errors() {
+        let mut scratch = scratch_vec();
+
+        let result = decode_component(
+            "caf%E9+%FC",
+            true,
+            Charset::Iso88591,
+            0,
+            ParseLocation::Value,
+            &mut scratch,
+        )
+        .expect("latin1 bytes should decode");
+
+        assert_eq!(result.as_ref(), "caf\u{e9} \u{fc}");
+    }
+
+    #[test]
+    fn should_keep_literal_unicode_when_charset_is_latin1_then_only_widen_percent_bytes() {
+        let mut scratch = scratch_vec();
+
+        let result = decode_component(
+            "\u{e9}%E9",
+            false,
+            Charset::Iso88591,
+            0,
+            ParseLocation::Value,
+            &mut scratch,
+        )
+        .expect("mixed input should decode");
+
+        assert_eq!(result.as_ref(), "\u{e9}\u{e9}");
+    }
+
+    #[test]
+    fn should_substitute_replacement_character_when_charset_is_lossy_then_accept_invalid_utf8() {
+        let mut scratch = scratch_vec();
+
+        let result = decode_component(
+            "bad%FFbyte",
+            false,
+            Charset::Utf8Lossy,
+            0,
+            ParseLocation::Value,
+            &mut scratch,
+        )
+        .expect("lossy decoding should succeed");
+
+        assert_eq!(result.as_ref(), "bad\u{fffd}byte");
+    }
+
+    #[test]
+    fn should_report_invalid_utf8_when_charset_is_strict_then_reject_latin1_bytes() {
+        let mut scratch = scratch_vec();
+
+        let error = decode_component(
+            "caf%E9",
+            false,
+            Charset::Utf8,
+            0,
+            ParseLocation::Value,
+            &mut scratch,
+        )
+        .expect_err("latin1 byte should fail in utf8 mode");
+
+        assert_matches!(
+            error,
+            ParseError::InvalidUtf8 {
+                location: ParseLocation::Value
+            }
+        );
+    }
+}
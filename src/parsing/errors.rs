@@ -27,7 +27,7 @@ impl fmt::Display for ParseLocation {
     }
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum ParseError {
     #[error("input exceeds maximum length of {limit} characters (received {actual})")]
     InputTooLong { limit: usize, actual: usize },
@@ -41,6 +41,12 @@ pub enum ParseError {
     DuplicateSequenceIndex { parent: String, index: usize },
     #[error("invalid sequence index '{segment}' under '{parent}' not allowed")]
     InvalidSequenceIndex { parent: String, segment: String },
+    #[error("sequence index '{segment}' under '{parent}' exceeds array limit {limit}")]
+    ArrayIndexTooLarge {
+        parent: String,
+        segment: String,
+        limit: usize,
+    },
     #[error("nested value conflict under '{parent}' mixes scalars and structured data")]
     NestedValueConflict { parent: String },
     #[error("incompatible key pattern for segment '{segment}' under '{parent}'")]
@@ -75,6 +81,54 @@ pub enum ParseError {
     Serde(#[from] DeserializeError),
 }
 
+impl ParseError {
+    pub fn location(&self) -> Option<ParseLocation> {
+        match self {
+            ParseError::InvalidPercentEncoding { location, .. }
+            | ParseError::InvalidCharacter { location, .. }
+            | ParseError::UnexpectedQuestionMark { location, .. }
+            | ParseError::InvalidUtf8 { location } => Some(*location),
+            _ => None,
+        }
+    }
+
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            ParseError::InvalidPercentEncoding { index, .. }
+            | ParseError::InvalidCharacter { index, .. }
+            | ParseError::UnexpectedQuestionMark { index, .. } => Some(*index),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParseDiagnostic {
+    pub error: ParseError,
+    pub key: String,
+    pub offset: usize,
+}
+
+impl ParseDiagnostic {
+    pub fn location(&self) -> ParseLocation {
+        self.error.location().unwrap_or(ParseLocation::Key)
+    }
+
+    pub fn index(&self) -> usize {
+        self.error.index().unwrap_or(self.offset)
+    }
+}
+
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "skipped pair at byte offset {}: {}",
+            self.offset, self.error
+        )
+    }
+}
+
 #[cfg(test)]
 #[path = "errors_test.rs"]
 mod errors_test;
@@ -53,6 +53,21 @@ mod to_string {
         );
     }
 
+    #[test]
+    fn should_format_array_index_too_large_when_to_string_called_then_include_segment_and_limit() {
+        let error = ParseError::ArrayIndexTooLarge {
+            parent: "items".into(),
+            segment: "500".into(),
+            limit: 20,
+        };
+        let message = error.to_string();
+
+        assert_eq!(
+            message,
+            "sequence index '500' under 'items' exceeds array limit 20"
+        );
+    }
+
     #[test]
     fn should_format_duplicate_sequence_index_when_to_string_called_then_include_index() {
         let error = ParseError::DuplicateSequenceIndex {
@@ -224,3 +239,49 @@ mod from {
         );
     }
 }
+
+mod location {
+    use super::*;
+
+    #[test]
+    fn should_return_location_when_error_points_into_component_then_yield_component_location() {
+        let error = ParseError::InvalidPercentEncoding {
+            index: 4,
+            location: ParseLocation::Value,
+        };
+
+        assert_eq!(error.location(), Some(ParseLocation::Value));
+        assert_eq!(error.index(), Some(4));
+    }
+
+    #[test]
+    fn should_return_none_when_error_describes_structure_then_yield_no_location() {
+        let error = ParseError::UnmatchedBracket {
+            key: "a[".into(),
+            bracket: '[',
+        };
+
+        assert_eq!(error.location(), None);
+        assert_eq!(error.index(), None);
+    }
+}
+
+mod parse_diagnostic {
+    use super::*;
+
+    #[test]
+    fn should_fall_back_to_key_and_pair_offset_when_error_has_no_position_then_report_pair_start() {
+        let diagnostic = ParseDiagnostic {
+            error: ParseError::DuplicateRootKey { key: "a".into() },
+            key: "a".into(),
+            offset: 4,
+        };
+
+        assert_eq!(diagnostic.location(), ParseLocation::Key);
+        assert_eq!(diagnostic.index(), 4);
+        assert_eq!(
+            diagnostic.to_string(),
+            "skipped pair at byte offset 4: duplicate root key 'a' not allowed"
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::parsing::ParseError;
-use memchr::memchr_iter;
+use memchr::{memchr, memchr_iter, memchr2, memchr2_iter};
 
 pub(crate) fn validate_brackets(key: &str, max_depth: Option<usize>) -> Result<(), ParseError> {
     let mut open = 0usize;
@@ -44,16 +44,51 @@ pub(crate) fn validate_brackets(key: &str, max_depth: Option<usize>) -> Result<(
     Ok(())
 }
 
+pub(crate) fn is_well_formed_key_path(key: &str) -> bool {
+    let bytes = key.as_bytes();
+    let Some(open) = memchr(b'[', bytes) else {
+        return memchr(b']', bytes).is_none();
+    };
+    if memchr(b']', &bytes[..open]).is_some() {
+        return false;
+    }
+
+    let mut cursor = open;
+    while cursor < bytes.len() {
+        if bytes[cursor] != b'[' {
+            return false;
+        }
+        let segment_start = cursor + 1;
+        match memchr2(b'[', b']', &bytes[segment_start..]) {
+            Some(offset) if bytes[segment_start + offset] == b']' => {
+                cursor = segment_start + offset + 1;
+            }
+            _ => return false,
+        }
+    }
+
+    true
+}
+
 pub(crate) fn duplicate_key_label(key: &str) -> String {
     key.to_string()
 }
 
-pub(crate) fn estimate_param_capacity(input: &str) -> usize {
+pub(crate) fn estimate_param_capacity(input: &str, delimiters: &[u8]) -> usize {
     if input.is_empty() {
         return 0;
     }
 
-    memchr_iter(b'&', input.as_bytes()).count() + 1
+    let bytes = input.as_bytes();
+    let count = match delimiters {
+        [delimiter] => memchr_iter(*delimiter, bytes).count(),
+        [first, second] => memchr2_iter(*first, *second, bytes).count(),
+        delimiters => bytes
+            .iter()
+            .filter(|byte| delimiters.contains(byte))
+            .count(),
+    };
+    count + 1
 }
 
 #[cfg(test)]
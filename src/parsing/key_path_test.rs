@@ -1,4 +1,6 @@
-use super::{duplicate_key_label, estimate_param_capacity, validate_brackets};
+use super::{
+    duplicate_key_label, estimate_param_capacity, is_well_formed_key_path, validate_brackets,
+};
 use crate::parsing::ParseError;
 use assert_matches::assert_matches;
 
@@ -61,6 +63,36 @@ mod validate_brackets {
     }
 }
 
+mod is_well_formed_key_path {
+    use super::*;
+
+    #[test]
+    fn should_accept_plain_and_bracketed_keys_when_brackets_are_balanced_then_return_true() {
+        for key in ["plain", "a[b]", "a[]", "a[0][name]", "[a]", ""] {
+            assert!(is_well_formed_key_path(key), "{key} should be well formed");
+        }
+    }
+
+    #[test]
+    fn should_reject_unbalanced_brackets_when_pair_is_incomplete_then_return_false() {
+        for key in ["a[b", "a]b", "a[", "]a", "a[b]]"] {
+            assert!(!is_well_formed_key_path(key), "{key} should be malformed");
+        }
+    }
+
+    #[test]
+    fn should_reject_nested_open_bracket_when_segment_contains_bracket_then_return_false() {
+        assert!(!is_well_formed_key_path("arr[[0]"));
+        assert!(!is_well_formed_key_path("a[b[c]]"));
+    }
+
+    #[test]
+    fn should_reject_trailing_text_when_text_follows_closing_bracket_then_return_false() {
+        assert!(!is_well_formed_key_path("a[b]c"));
+        assert!(!is_well_formed_key_path("a[b]c[d]"));
+    }
+}
+
 mod duplicate_key_label {
     use super::*;
 
@@ -85,7 +117,7 @@ mod estimate_param_capacity {
     fn should_count_zero_parameters_when_input_is_empty_then_return_zero_capacity() {
         let query = "";
 
-        let capacity = estimate_param_capacity(query);
+        let capacity = estimate_param_capacity(query, b"&");
 
         assert_eq!(capacity, 0);
     }
@@ -95,7 +127,7 @@ mod estimate_param_capacity {
      {
         let query = "a=1&b=2&c=3";
 
-        let capacity = estimate_param_capacity(query);
+        let capacity = estimate_param_capacity(query, b"&");
 
         assert_eq!(capacity, 3);
     }
@@ -105,8 +137,18 @@ mod estimate_param_capacity {
      {
         let query = "token=&";
 
-        let capacity = estimate_param_capacity(query);
+        let capacity = estimate_param_capacity(query, b"&");
 
         assert_eq!(capacity, 2);
     }
+
+    #[test]
+    fn should_count_every_delimiter_when_multiple_delimiters_are_configured_then_include_each_pair()
+    {
+        let query = "a=1;b=2&c=3";
+
+        let capacity = estimate_param_capacity(query, b"&;");
+
+        assert_eq!(capacity, 3);
+    }
 }
@@ -11,5 +11,7 @@ pub mod arena;
 
 pub mod api;
 
-pub use api::{ParseResult, parse};
-pub use errors::ParseError;
+pub use api::{
+    ParseOutcome, ParseResult, ParsedQuery, parse, parse_detailed, parse_to_query_map, with_parsed,
+};
+pub use errors::{ParseDiagnostic, ParseError};
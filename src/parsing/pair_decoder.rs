@@ -1,11 +1,16 @@
 use std::borrow::Cow;
 
-use crate::config::ParseOptions;
+use memchr::{memchr, memchr_iter};
+use smallvec::SmallVec;
+
+use crate::config::{
+    Charset, ComponentKind, DecodeContext, DefaultDecode, DepthOverflow, KeySyntax, ParseOptions,
+};
 use crate::parsing::ParseResult;
 use crate::parsing::errors::ParseLocation;
 
 use super::decoder::decode_component;
-use super::key_path::validate_brackets;
+use super::key_path::{is_well_formed_key_path, validate_brackets};
 
 pub(crate) fn decode_pair<'a>(
     raw_key: &'a str,
@@ -13,28 +18,224 @@ pub(crate) fn decode_pair<'a>(
     key_start: usize,
     value_offset: usize,
     options: &ParseOptions,
+    charset: Charset,
     decode_scratch: &mut Vec<u8>,
 ) -> ParseResult<(Cow<'a, str>, Cow<'a, str>)> {
-    let key = decode_component(
-        raw_key,
-        options.space_as_plus,
-        key_start,
-        ParseLocation::Key,
-        decode_scratch,
-    )?;
-    validate_brackets(key.as_ref(), options.max_depth)?;
+    let key = decode_key(raw_key, key_start, options, charset, decode_scratch)?;
 
-    let value = decode_component(
+    let value = decode_part(
         raw_value,
-        options.space_as_plus,
+        &key,
+        ComponentKind::Value,
         value_offset,
-        ParseLocation::Value,
+        options,
+        charset,
         decode_scratch,
     )?;
 
     Ok((key, value))
 }
 
+pub(crate) fn decode_key<'a>(
+    raw_key: &'a str,
+    key_start: usize,
+    options: &ParseOptions,
+    charset: Charset,
+    decode_scratch: &mut Vec<u8>,
+) -> ParseResult<Cow<'a, str>> {
+    let key = if options.allow_dots && memchr(b'.', raw_key.as_bytes()).is_some() {
+        Cow::Owned(decode_dotted_key(
+            raw_key,
+            key_start,
+            options,
+            charset,
+            decode_scratch,
+        )?)
+    } else {
+        decode_part(
+            raw_key,
+            raw_key,
+            ComponentKind::Key,
+            key_start,
+            options,
+            charset,
+            decode_scratch,
+        )?
+    };
+    if options.key_syntax == KeySyntax::Lenient && !is_well_formed_key_path(key.as_ref()) {
+        return Ok(key);
+    }
+    let max_depth = match options.depth_overflow {
+        DepthOverflow::Error => options.max_depth,
+        DepthOverflow::CollapseRemainder => None,
+    };
+    validate_brackets(key.as_ref(), max_depth)?;
+    Ok(key)
+}
+
+fn decode_dotted_key(
+    raw_key: &str,
+    key_start: usize,
+    options: &ParseOptions,
+    charset: Charset,
+    decode_scratch: &mut Vec<u8>,
+) -> ParseResult<String> {
+    let key_path = match options.decoder {
+        Some(_) => rewrite_dotted_key(raw_key, |key, start, end| {
+            key.push_str(&raw_key[start..end]);
+            Ok(())
+        })?,
+        None => String::new(),
+    };
+
+    rewrite_dotted_key(raw_key, |key, start, end| {
+        let piece = decode_part(
+            &raw_key[start..end],
+            &key_path,
+            ComponentKind::Key,
+            key_start + start,
+            options,
+            charset,
+            decode_scratch,
+        )?;
+        key.push_str(&piece);
+        Ok(())
+    })
+}
+
+fn rewrite_dotted_key<F>(raw_key: &str, mut flush: F) -> ParseResult<String>
+where
+    F: FnMut(&mut String, usize, usize) -> ParseResult<()>,
+{
+    let bytes = raw_key.as_bytes();
+    let mut key = String::with_capacity(raw_key.len() + 8);
+    let mut start = 0usize;
+    let mut depth = 0usize;
+    let mut in_dot_segment = false;
+
+    for (idx, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'[' => {
+                if depth == 0 && in_dot_segment {
+                    flush(&mut key, start, idx)?;
+                    key.push(']');
+                    in_dot_segment = false;
+                    start = idx;
+                }
+                depth += 1;
+            }
+            b']' => depth = depth.saturating_sub(1),
+            b'.' if depth == 0 && is_dot_separator(bytes, idx) => {
+                flush(&mut key, start, idx)?;
+                if in_dot_segment {
+                    key.push(']');
+                }
+                key.push('[');
+                in_dot_segment = true;
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+
+    flush(&mut key, start, bytes.len())?;
+    if in_dot_segment {
+        key.push(']');
+    }
+
+    Ok(key)
+}
+
+fn is_dot_separator(bytes: &[u8], idx: usize) -> bool {
+    idx > 0 && !matches!(bytes.get(idx + 1), None | Some(b'.') | Some(b'['))
+}
+
+pub(crate) type CommaValues<'a> = SmallVec<[Cow<'a, str>; 8]>;
+
+pub(crate) fn decode_comma_values<'a>(
+    raw_value: &'a str,
+    value_offset: usize,
+    key: &str,
+    options: &ParseOptions,
+    charset: Charset,
+    decode_scratch: &mut Vec<u8>,
+) -> ParseResult<CommaValues<'a>> {
+    let mut values = CommaValues::new();
+    let mut start = 0usize;
+    let ends = memchr_iter(b',', raw_value.as_bytes()).chain(std::iter::once(raw_value.len()));
+
+    for end in ends {
+        values.push(decode_part(
+            &raw_value[start..end],
+            key,
+            ComponentKind::Value,
+            value_offset + start,
+            options,
+            charset,
+            decode_scratch,
+        )?);
+        start = end + 1;
+    }
+
+    Ok(values)
+}
+
+struct BuiltinDecode<'s> {
+    space_as_plus: bool,
+    charset: Charset,
+    offset: usize,
+    location: ParseLocation,
+    scratch: &'s mut Vec<u8>,
+}
+
+impl DefaultDecode for BuiltinDecode<'_> {
+    fn decode_default<'c>(&mut self, component: &'c str) -> ParseResult<Cow<'c, str>> {
+        decode_component(
+            component,
+            self.space_as_plus,
+            self.charset,
+            self.offset,
+            self.location,
+            self.scratch,
+        )
+    }
+}
+
+fn decode_part<'a>(
+    raw: &'a str,
+    key_path: &str,
+    kind: ComponentKind,
+    offset: usize,
+    options: &ParseOptions,
+    charset: Charset,
+    decode_scratch: &mut Vec<u8>,
+) -> ParseResult<Cow<'a, str>> {
+    let location = match kind {
+        ComponentKind::Key => ParseLocation::Key,
+        ComponentKind::Value => ParseLocation::Value,
+    };
+    let Some(decoder) = options.decoder.as_deref() else {
+        return decode_component(
+            raw,
+            options.space_as_plus,
+            charset,
+            offset,
+            location,
+            decode_scratch,
+        );
+    };
+
+    let mut builtin = BuiltinDecode {
+        space_as_plus: options.space_as_plus,
+        charset,
+        offset,
+        location,
+        scratch: decode_scratch,
+    };
+    let mut context = DecodeContext::new(key_path, kind, &mut builtin);
+    decoder.decode(raw, &mut context)
+}
+
 #[cfg(test)]
 #[path = "pair_decoder_test.rs"]
 mod pair_decoder_test;
@@ -1,4 +1,5 @@
 use super::*;
+use crate::config::DepthOverflow;
 use crate::parsing::ParseError;
 use crate::parsing::errors::ParseLocation;
 use assert_matches::assert_matches;
@@ -12,8 +13,8 @@ mod decode_pair {
         let options = ParseOptions::default();
         let mut scratch = Vec::new();
 
-        let (key, value) =
-            decode_pair("foo", "bar", 0, 4, &options, &mut scratch).expect("decode succeeds");
+        let (key, value) = decode_pair("foo", "bar", 0, 4, &options, Charset::Utf8, &mut scratch)
+            .expect("decode succeeds");
 
         assert_matches!(key, Cow::Borrowed("foo"));
         assert_matches!(value, Cow::Borrowed("bar"));
@@ -28,8 +29,16 @@ mod decode_pair {
         };
         let mut scratch = Vec::new();
 
-        let (key, value) = decode_pair("hello+world", "value+here", 0, 17, &options, &mut scratch)
-            .expect("decode succeeds");
+        let (key, value) = decode_pair(
+            "hello+world",
+            "value+here",
+            0,
+            17,
+            &options,
+            Charset::Utf8,
+            &mut scratch,
+        )
+        .expect("decode succeeds");
 
         assert_eq!(key.as_ref(), "hello world");
         assert_eq!(value.as_ref(), "value here");
@@ -40,8 +49,8 @@ mod decode_pair {
         let options = ParseOptions::default();
         let mut scratch = Vec::new();
 
-        let error =
-            decode_pair("foo[", "bar", 0, 4, &options, &mut scratch).expect_err("decode fails");
+        let error = decode_pair("foo[", "bar", 0, 4, &options, Charset::Utf8, &mut scratch)
+            .expect_err("decode fails");
 
         assert_matches!(
             error,
@@ -58,7 +67,7 @@ mod decode_pair {
         let options = ParseOptions::default();
         let mut scratch = Vec::new();
 
-        let error = decode_pair("%2Z", "value", 3, 9, &options, &mut scratch)
+        let error = decode_pair("%2Z", "value", 3, 9, &options, Charset::Utf8, &mut scratch)
             .expect_err("invalid key percent encoding should fail");
 
         assert_matches!(
@@ -77,8 +86,16 @@ mod decode_pair {
         let mut scratch = Vec::new();
         let key = "user[address][city]";
 
-        let error = decode_pair(key, "seattle", 0, key.len() + 1, &options, &mut scratch)
-            .expect_err("depth should exceed limit");
+        let error = decode_pair(
+            key,
+            "seattle",
+            0,
+            key.len() + 1,
+            &options,
+            Charset::Utf8,
+            &mut scratch,
+        )
+        .expect_err("depth should exceed limit");
 
         assert_matches!(
             error,
@@ -91,13 +108,35 @@ mod decode_pair {
         );
     }
 
+    #[test]
+    fn should_accept_deep_key_when_collapsing_remainder_then_skip_depth_error() {
+        let options = ParseOptions::new()
+            .max_depth(1)
+            .depth_overflow(DepthOverflow::CollapseRemainder);
+        let mut scratch = Vec::new();
+        let key = "user[address][city]";
+
+        let (decoded, _) = decode_pair(
+            key,
+            "seattle",
+            0,
+            key.len() + 1,
+            &options,
+            Charset::Utf8,
+            &mut scratch,
+        )
+        .expect("collapsing should not reject deep keys");
+
+        assert_eq!(decoded, "user[address][city]");
+    }
+
     #[test]
     fn should_return_invalid_percent_error_when_value_percent_encoding_is_invalid_then_report_index()
      {
         let options = ParseOptions::default();
         let mut scratch = Vec::new();
 
-        let error = decode_pair("flag", "%GG", 0, 5, &options, &mut scratch)
+        let error = decode_pair("flag", "%GG", 0, 5, &options, Charset::Utf8, &mut scratch)
             .expect_err("invalid value percent encoding should fail");
 
         assert_matches!(
@@ -114,10 +153,293 @@ mod decode_pair {
         let options = ParseOptions::default();
         let mut scratch = Vec::new();
 
-        let (key, value) = decode_pair("token", "caf%25C3%25A9", 0, 6, &options, &mut scratch)
-            .expect("percent-decoded value should succeed");
+        let (key, value) = decode_pair(
+            "token",
+            "caf%25C3%25A9",
+            0,
+            6,
+            &options,
+            Charset::Utf8,
+            &mut scratch,
+        )
+        .expect("percent-decoded value should succeed");
 
         assert_matches!(key, Cow::Borrowed("token"));
         assert_matches!(value, Cow::Owned(ref owned) if owned == "caf%C3%A9");
     }
 }
+
+mod decode_comma_values {
+    use super::*;
+
+    #[test]
+    fn should_split_on_raw_commas_when_value_contains_commas_then_return_each_item() {
+        let options = ParseOptions::default();
+        let mut scratch = Vec::new();
+
+        let values = decode_comma_values("1,2,3", 4, "ids", &options, Charset::Utf8, &mut scratch)
+            .expect("decode succeeds");
+
+        assert_eq!(values.as_slice(), ["1", "2", "3"]);
+        assert!(values.iter().all(|value| matches!(value, Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn should_keep_encoded_comma_when_item_contains_percent_two_c_then_decode_without_splitting() {
+        let options = ParseOptions::default();
+        let mut scratch = Vec::new();
+
+        let values =
+            decode_comma_values("a%2Cb,c", 0, "ids", &options, Charset::Utf8, &mut scratch)
+                .expect("decode succeeds");
+
+        assert_eq!(values.as_slice(), ["a,b", "c"]);
+    }
+
+    #[test]
+    fn should_report_absolute_index_when_later_item_is_invalid_then_offset_by_item_start() {
+        let options = ParseOptions::default();
+        let mut scratch = Vec::new();
+
+        let error = decode_comma_values(
+            "ok,b\u{0001}",
+            10,
+            "ids",
+            &options,
+            Charset::Utf8,
+            &mut scratch,
+        )
+        .expect_err("control character should fail");
+
+        assert_matches!(
+            error,
+            ParseError::InvalidCharacter {
+                index: 14,
+                location: ParseLocation::Value,
+                ..
+            }
+        );
+    }
+}
+
+mod decode_key_with_dots {
+    use super::*;
+
+    fn decode_dotted(raw: &str) -> ParseResult<Cow<'_, str>> {
+        let options = ParseOptions::new().allow_dots(true);
+        let mut scratch = Vec::new();
+        decode_key(raw, 0, &options, Charset::Utf8, &mut scratch)
+    }
+
+    #[test]
+    fn should_convert_dots_into_brackets_when_allow_dots_enabled_then_build_bracket_path() {
+        let key = decode_dotted("filter.owner.id").expect("decode succeeds");
+
+        assert_eq!(key.as_ref(), "filter[owner][id]");
+    }
+
+    #[test]
+    fn should_keep_encoded_dot_literal_when_segment_contains_percent_two_e_then_avoid_splitting() {
+        let key = decode_dotted("filter.v1%2E0").expect("decode succeeds");
+
+        assert_eq!(key.as_ref(), "filter[v1.0]");
+    }
+
+    #[test]
+    fn should_mix_dots_and_brackets_when_both_styles_present_then_normalize_to_brackets() {
+        let key = decode_dotted("a[b].c[0].d").expect("decode succeeds");
+
+        assert_eq!(key.as_ref(), "a[b][c][0][d]");
+    }
+
+    #[test]
+    fn should_keep_dots_inside_brackets_when_allow_dots_enabled_then_leave_bracket_content() {
+        let key = decode_dotted("a[b.c]").expect("decode succeeds");
+
+        assert_eq!(key.as_ref(), "a[b.c]");
+    }
+
+    #[test]
+    fn should_keep_leading_trailing_and_doubled_dots_when_no_segment_follows_then_treat_as_literal()
+    {
+        assert_eq!(decode_dotted(".a").expect("decode").as_ref(), ".a");
+        assert_eq!(decode_dotted("a.").expect("decode").as_ref(), "a.");
+        assert_eq!(decode_dotted("a..b").expect("decode").as_ref(), "a.[b]");
+    }
+
+    #[test]
+    fn should_borrow_key_when_dots_are_disabled_then_skip_splitting() {
+        let options = ParseOptions::new();
+        let mut scratch = Vec::new();
+
+        let key =
+            decode_key("a.b", 0, &options, Charset::Utf8, &mut scratch).expect("decode succeeds");
+
+        assert_matches!(key, Cow::Borrowed("a.b"));
+    }
+
+    #[test]
+    fn should_count_dot_segments_toward_depth_when_max_depth_is_set_then_return_depth_error() {
+        let options = ParseOptions::new().allow_dots(true).max_depth(1);
+        let mut scratch = Vec::new();
+
+        let error = decode_key("a.b.c", 0, &options, Charset::Utf8, &mut scratch)
+            .expect_err("depth exceeded");
+
+        assert_matches!(
+            error,
+            ParseError::DepthExceeded {
+                limit: 1,
+                depth: 2,
+                ..
+            }
+        );
+    }
+
+    #[test]
+    fn should_report_raw_index_when_dotted_segment_has_invalid_character_then_use_original_offset()
+    {
+        let options = ParseOptions::new().allow_dots(true);
+        let mut scratch = Vec::new();
+
+        let error = decode_key("ab.c\u{0001}", 5, &options, Charset::Utf8, &mut scratch)
+            .expect_err("control character should fail");
+
+        assert_matches!(
+            error,
+            ParseError::InvalidCharacter {
+                index: 9,
+                location: ParseLocation::Key,
+                ..
+            }
+        );
+    }
+}
+
+mod decode_with_hook {
+    use super::*;
+    use crate::config::{ComponentKind, DecodeContext, Decoder};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Default)]
+    struct RecordingDecoder {
+        calls: Mutex<Vec<(String, String, ComponentKind)>>,
+    }
+
+    impl Decoder for Arc<RecordingDecoder> {
+        fn decode<'a>(
+            &self,
+            component: &'a str,
+            context: &mut DecodeContext<'_>,
+        ) -> Result<Cow<'a, str>, ParseError> {
+            self.calls.lock().expect("lock").push((
+                component.to_string(),
+                context.key_path().to_string(),
+                context.kind(),
+            ));
+            let decoded = context.decode_default(component)?;
+            match context.kind() {
+                ComponentKind::Key => Ok(decoded),
+                ComponentKind::Value => Ok(Cow::Owned(decoded.trim().to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn should_call_hook_for_key_and_value_when_decoder_installed_then_pass_key_path_and_kind() {
+        let recorder = Arc::new(RecordingDecoder::default());
+        let options = ParseOptions::new().decoder(Arc::clone(&recorder));
+        let mut scratch = Vec::new();
+
+        let (key, value) = decode_pair(
+            "user%5Bname%5D",
+            "%20kim%20",
+            0,
+            15,
+            &options,
+            Charset::Utf8,
+            &mut scratch,
+        )
+        .expect("hooked decode succeeds");
+
+        assert_eq!(key, "user[name]");
+        assert_eq!(value, "kim");
+        let calls = recorder.calls.lock().expect("lock");
+        assert_eq!(
+            calls.as_slice(),
+            [
+                (
+                    "user%5Bname%5D".to_string(),
+                    "user%5Bname%5D".to_string(),
+                    ComponentKind::Key
+                ),
+                (
+                    "%20kim%20".to_string(),
+                    "user[name]".to_string(),
+                    ComponentKind::Value
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_pass_bracketed_path_for_dotted_key_pieces_when_allow_dots_then_match_value_path() {
+        let recorder = Arc::new(RecordingDecoder::default());
+        let options = ParseOptions::new()
+            .allow_dots(true)
+            .decoder(Arc::clone(&recorder));
+        let mut scratch = Vec::new();
+
+        let (key, value) = decode_pair("a.b", "x", 0, 4, &options, Charset::Utf8, &mut scratch)
+            .expect("hooked decode succeeds");
+
+        assert_eq!(key, "a[b]");
+        assert_eq!(value, "x");
+        let calls = recorder.calls.lock().expect("lock");
+        assert_eq!(
+            calls.as_slice(),
+            [
+                ("a".to_string(), "a[b]".to_string(), ComponentKind::Key),
+                ("b".to_string(), "a[b]".to_string(), ComponentKind::Key),
+                ("x".to_string(), "a[b]".to_string(), ComponentKind::Value),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_call_hook_per_item_when_decoding_comma_values_then_use_decoded_key_as_path() {
+        let recorder = Arc::new(RecordingDecoder::default());
+        let options = ParseOptions::new().decoder(Arc::clone(&recorder));
+        let mut scratch = Vec::new();
+
+        let values =
+            decode_comma_values("a%20,%20b", 0, "ids", &options, Charset::Utf8, &mut scratch)
+                .expect("hooked decode succeeds");
+
+        assert_eq!(values.as_slice(), ["a", "b"]);
+        let calls = recorder.calls.lock().expect("lock");
+        assert!(
+            calls
+                .iter()
+                .all(|(_, path, kind)| path == "ids" && *kind == ComponentKind::Value)
+        );
+        assert_eq!(calls.len(), 2);
+    }
+
+    #[test]
+    fn should_report_offsets_from_default_decoder_when_hook_delegates_then_keep_absolute_index() {
+        let options = ParseOptions::new().decoder(Arc::new(RecordingDecoder::default()));
+        let mut scratch = Vec::new();
+
+        let error = decode_pair("k", "%zz", 0, 2, &options, Charset::Utf8, &mut scratch)
+            .expect_err("invalid escape should fail");
+
+        assert_matches!(
+            error,
+            ParseError::InvalidPercentEncoding {
+                index: 2,
+                location: ParseLocation::Value
+            }
+        );
+    }
+}
@@ -1,65 +1,132 @@
 use std::borrow::Cow;
 
-use crate::config::DuplicateKeyBehavior;
+use crate::config::{DepthOverflow, DuplicateKeyBehavior, KeySyntax, ParseOptions};
+use crate::nested::insertion::{arena_combine_value, insert_nested_value_arena};
+use crate::nested::parse_key_path;
 use crate::nested::pattern_state::PatternState;
-use crate::nested::{insertion::insert_nested_value_arena, parse_key_path};
 use crate::parsing::{ParseError, ParseResult};
 
 use super::arena::{ArenaQueryMap, ArenaValue, ParseArena};
-use super::key_path::duplicate_key_label;
+use super::key_path::{duplicate_key_label, is_well_formed_key_path};
 
 pub(crate) fn insert_pair_arena<'arena>(
     arena: &'arena ParseArena,
     map: &mut ArenaQueryMap<'arena>,
     pattern_state: &mut PatternState,
     key: Cow<'_, str>,
-    value: Cow<'_, str>,
-    duplicate_keys: DuplicateKeyBehavior,
+    value: Cow<'arena, str>,
+    options: &ParseOptions,
+) -> ParseResult<()> {
+    let value = ArenaValue::string(arena_str(arena, value));
+    insert_arena_value(arena, map, pattern_state, key, value, options)
+}
+
+pub(crate) fn insert_comma_pair_arena<'arena, I>(
+    arena: &'arena ParseArena,
+    map: &mut ArenaQueryMap<'arena>,
+    pattern_state: &mut PatternState,
+    key: Cow<'_, str>,
+    values: I,
+    options: &ParseOptions,
+) -> ParseResult<()>
+where
+    I: IntoIterator<Item = Cow<'arena, str>>,
+    I::IntoIter: ExactSizeIterator,
+{
+    let values = values.into_iter();
+    let mut items = ArenaValue::seq_with_capacity(arena, values.len());
+    if let ArenaValue::Seq(seq) = &mut items {
+        seq.extend(values.map(|value| ArenaValue::string(arena_str(arena, value))));
+    }
+    insert_arena_value(arena, map, pattern_state, key, items, options)
+}
+
+pub(crate) fn insert_null_pair_arena<'arena>(
+    arena: &'arena ParseArena,
+    map: &mut ArenaQueryMap<'arena>,
+    pattern_state: &mut PatternState,
+    key: Cow<'_, str>,
+    options: &ParseOptions,
+) -> ParseResult<()> {
+    insert_arena_value(arena, map, pattern_state, key, ArenaValue::Null, options)
+}
+
+pub(crate) fn insert_raw_pair_arena<'arena>(
+    arena: &'arena ParseArena,
+    map: &mut ArenaQueryMap<'arena>,
+    raw_key: &str,
+    raw_value: &'arena str,
+    options: &ParseOptions,
 ) -> ParseResult<()> {
-    let value_ref = arena.alloc_str(value.as_ref());
+    let value = ArenaValue::string(raw_value);
+    insert_root_value(arena, map, raw_key, value, options.duplicate_keys)
+}
+
+fn arena_str<'arena>(arena: &'arena ParseArena, value: Cow<'arena, str>) -> &'arena str {
+    match value {
+        Cow::Borrowed(text) => text,
+        Cow::Owned(text) => arena.alloc_str(&text),
+    }
+}
 
+fn insert_arena_value<'arena>(
+    arena: &'arena ParseArena,
+    map: &mut ArenaQueryMap<'arena>,
+    pattern_state: &mut PatternState,
+    key: Cow<'_, str>,
+    value: ArenaValue<'arena>,
+    options: &ParseOptions,
+) -> ParseResult<()> {
     if key.is_empty() {
-        return insert_root_value(arena, map, "", value_ref, duplicate_keys);
+        return insert_root_value(arena, map, "", value, options.duplicate_keys);
     }
 
     if !key.is_empty() && !key.contains('[') {
         let key_str = key.as_ref();
-        return insert_root_value(arena, map, key_str, value_ref, duplicate_keys);
+        return insert_root_value(arena, map, key_str, value, options.duplicate_keys);
+    }
+
+    if options.key_syntax == KeySyntax::Lenient && !is_well_formed_key_path(key.as_ref()) {
+        return insert_root_value(arena, map, key.as_ref(), value, options.duplicate_keys);
     }
 
-    let key_segments = parse_key_path(key.as_ref());
-    insert_nested_value_arena(
-        arena,
-        map,
-        &key_segments,
-        value_ref,
-        pattern_state,
-        duplicate_keys,
-    )
+    let collapse_after = match options.depth_overflow {
+        DepthOverflow::Error => None,
+        DepthOverflow::CollapseRemainder => options.max_depth,
+    };
+    let key_segments = parse_key_path(key.as_ref(), collapse_after);
+    insert_nested_value_arena(arena, map, &key_segments, value, pattern_state, options)
 }
 
 fn insert_root_value<'arena>(
     arena: &'arena ParseArena,
     map: &mut ArenaQueryMap<'arena>,
     key: &str,
-    value: &'arena str,
+    value: ArenaValue<'arena>,
     duplicate_keys: DuplicateKeyBehavior,
 ) -> ParseResult<()> {
-    match map.try_insert_str(arena, key, ArenaValue::string(value)) {
-        Ok(()) => Ok(()),
-        Err(()) => match duplicate_keys {
-            DuplicateKeyBehavior::Reject => Err(ParseError::DuplicateRootKey {
-                key: duplicate_key_label(key),
-            }),
-            DuplicateKeyBehavior::FirstWins => Ok(()),
-            DuplicateKeyBehavior::LastWins => {
-                let existing = map
-                    .get_mut(key)
-                    .expect("duplicate key should exist for last-wins behavior");
-                *existing = ArenaValue::string(value);
+    let Err((existing, value)) = map.try_insert_value(arena, key, value) else {
+        return Ok(());
+    };
+
+    match duplicate_keys {
+        DuplicateKeyBehavior::Reject => Err(ParseError::DuplicateRootKey {
+            key: duplicate_key_label(key),
+        }),
+        DuplicateKeyBehavior::FirstWins => Ok(()),
+        DuplicateKeyBehavior::LastWins => {
+            *existing = value;
+            Ok(())
+        }
+        DuplicateKeyBehavior::Combine => {
+            if arena_combine_value(arena, existing, value) {
                 Ok(())
+            } else {
+                Err(ParseError::NestedValueConflict {
+                    parent: duplicate_key_label(key),
+                })
             }
-        },
+        }
     }
 }
 
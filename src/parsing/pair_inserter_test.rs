@@ -21,7 +21,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed("foo"),
             Cow::Borrowed("bar"),
-            DuplicateKeyBehavior::Reject,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject),
         )
         .expect("insert succeeds");
 
@@ -42,7 +42,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed("foo"),
             Cow::Borrowed("first"),
-            DuplicateKeyBehavior::Reject,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject),
         )
         .expect("initial insert succeeds");
 
@@ -52,7 +52,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed("foo"),
             Cow::Borrowed("second"),
-            DuplicateKeyBehavior::Reject,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject),
         )
         .expect_err("duplicate key error");
 
@@ -71,7 +71,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed("foo"),
             Cow::Borrowed("first"),
-            DuplicateKeyBehavior::FirstWins,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::FirstWins),
         )
         .expect("initial insert succeeds");
 
@@ -81,7 +81,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed("foo"),
             Cow::Borrowed("second"),
-            DuplicateKeyBehavior::FirstWins,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::FirstWins),
         )
         .expect("duplicate insert ignored");
 
@@ -102,7 +102,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed("foo"),
             Cow::Borrowed("first"),
-            DuplicateKeyBehavior::LastWins,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins),
         )
         .expect("initial insert succeeds");
 
@@ -112,7 +112,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed("foo"),
             Cow::Borrowed("second"),
-            DuplicateKeyBehavior::LastWins,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::LastWins),
         )
         .expect("duplicate insert overwrites");
 
@@ -122,6 +122,39 @@ mod insert_pair_arena {
         assert_matches!(&entries[0].1, ArenaValue::String(value) if *value == "second");
     }
 
+    #[test]
+    fn should_collect_values_into_sequence_when_flat_key_repeats_and_combine_then_keep_order() {
+        let arena = ParseArena::new();
+        let mut map = map_with_capacity(&arena, 2);
+        let mut pattern_state = acquire_pattern_state();
+        let options = ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Combine);
+
+        for value in ["a", "b", "c"] {
+            insert_pair_arena(
+                &arena,
+                &mut map,
+                &mut pattern_state,
+                Cow::Borrowed("tag"),
+                Cow::Borrowed(value),
+                &options,
+            )
+            .expect("combine insert succeeds");
+        }
+
+        let entries = map.entries_slice();
+        assert_eq!(entries.len(), 1);
+        assert_matches!(&entries[0].1, ArenaValue::Seq(items) => {
+            let values: Vec<_> = items
+                .iter()
+                .map(|item| match item {
+                    ArenaValue::String(text) => *text,
+                    other => panic!("expected string item, got {other:?}"),
+                })
+                .collect();
+            assert_eq!(values, ["a", "b", "c"]);
+        });
+    }
+
     #[test]
     fn should_store_under_empty_label_when_key_is_empty_then_use_empty_key() {
         let arena = ParseArena::new();
@@ -134,7 +167,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed(""),
             Cow::Borrowed("value"),
-            DuplicateKeyBehavior::Reject,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject),
         )
         .expect("empty key insert succeeds");
 
@@ -155,7 +188,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed(""),
             Cow::Borrowed("first"),
-            DuplicateKeyBehavior::Reject,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject),
         )
         .expect("initial insert succeeds");
 
@@ -165,7 +198,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed(""),
             Cow::Borrowed("second"),
-            DuplicateKeyBehavior::Reject,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject),
         )
         .expect_err("duplicate root key should error");
 
@@ -184,7 +217,7 @@ mod insert_pair_arena {
             &mut pattern_state,
             Cow::Borrowed("user[profile][name]"),
             Cow::Borrowed("neo"),
-            DuplicateKeyBehavior::Reject,
+            &ParseOptions::new().duplicate_keys(DuplicateKeyBehavior::Reject),
         )
         .expect("nested insert should succeed");
 
@@ -210,3 +243,60 @@ mod insert_pair_arena {
         });
     }
 }
+
+mod insert_comma_pair_arena {
+    use super::*;
+
+    #[test]
+    fn should_store_sequence_when_values_are_split_then_keep_value_order() {
+        let arena = ParseArena::new();
+        let mut map = map_with_capacity(&arena, 1);
+        let mut pattern_state = acquire_pattern_state();
+
+        insert_comma_pair_arena(
+            &arena,
+            &mut map,
+            &mut pattern_state,
+            Cow::Borrowed("ids"),
+            [Cow::Borrowed("1"), Cow::Owned("2".to_string())],
+            &ParseOptions::new(),
+        )
+        .expect("insert succeeds");
+
+        let entries = map.entries_slice();
+        assert_eq!(entries.len(), 1);
+        assert_matches!(&entries[0].1, ArenaValue::Seq(items) => {
+            assert_eq!(items.len(), 2);
+            assert_matches!(&items[0], ArenaValue::String(value) if *value == "1");
+            assert_matches!(&items[1], ArenaValue::String(value) if *value == "2");
+        });
+    }
+
+    #[test]
+    fn should_return_duplicate_key_error_when_split_key_repeats_then_keep_reject_policy() {
+        let arena = ParseArena::new();
+        let mut map = map_with_capacity(&arena, 1);
+        let mut pattern_state = acquire_pattern_state();
+        insert_pair_arena(
+            &arena,
+            &mut map,
+            &mut pattern_state,
+            Cow::Borrowed("ids"),
+            Cow::Borrowed("0"),
+            &ParseOptions::new(),
+        )
+        .expect("initial insert succeeds");
+
+        let error = insert_comma_pair_arena(
+            &arena,
+            &mut map,
+            &mut pattern_state,
+            Cow::Borrowed("ids"),
+            [Cow::Borrowed("1"), Cow::Borrowed("2")],
+            &ParseOptions::new(),
+        )
+        .expect_err("duplicate key error");
+
+        expect_duplicate_key(error, "ids");
+    }
+}
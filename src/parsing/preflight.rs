@@ -1,4 +1,4 @@
-use crate::config::ParseOptions;
+use crate::config::{MalformedPairBehavior, ParseOptions};
 use crate::parsing::ParseError;
 use crate::parsing::errors::ParseLocation;
 use crate::util::is_ascii_control;
@@ -21,13 +21,20 @@ pub(crate) fn preflight<'a>(
         None => (raw, 0),
     };
 
-    for (idx, ch) in trimmed.char_indices() {
-        check_character(ch, offset + idx)?;
+    if options.malformed_pairs == MalformedPairBehavior::Reject {
+        check_characters(trimmed, offset)?;
     }
 
     Ok((trimmed, offset))
 }
 
+pub(crate) fn check_characters(text: &str, offset: usize) -> Result<(), ParseError> {
+    for (idx, ch) in text.char_indices() {
+        check_character(ch, offset + idx)?;
+    }
+    Ok(())
+}
+
 fn check_character(ch: char, index: usize) -> Result<(), ParseError> {
     if ch == '?' {
         return Err(ParseError::UnexpectedQuestionMark {
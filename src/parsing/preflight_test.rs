@@ -1,5 +1,5 @@
-use super::{ParseError, preflight};
-use crate::config::ParseOptions;
+use super::{ParseError, check_characters, preflight};
+use crate::config::{MalformedPairBehavior, ParseOptions};
 use crate::parsing::errors::ParseLocation;
 use assert_matches::assert_matches;
 
@@ -108,4 +108,28 @@ mod preflight {
             }) if character == ' ' && index == 3 && location == ParseLocation::Query
         );
     }
+
+    #[test]
+    fn should_defer_character_checks_when_malformed_pairs_are_recoverable_then_keep_input() {
+        let raw = "?a=1&b=?x";
+        let options = ParseOptions::new().malformed_pairs(MalformedPairBehavior::Skip);
+
+        let result = preflight(raw, &options).expect("character checks should move per pair");
+
+        assert_eq!(result, ("a=1&b=?x", 1));
+    }
+}
+
+mod check_characters {
+    use super::*;
+
+    #[test]
+    fn should_report_offset_position_when_segment_contains_question_mark_then_include_offset() {
+        let result = check_characters("b=?x", 5);
+
+        assert_matches!(
+            result,
+            Err(ParseError::UnexpectedQuestionMark { index: 7, .. })
+        );
+    }
 }
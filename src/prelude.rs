@@ -1,4 +1,7 @@
 pub use crate::{
-    DuplicateKeyBehavior, OptionsValidationError, ParseOptions, Qs, QsParseError, QsStringifyError,
-    StringifyOptions,
+    ArrayFormat, ArrayLimitBehavior, BoolFormat, BoolLiterals, Charset, ComponentKind,
+    DecodeContext, Decoder, Delimiters, DepthOverflow, DuplicateKeyBehavior, EncodeContext,
+    Encoder, EncodingProfile, KeySyntax, MalformedPairBehavior, MixedContainerPolicy,
+    OptionsValidationError, ParseOptions, Qs, QsParseError, QsStringifyError, QueryMap,
+    SparseArrayBehavior, StringifyOptions, UnknownFieldPolicy, Value,
 };
@@ -3,7 +3,7 @@ use serde::de::DeserializeOwned;
 use thiserror::Error;
 
 use crate::config::OptionsValidationError;
-use crate::parsing::{ParseError, parse};
+use crate::parsing::{ParseError, ParseOutcome, parse, parse_detailed};
 use crate::stringify::{StringifyError, stringify};
 use crate::{ParseOptions, StringifyOptions};
 
@@ -44,6 +44,17 @@ impl Qs {
         parse(input, options).map_err(QsParseError::Parse)
     }
 
+    pub fn parse_detailed<T>(&self, input: impl AsRef<str>) -> Result<ParseOutcome<T>, QsParseError>
+    where
+        T: DeserializeOwned + Default + 'static,
+    {
+        let options = self
+            .parse
+            .as_ref()
+            .ok_or(QsParseError::MissingParseOptions)?;
+        parse_detailed(input, options).map_err(QsParseError::Parse)
+    }
+
     pub fn stringify<T>(&self, data: &T) -> Result<String, QsStringifyError>
     where
         T: Serialize,
@@ -1,4 +1,6 @@
+use crate::model::{OrderedMap, QueryMap, Value};
 use crate::parsing::arena::{ArenaQueryMap, ArenaValue};
+use ahash::RandomState;
 use serde_json::{Map as JsonMap, Value as JsonValue};
 
 pub fn arena_map_to_json_value(query_map: &ArenaQueryMap<'_>) -> JsonValue {
@@ -11,6 +13,7 @@ pub fn arena_map_to_json_value(query_map: &ArenaQueryMap<'_>) -> JsonValue {
 
 fn arena_value_to_json(value: &ArenaValue<'_>) -> JsonValue {
     match value {
+        ArenaValue::Null | ArenaValue::Hole => JsonValue::Null,
         ArenaValue::String(s) => JsonValue::String((*s).to_string()),
         ArenaValue::Seq(items) => {
             let array = items.iter().map(arena_value_to_json).collect();
@@ -26,6 +29,30 @@ fn arena_value_to_json(value: &ArenaValue<'_>) -> JsonValue {
     }
 }
 
+pub fn arena_map_to_query_map(query_map: &ArenaQueryMap<'_>) -> QueryMap {
+    let mut map = QueryMap::with_capacity(query_map.len());
+    for (key, value) in query_map.iter() {
+        map.insert(key.to_string(), arena_value_to_value(value));
+    }
+    map
+}
+
+fn arena_value_to_value(value: &ArenaValue<'_>) -> Value {
+    match value {
+        ArenaValue::Null | ArenaValue::Hole => Value::Null,
+        ArenaValue::String(s) => Value::String((*s).to_string()),
+        ArenaValue::Seq(items) => Value::Array(items.iter().map(arena_value_to_value).collect()),
+        ArenaValue::Map { entries, .. } => {
+            let mut object =
+                OrderedMap::with_capacity_and_hasher(entries.len(), RandomState::default());
+            for (key, value) in entries.iter() {
+                object.insert((*key).to_string(), arena_value_to_value(value));
+            }
+            Value::Object(object)
+        }
+    }
+}
+
 #[cfg(test)]
 #[path = "arena_test.rs"]
 mod arena_test;
@@ -1,29 +1,104 @@
+use crate::config::{BoolLiterals, ParseOptions, UnknownFieldPolicy};
 use crate::parsing::arena::{ArenaQueryMap, ArenaValue};
 use crate::serde_adapter::errors::{
-    DeserializeError, DeserializeErrorKind, PathSegment, format_expected,
+    DeserializeError, DeserializeErrorKind, PathSegment, format_expected, format_path,
 };
+use serde::de::value::BorrowedStrDeserializer;
 use serde::de::{
-    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+    self, Deserialize, DeserializeOwned, DeserializeSeed, EnumAccess, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
 };
+use std::cell::RefCell;
 use std::collections::HashSet;
+use std::rc::Rc;
 
 pub(crate) fn deserialize_from_arena_map<T: DeserializeOwned>(
     map: &ArenaQueryMap<'_>,
 ) -> Result<T, DeserializeError> {
-    T::deserialize(ArenaValueDeserializer::new(
-        super::value_ref::ArenaValueRef::Map(map.entries_slice()),
+    deserialize_with_config(map.entries_slice(), DeserializeConfig::default())
+        .map(|(value, _)| value)
+}
+
+pub(crate) fn deserialize_from_arena_entries<'de, T: Deserialize<'de>>(
+    entries: &'de [(&'de str, ArenaValue<'de>)],
+    options: &ParseOptions,
+) -> Result<(T, Vec<String>), DeserializeError> {
+    deserialize_with_config(entries, DeserializeConfig::new(options))
+}
+
+fn deserialize_with_config<'de, T: Deserialize<'de>>(
+    entries: &'de [(&'de str, ArenaValue<'de>)],
+    config: DeserializeConfig,
+) -> Result<(T, Vec<String>), DeserializeError> {
+    let value = T::deserialize(ArenaValueDeserializer::new(
+        super::value_ref::ArenaValueRef::Map(entries),
         Vec::new(),
-    ))
+        config.clone(),
+    ))?;
+    Ok((value, config.fields.into_collected()))
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct DeserializeConfig {
+    fields: FieldPolicy,
+    empty_as_none: bool,
+    bool_literals: Rc<BoolLiterals>,
+    infer_scalars: bool,
+}
+
+impl DeserializeConfig {
+    fn new(options: &ParseOptions) -> Self {
+        Self {
+            fields: FieldPolicy::new(options.unknown_fields),
+            empty_as_none: options.empty_as_none,
+            bool_literals: Rc::new(options.bool_literals.clone()),
+            infer_scalars: options.infer_scalars,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) enum FieldPolicy {
+    #[default]
+    Reject,
+    Ignore,
+    Collect(Rc<RefCell<Vec<String>>>),
+}
+
+impl FieldPolicy {
+    fn new(policy: UnknownFieldPolicy) -> Self {
+        match policy {
+            UnknownFieldPolicy::Reject => FieldPolicy::Reject,
+            UnknownFieldPolicy::Ignore => FieldPolicy::Ignore,
+            UnknownFieldPolicy::Collect => FieldPolicy::Collect(Rc::default()),
+        }
+    }
+
+    fn into_collected(self) -> Vec<String> {
+        match self {
+            FieldPolicy::Collect(collected) => collected.take(),
+            FieldPolicy::Reject | FieldPolicy::Ignore => Vec::new(),
+        }
+    }
 }
 
 pub(crate) struct ArenaValueDeserializer<'de> {
     value: super::value_ref::ArenaValueRef<'de>,
     path: Vec<PathSegment>,
+    config: DeserializeConfig,
 }
 
 impl<'de> ArenaValueDeserializer<'de> {
-    fn new(value: super::value_ref::ArenaValueRef<'de>, path: Vec<PathSegment>) -> Self {
-        Self { value, path }
+    fn new(
+        value: super::value_ref::ArenaValueRef<'de>,
+        path: Vec<PathSegment>,
+        config: DeserializeConfig,
+    ) -> Self {
+        Self {
+            value,
+            path,
+            config,
+        }
     }
 
     fn error(&self, kind: DeserializeErrorKind) -> DeserializeError {
@@ -32,6 +107,8 @@ impl<'de> ArenaValueDeserializer<'de> {
 
     fn unexpected(&self) -> &'static str {
         match self.value {
+            super::value_ref::ArenaValueRef::Null => "null",
+            super::value_ref::ArenaValueRef::Hole => "missing value",
             super::value_ref::ArenaValueRef::String(_) => "string",
             super::value_ref::ArenaValueRef::Seq(_) => "array",
             super::value_ref::ArenaValueRef::Map(_) => "object",
@@ -40,7 +117,13 @@ impl<'de> ArenaValueDeserializer<'de> {
 
     fn as_str(&self) -> Result<&'de str, DeserializeError> {
         match self.value {
+            super::value_ref::ArenaValueRef::Null => Ok(""),
             super::value_ref::ArenaValueRef::String(s) => Ok(s),
+            super::value_ref::ArenaValueRef::Hole => {
+                Err(self.error(DeserializeErrorKind::ExpectedString {
+                    found: "missing value",
+                }))
+            }
             super::value_ref::ArenaValueRef::Seq(_) => {
                 Err(self.error(DeserializeErrorKind::ExpectedString { found: "array" }))
             }
@@ -82,11 +165,13 @@ impl<'de> ArenaValueDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        match self.value {
-            super::value_ref::ArenaValueRef::Seq(items) => {
-                visitor.visit_seq(ArenaSequenceAccess::new(items.iter(), self.path.clone()))
-            }
-            _ => Err(self.error(DeserializeErrorKind::UnexpectedType {
+        match self.sequence_items() {
+            Some(items) => visitor.visit_seq(ArenaSequenceAccess::new(
+                items,
+                self.path.clone(),
+                self.config.clone(),
+            )),
+            None => Err(self.error(DeserializeErrorKind::UnexpectedType {
                 expected,
                 found: self.unexpected(),
             })),
@@ -104,23 +189,144 @@ impl<'de> ArenaValueDeserializer<'de> {
         V: Visitor<'de>,
         F: FnOnce(usize) -> String,
     {
-        match self.value {
-            super::value_ref::ArenaValueRef::Seq(items) => {
+        match self.sequence_items() {
+            Some(items) => {
                 if items.len() != expected_len {
                     return Err(
                         self.error(DeserializeErrorKind::Message(format_mismatch(items.len())))
                     );
                 }
-                visitor.visit_seq(ArenaSequenceAccess::new(items.iter(), self.path.clone()))
+                visitor.visit_seq(ArenaSequenceAccess::new(
+                    items,
+                    self.path.clone(),
+                    self.config.clone(),
+                ))
             }
-            _ => Err(self.error(DeserializeErrorKind::UnexpectedType {
+            None => Err(self.error(DeserializeErrorKind::UnexpectedType {
                 expected: expected_label,
                 found: self.unexpected(),
             })),
         }
     }
+
+    fn visit_inferred<V>(self, s: &'de str, visitor: V) -> Result<V::Value, DeserializeError>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(value) = self.config.bool_literals.parse(s) {
+            return visitor.visit_bool(value);
+        }
+        match classify_number(s) {
+            Some(NumberShape::Unsigned) => {
+                if let Ok(value) = s.parse::<u64>() {
+                    return visitor.visit_u64(value);
+                }
+            }
+            Some(NumberShape::Signed) => {
+                if let Ok(value) = s.parse::<i64>() {
+                    return visitor.visit_i64(value);
+                }
+            }
+            Some(NumberShape::Float) => {
+                if let Ok(value) = s.parse::<f64>()
+                    && value.is_finite()
+                {
+                    return visitor.visit_f64(value);
+                }
+            }
+            None => {}
+        }
+        visitor.visit_borrowed_str(s)
+    }
+
+    fn sequence_items(&self) -> Option<SequenceItems<'de>> {
+        match self.value {
+            super::value_ref::ArenaValueRef::Seq(items) => {
+                Some(SequenceItems::Slice(items.iter().enumerate()))
+            }
+            super::value_ref::ArenaValueRef::Map(entries) => {
+                let mut indexed = Vec::with_capacity(entries.len());
+                for (key, value) in entries {
+                    indexed.push((parse_sequence_index(key)?, value));
+                }
+                indexed.sort_unstable_by_key(|(index, _)| *index);
+                Some(SequenceItems::Indexed(indexed.into_iter()))
+            }
+            super::value_ref::ArenaValueRef::Hole => Some(SequenceItems::Single(None.into_iter())),
+            scalar => Some(SequenceItems::Single(Some(scalar).into_iter())),
+        }
+    }
+}
+
+enum NumberShape {
+    Unsigned,
+    Signed,
+    Float,
+}
+
+fn classify_number(s: &str) -> Option<NumberShape> {
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+    let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit());
+    if !is_digits(integer) || (integer.len() > 1 && integer.starts_with('0')) {
+        return None;
+    }
+    match fraction {
+        Some(fraction) if is_digits(fraction) => Some(NumberShape::Float),
+        Some(_) => None,
+        None if negative => Some(NumberShape::Signed),
+        None => Some(NumberShape::Unsigned),
+    }
 }
 
+fn parse_sequence_index(key: &str) -> Option<usize> {
+    if key.len() > 1 && key.starts_with('0') {
+        return None;
+    }
+    if !key.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    key.parse().ok()
+}
+
+pub(crate) enum SequenceItems<'de> {
+    Slice(std::iter::Enumerate<std::slice::Iter<'de, ArenaValue<'de>>>),
+    Indexed(std::vec::IntoIter<(usize, &'de ArenaValue<'de>)>),
+    Single(std::option::IntoIter<super::value_ref::ArenaValueRef<'de>>),
+}
+
+impl<'de> Iterator for SequenceItems<'de> {
+    type Item = (usize, super::value_ref::ArenaValueRef<'de>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            SequenceItems::Slice(iter) => iter
+                .next()
+                .map(|(index, value)| (index, super::value_ref::ArenaValueRef::from_value(value))),
+            SequenceItems::Indexed(iter) => iter
+                .next()
+                .map(|(index, value)| (index, super::value_ref::ArenaValueRef::from_value(value))),
+            SequenceItems::Single(iter) => iter.next().map(|value| (0, value)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            SequenceItems::Slice(iter) => iter.size_hint(),
+            SequenceItems::Indexed(iter) => iter.size_hint(),
+            SequenceItems::Single(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for SequenceItems<'_> {}
+
 impl<'de> de::Deserializer<'de> for ArenaValueDeserializer<'de> {
     type Error = DeserializeError;
 
@@ -129,6 +335,11 @@ impl<'de> de::Deserializer<'de> for ArenaValueDeserializer<'de> {
         V: Visitor<'de>,
     {
         match self.value {
+            super::value_ref::ArenaValueRef::Null => visitor.visit_unit(),
+            super::value_ref::ArenaValueRef::Hole => visitor.visit_none(),
+            super::value_ref::ArenaValueRef::String(s) if self.config.infer_scalars => {
+                self.visit_inferred(s, visitor)
+            }
             super::value_ref::ArenaValueRef::String(_) => self.deserialize_str(visitor),
             super::value_ref::ArenaValueRef::Seq(_) => self.deserialize_seq(visitor),
             super::value_ref::ArenaValueRef::Map(_) => self.deserialize_map(visitor),
@@ -139,12 +350,19 @@ impl<'de> de::Deserializer<'de> for ArenaValueDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
+        if let super::value_ref::ArenaValueRef::Null = self.value {
+            return match self.config.bool_literals.parse_bare_key() {
+                Some(value) => visitor.visit_bool(value),
+                None => Err(self.error(DeserializeErrorKind::InvalidBool {
+                    value: String::new(),
+                })),
+            };
+        }
         let s = self.as_str()?;
-        match s {
-            "true" => visitor.visit_bool(true),
-            "false" => visitor.visit_bool(false),
-            other => Err(self.error(DeserializeErrorKind::InvalidBool {
-                value: other.to_string(),
+        match self.config.bool_literals.parse(s) {
+            Some(value) => visitor.visit_bool(value),
+            None => Err(self.error(DeserializeErrorKind::InvalidBool {
+                value: s.to_string(),
             })),
         }
     }
@@ -284,7 +502,15 @@ impl<'de> de::Deserializer<'de> for ArenaValueDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_some(self)
+        match self.value {
+            super::value_ref::ArenaValueRef::Null | super::value_ref::ArenaValueRef::Hole => {
+                visitor.visit_none()
+            }
+            super::value_ref::ArenaValueRef::String("") if self.config.empty_as_none => {
+                visitor.visit_none()
+            }
+            _ => visitor.visit_some(self),
+        }
     }
 
     fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -311,7 +537,9 @@ impl<'de> de::Deserializer<'de> for ArenaValueDeserializer<'de> {
         V: Visitor<'de>,
     {
         match self.value {
-            super::value_ref::ArenaValueRef::String("") => visitor.visit_unit(),
+            super::value_ref::ArenaValueRef::String("") | super::value_ref::ArenaValueRef::Null => {
+                visitor.visit_unit()
+            }
             _ => Err(self.error(DeserializeErrorKind::UnexpectedType {
                 expected: name,
                 found: self.unexpected(),
@@ -370,6 +598,7 @@ impl<'de> de::Deserializer<'de> for ArenaValueDeserializer<'de> {
                 value: None,
                 path: self.path.clone(),
                 pending_key: None,
+                config: self.config.clone(),
             }),
             _ => Err(self.error(DeserializeErrorKind::UnexpectedType {
                 expected: "object",
@@ -396,6 +625,7 @@ impl<'de> de::Deserializer<'de> for ArenaValueDeserializer<'de> {
                     seen: HashSet::with_capacity(map.len()),
                     path: self.path.clone(),
                     pending_key: None,
+                    config: self.config.clone(),
                 })
             }
             _ => Err(self.error(DeserializeErrorKind::ExpectedObject {
@@ -414,11 +644,29 @@ impl<'de> de::Deserializer<'de> for ArenaValueDeserializer<'de> {
     where
         V: Visitor<'de>,
     {
-        drop(visitor);
-        Err(self.error(DeserializeErrorKind::Message(format!(
-            "enum `{name}` with variants [{}] cannot be deserialized from query strings",
-            format_expected(variants)
-        ))))
+        match self.value {
+            super::value_ref::ArenaValueRef::String(variant) => {
+                visitor.visit_enum(ArenaEnumAccess {
+                    variant,
+                    value: None,
+                    path: self.path,
+                    config: self.config,
+                })
+            }
+            super::value_ref::ArenaValueRef::Map([(variant, value)]) => {
+                visitor.visit_enum(ArenaEnumAccess {
+                    variant,
+                    value: Some(value),
+                    path: self.path,
+                    config: self.config,
+                })
+            }
+            _ => Err(self.error(DeserializeErrorKind::Message(format!(
+                "enum `{name}` expects a variant name or a single-entry object with one of [{}], found {}",
+                format_expected(variants),
+                self.unexpected()
+            )))),
+        }
     }
 
     fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -436,20 +684,109 @@ impl<'de> de::Deserializer<'de> for ArenaValueDeserializer<'de> {
     }
 }
 
-pub(crate) struct ArenaSequenceAccess<'de> {
-    iter: std::slice::Iter<'de, ArenaValue<'de>>,
+pub(crate) struct ArenaEnumAccess<'de> {
+    variant: &'de str,
+    value: Option<&'de ArenaValue<'de>>,
     path: Vec<PathSegment>,
-    index: usize,
+    config: DeserializeConfig,
 }
 
-impl<'de> ArenaSequenceAccess<'de> {
-    fn new(iter: std::slice::Iter<'de, ArenaValue<'de>>, path: Vec<PathSegment>) -> Self {
-        Self {
-            iter,
+impl<'de> ArenaEnumAccess<'de> {
+    fn error(&self, kind: DeserializeErrorKind) -> DeserializeError {
+        DeserializeError::from_kind(kind).with_path(self.path.clone())
+    }
+
+    fn payload(
+        &self,
+        expected: &'static str,
+    ) -> Result<ArenaValueDeserializer<'de>, DeserializeError> {
+        let value = self.value.ok_or_else(|| {
+            self.error(DeserializeErrorKind::UnexpectedType {
+                expected,
+                found: "unit variant",
+            })
+        })?;
+        let mut path = self.path.clone();
+        path.push(PathSegment::Key(self.variant.to_string()));
+        Ok(ArenaValueDeserializer::new(
+            super::value_ref::ArenaValueRef::from_value(value),
             path,
-            index: 0,
+            self.config.clone(),
+        ))
+    }
+}
+
+impl<'de> EnumAccess<'de> for ArenaEnumAccess<'de> {
+    type Error = DeserializeError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed
+            .deserialize(BorrowedStrDeserializer::<DeserializeError>::new(
+                self.variant,
+            ))
+            .map_err(|err| err.with_path(self.path.clone()))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for ArenaEnumAccess<'de> {
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.value {
+            None | Some(ArenaValue::String("")) | Some(ArenaValue::Null) => Ok(()),
+            Some(_) => Err(self.error(DeserializeErrorKind::UnexpectedType {
+                expected: "unit variant",
+                found: "variant payload",
+            })),
         }
     }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.payload("newtype variant")?)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(self.payload("tuple variant")?, len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(
+            self.payload("struct variant")?,
+            "struct variant",
+            fields,
+            visitor,
+        )
+    }
+}
+
+pub(crate) struct ArenaSequenceAccess<'de> {
+    iter: SequenceItems<'de>,
+    path: Vec<PathSegment>,
+    config: DeserializeConfig,
+}
+
+impl<'de> ArenaSequenceAccess<'de> {
+    fn new(iter: SequenceItems<'de>, path: Vec<PathSegment>, config: DeserializeConfig) -> Self {
+        Self { iter, path, config }
+    }
 }
 
 impl<'de> SeqAccess<'de> for ArenaSequenceAccess<'de> {
@@ -459,14 +796,10 @@ impl<'de> SeqAccess<'de> for ArenaSequenceAccess<'de> {
     where
         T: DeserializeSeed<'de>,
     {
-        if let Some(value) = self.iter.next() {
+        if let Some((index, value)) = self.iter.next() {
             let mut path = self.path.clone();
-            path.push(PathSegment::Index(self.index));
-            self.index += 1;
-            let deserializer = ArenaValueDeserializer::new(
-                super::value_ref::ArenaValueRef::from_value(value),
-                path,
-            );
+            path.push(PathSegment::Index(index));
+            let deserializer = ArenaValueDeserializer::new(value, path, self.config.clone());
             seed.deserialize(deserializer).map(Some)
         } else {
             Ok(None)
@@ -479,6 +812,7 @@ pub(crate) struct ArenaMapDeserializer<'de> {
     value: Option<&'de ArenaValue<'de>>,
     path: Vec<PathSegment>,
     pending_key: Option<PathSegment>,
+    config: DeserializeConfig,
 }
 
 impl<'de> MapAccess<'de> for ArenaMapDeserializer<'de> {
@@ -491,8 +825,8 @@ impl<'de> MapAccess<'de> for ArenaMapDeserializer<'de> {
         if let Some((key, value)) = self.iter.next() {
             self.value = Some(value);
             self.pending_key = Some(PathSegment::Key(key.to_string()));
-            let key_deser = key.to_string().into_deserializer();
-            seed.deserialize(key_deser).map(Some)
+            seed.deserialize(BorrowedStrDeserializer::new(key))
+                .map(Some)
         } else {
             Ok(None)
         }
@@ -518,6 +852,7 @@ impl<'de> MapAccess<'de> for ArenaMapDeserializer<'de> {
         seed.deserialize(ArenaValueDeserializer::new(
             super::value_ref::ArenaValueRef::from_value(value),
             path,
+            self.config.clone(),
         ))
     }
 }
@@ -529,6 +864,7 @@ pub(crate) struct ArenaStructDeserializer<'de> {
     seen: HashSet<&'de str>,
     path: Vec<PathSegment>,
     pending_key: Option<PathSegment>,
+    config: DeserializeConfig,
 }
 
 impl<'de> MapAccess<'de> for ArenaStructDeserializer<'de> {
@@ -538,18 +874,27 @@ impl<'de> MapAccess<'de> for ArenaStructDeserializer<'de> {
     where
         K: DeserializeSeed<'de>,
     {
-        if let Some((key, value)) = self.iter.next() {
+        for (key, value) in self.iter.by_ref() {
             let key_str = *key;
             if !self.allowed.contains(&key_str) {
                 let mut path = self.path.clone();
                 path.push(PathSegment::Key(key_str.to_string()));
-                return Err(
-                    DeserializeError::from_kind(DeserializeErrorKind::UnknownField {
-                        field: key_str.to_string(),
-                        expected: format_expected(self.allowed),
-                    })
-                    .with_path(path),
-                );
+                match &self.config.fields {
+                    FieldPolicy::Reject => {
+                        return Err(DeserializeError::from_kind(
+                            DeserializeErrorKind::UnknownField {
+                                field: key_str.to_string(),
+                                expected: format_expected(self.allowed),
+                            },
+                        )
+                        .with_path(path));
+                    }
+                    FieldPolicy::Ignore => {}
+                    FieldPolicy::Collect(collected) => {
+                        collected.borrow_mut().push(format_path(&path));
+                        continue;
+                    }
+                }
             }
             if !self.seen.insert(key_str) {
                 let mut path = self.path.clone();
@@ -563,11 +908,11 @@ impl<'de> MapAccess<'de> for ArenaStructDeserializer<'de> {
             }
             self.value = Some(value);
             self.pending_key = Some(PathSegment::Key(key_str.to_string()));
-            let key_deser = key_str.to_string().into_deserializer();
-            seed.deserialize(key_deser).map(Some)
-        } else {
-            Ok(None)
+            return seed
+                .deserialize(BorrowedStrDeserializer::new(key_str))
+                .map(Some);
         }
+        Ok(None)
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
@@ -590,6 +935,7 @@ impl<'de> MapAccess<'de> for ArenaStructDeserializer<'de> {
         seed.deserialize(ArenaValueDeserializer::new(
             super::value_ref::ArenaValueRef::from_value(value),
             path,
+            self.config.clone(),
         ))
     }
 }
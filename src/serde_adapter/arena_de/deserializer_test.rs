@@ -1,9 +1,11 @@
 use super::super::value_ref::ArenaValueRef;
 use super::{
-    ArenaMapDeserializer, ArenaStructDeserializer, ArenaValueDeserializer,
-    deserialize_from_arena_map,
+    ArenaMapDeserializer, ArenaStructDeserializer, ArenaValueDeserializer, DeserializeConfig,
+    deserialize_from_arena_entries, deserialize_from_arena_map,
 };
 use crate::arena_helpers::{alloc_key, map_with_capacity};
+use crate::config::{BoolFormat, BoolLiterals, ParseOptions, UnknownFieldPolicy};
+use crate::model::Value;
 use crate::parsing::arena::{ArenaValue, ParseArena};
 use crate::parsing_helpers::{make_sequence, make_string};
 use crate::serde_adapter::errors::{DeserializeErrorKind, PathSegment};
@@ -11,7 +13,11 @@ use assert_matches::assert_matches;
 use serde::Deserialize;
 
 fn deserializer_for<'arena>(value: &'arena ArenaValue<'arena>) -> ArenaValueDeserializer<'arena> {
-    ArenaValueDeserializer::new(ArenaValueRef::from_value(value), Vec::new())
+    ArenaValueDeserializer::new(
+        ArenaValueRef::from_value(value),
+        Vec::new(),
+        DeserializeConfig::default(),
+    )
 }
 
 mod map_deserializer {
@@ -116,6 +122,7 @@ mod map_deserializer {
             value: None,
             path: vec![PathSegment::Key("root".into())],
             pending_key: None,
+            config: DeserializeConfig::default(),
         };
 
         struct UnitSeed;
@@ -150,6 +157,7 @@ mod map_deserializer {
             value: None,
             path: Vec::new(),
             pending_key: None,
+            config: DeserializeConfig::default(),
         };
 
         struct KeySeed;
@@ -203,6 +211,7 @@ mod map_deserializer {
             seen: std::collections::HashSet::with_capacity(0),
             path: vec![PathSegment::Key("root".into())],
             pending_key: None,
+            config: DeserializeConfig::default(),
         };
 
         struct UnitSeed;
@@ -226,6 +235,126 @@ mod map_deserializer {
     }
 }
 
+mod unknown_field_policy {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Profile {
+        name: String,
+        address: Address,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Address {
+        city: String,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Strict {
+        name: String,
+    }
+
+    fn profile_map(arena: &ParseArena) -> crate::parsing::arena::ArenaQueryMap<'_> {
+        let mut address = arena.alloc_vec();
+        address.push((alloc_key(arena, "city"), make_string(arena, "Seoul")));
+        address.push((alloc_key(arena, "zip"), make_string(arena, "04524")));
+
+        let mut map = map_with_capacity(arena, 3);
+        map.try_insert_str(arena, "utm_source", make_string(arena, "mail"))
+            .expect("unique key should insert");
+        map.try_insert_str(arena, "name", make_string(arena, "Dana"))
+            .expect("unique key should insert");
+        map.try_insert_str(
+            arena,
+            "address",
+            ArenaValue::Map {
+                entries: address,
+                index: Default::default(),
+            },
+        )
+        .expect("unique key should insert");
+        map
+    }
+
+    fn expected_profile() -> Profile {
+        Profile {
+            name: "Dana".to_string(),
+            address: Address {
+                city: "Seoul".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_field_when_policy_is_reject_then_return_unknown_field_error() {
+        let arena = ParseArena::new();
+        let map = profile_map(&arena);
+
+        let error = deserialize_from_arena_entries::<Profile>(
+            map.entries_slice(),
+            &ParseOptions::new().unknown_fields(UnknownFieldPolicy::Reject),
+        )
+        .expect_err("unknown field should fail");
+
+        assert_matches!(
+            error.kind(),
+            DeserializeErrorKind::UnknownField { field, .. } if field == "utm_source"
+        );
+    }
+
+    #[test]
+    fn should_skip_unknown_fields_when_policy_is_ignore_then_return_value_without_paths() {
+        let arena = ParseArena::new();
+        let map = profile_map(&arena);
+
+        let (profile, unknown) = deserialize_from_arena_entries::<Profile>(
+            map.entries_slice(),
+            &ParseOptions::new().unknown_fields(UnknownFieldPolicy::Ignore),
+        )
+        .expect("unknown fields should be ignored");
+
+        assert_eq!(profile, expected_profile());
+        assert!(unknown.is_empty());
+    }
+
+    #[test]
+    fn should_collect_nested_unknown_paths_when_policy_is_collect_then_return_value_and_paths() {
+        let arena = ParseArena::new();
+        let map = profile_map(&arena);
+
+        let (profile, unknown) = deserialize_from_arena_entries::<Profile>(
+            map.entries_slice(),
+            &ParseOptions::new().unknown_fields(UnknownFieldPolicy::Collect),
+        )
+        .expect("unknown fields should be collected");
+
+        assert_eq!(profile, expected_profile());
+        assert_eq!(
+            unknown,
+            vec!["utm_source".to_string(), "address.zip".to_string()]
+        );
+    }
+
+    #[test]
+    fn should_defer_to_serde_when_policy_is_ignore_and_type_denies_unknown_fields_then_fail() {
+        let arena = ParseArena::new();
+        let mut map = map_with_capacity(&arena, 2);
+        map.try_insert_str(&arena, "name", make_string(&arena, "Dana"))
+            .expect("unique key should insert");
+        map.try_insert_str(&arena, "extra", make_string(&arena, "x"))
+            .expect("unique key should insert");
+
+        let error = deserialize_from_arena_entries::<Strict>(
+            map.entries_slice(),
+            &ParseOptions::new().unknown_fields(UnknownFieldPolicy::Ignore),
+        )
+        .expect_err("deny_unknown_fields should still apply");
+
+        assert!(error.to_string().contains("unknown field `extra`"));
+    }
+}
+
 mod arena_value_deserializer {
     use super::*;
     use serde::de::{Deserializer, SeqAccess, Visitor};
@@ -236,12 +365,19 @@ mod arena_value_deserializer {
         count: u8,
     }
 
-    #[derive(Debug, Deserialize)]
+    #[derive(Debug, Deserialize, PartialEq)]
     enum OperatingMode {
         Fast,
         Slow,
     }
 
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Filter {
+        Limit(u32),
+        Between(u8, u8),
+        Range { min: u8, max: u8 },
+    }
+
     #[derive(Debug, Deserialize, PartialEq)]
     struct Wrapper(String);
 
@@ -697,19 +833,19 @@ mod arena_value_deserializer {
     }
 
     #[test]
-    fn should_report_unexpected_type_when_tuple_expected_but_value_is_string_then_return_expected_sequence_error()
+    fn should_report_length_mismatch_when_tuple_expected_but_value_is_string_then_treat_string_as_single_element()
      {
         let arena = ParseArena::new();
         let value = make_string(&arena, "not-a-tuple");
         let deserializer = deserializer_for(&value);
 
         let error = <(u8, u8)>::deserialize(deserializer)
-            .expect_err("string should not deserialize as tuple");
+            .expect_err("single string should not fill a pair");
 
         assert_matches!(
             error.kind(),
-            DeserializeErrorKind::UnexpectedType { expected, found }
-                if *expected == "tuple" && *found == "string"
+            DeserializeErrorKind::Message(message)
+                if message == "expected tuple of length 2, found 1"
         );
         assert_eq!(error.path(), &[]);
     }
@@ -904,53 +1040,41 @@ mod arena_value_deserializer {
     }
 
     #[test]
-    fn should_report_unexpected_type_when_sequence_expected_but_scalar_provided_then_return_expected_sequence_error()
-     {
+    fn should_wrap_scalar_when_sequence_expected_then_return_single_element_sequence() {
         let arena = ParseArena::new();
         let value = make_string(&arena, "scalar");
         let deserializer = deserializer_for(&value);
 
-        let error = <Vec<String>>::deserialize(deserializer)
-            .expect_err("scalar cannot deserialize into sequence");
+        let result = <Vec<String>>::deserialize(deserializer)
+            .expect("scalar should deserialize into sequence");
 
-        assert_matches!(
-            error.kind(),
-            DeserializeErrorKind::UnexpectedType { expected, found }
-                if *expected == "array" && *found == "string"
-        );
-        assert!(error.path().is_empty());
+        assert_eq!(result, vec!["scalar".to_string()]);
     }
 
     #[test]
-    fn should_report_unexpected_type_for_tuple_when_scalar_provided_then_return_expected_tuple_error()
-     {
+    fn should_wrap_scalar_when_single_element_tuple_expected_then_return_tuple() {
         let arena = ParseArena::new();
-        let value = make_string(&arena, "scalar");
+        let value = make_string(&arena, "7");
 
-        let error = <(u8, u8)>::deserialize(deserializer_for(&value))
-            .expect_err("scalar cannot deserialize into tuple");
+        let result = <(u8,)>::deserialize(deserializer_for(&value))
+            .expect("scalar should deserialize into single element tuple");
 
-        assert_matches!(
-            error.kind(),
-            DeserializeErrorKind::UnexpectedType { expected, found }
-                if *expected == "tuple" && *found == "string"
-        );
-        assert!(error.path().is_empty());
+        assert_eq!(result, (7,));
     }
 
     #[test]
-    fn should_report_unexpected_type_for_tuple_struct_when_scalar_provided_then_return_expected_tuple_struct_error()
+    fn should_report_length_mismatch_for_tuple_struct_when_scalar_provided_then_return_tuple_struct_error()
      {
         let arena = ParseArena::new();
         let value = make_string(&arena, "scalar");
 
         let error = Pair::deserialize(deserializer_for(&value))
-            .expect_err("scalar cannot deserialize into tuple struct");
+            .expect_err("scalar cannot fill a two element tuple struct");
 
         assert_matches!(
             error.kind(),
-            DeserializeErrorKind::UnexpectedType { expected, found }
-                if *expected == "Pair" && *found == "string"
+            DeserializeErrorKind::Message(message)
+                if message == "expected tuple struct `Pair` with 2 elements, found 1"
         );
         assert!(error.path().is_empty());
     }
@@ -997,21 +1121,129 @@ mod arena_value_deserializer {
     }
 
     #[test]
-    fn should_reject_enumeration_deserialization_when_enum_is_not_supported_then_return_unsupported_error()
-     {
+    fn should_deserialize_unit_variant_when_value_is_variant_name_then_return_variant() {
+        let arena = ParseArena::new();
+        let value = make_string(&arena, "Slow");
+        let deserializer = deserializer_for(&value);
+
+        let mode = OperatingMode::deserialize(deserializer).expect("unit variant should parse");
+
+        assert_eq!(mode, OperatingMode::Slow);
+    }
+
+    #[test]
+    fn should_report_unknown_variant_when_name_does_not_match_then_include_expected_variants() {
         let arena = ParseArena::new();
-        let value = make_string(&arena, "Fast");
+        let value = make_string(&arena, "Medium");
         let deserializer = deserializer_for(&value);
 
         let error = OperatingMode::deserialize(deserializer)
-            .expect_err("enum deserialization should be unsupported");
+            .expect_err("unknown variant should be rejected");
 
         assert_eq!(
             error.to_string(),
-            "enum `OperatingMode` with variants [Fast, Slow] cannot be deserialized from query strings"
+            "unknown variant `Medium`, expected `Fast` or `Slow`"
         );
     }
 
+    #[test]
+    fn should_deserialize_newtype_variant_when_map_has_single_entry_then_use_entry_as_payload() {
+        let arena = ParseArena::new();
+        let mut entries = arena.alloc_vec();
+        entries.push((alloc_key(&arena, "Limit"), make_string(&arena, "42")));
+        let value = ArenaValue::Map {
+            entries,
+            index: Default::default(),
+        };
+        let deserializer = deserializer_for(&value);
+
+        let filter = Filter::deserialize(deserializer).expect("newtype variant should parse");
+
+        assert_eq!(filter, Filter::Limit(42));
+    }
+
+    #[test]
+    fn should_deserialize_struct_variant_when_map_entry_holds_fields_then_populate_variant() {
+        let arena = ParseArena::new();
+        let mut bounds = arena.alloc_vec();
+        bounds.push((alloc_key(&arena, "min"), make_string(&arena, "1")));
+        bounds.push((alloc_key(&arena, "max"), make_string(&arena, "9")));
+        let mut root = map_with_capacity(&arena, 1);
+        root.try_insert_str(
+            &arena,
+            "Range",
+            ArenaValue::Map {
+                entries: bounds,
+                index: Default::default(),
+            },
+        )
+        .expect("unique key should insert");
+
+        let filter =
+            deserialize_from_arena_map::<Filter>(&root).expect("struct variant should parse");
+
+        assert_eq!(filter, Filter::Range { min: 1, max: 9 });
+    }
+
+    #[test]
+    fn should_deserialize_tuple_variant_when_map_entry_holds_sequence_then_populate_variant() {
+        let arena = ParseArena::new();
+        let mut root = map_with_capacity(&arena, 1);
+        root.try_insert_str(&arena, "Between", make_sequence(&arena, &["3", "7"]))
+            .expect("unique key should insert");
+
+        let filter =
+            deserialize_from_arena_map::<Filter>(&root).expect("tuple variant should parse");
+
+        assert_eq!(filter, Filter::Between(3, 7));
+    }
+
+    #[test]
+    fn should_reject_enum_when_map_has_multiple_entries_then_report_expected_shape() {
+        let arena = ParseArena::new();
+        let mut root = map_with_capacity(&arena, 2);
+        root.try_insert_str(&arena, "Limit", make_string(&arena, "1"))
+            .expect("unique key should insert");
+        root.try_insert_str(&arena, "Between", make_string(&arena, "2"))
+            .expect("unique key should insert");
+
+        let error =
+            deserialize_from_arena_map::<Filter>(&root).expect_err("ambiguous enum should fail");
+
+        assert_eq!(
+            error.to_string(),
+            "enum `Filter` expects a variant name or a single-entry object with one of [Limit, Between, Range], found object"
+        );
+    }
+
+    #[test]
+    fn should_reject_newtype_variant_when_value_is_bare_name_then_report_missing_payload() {
+        let arena = ParseArena::new();
+        let value = make_string(&arena, "Limit");
+        let deserializer = deserializer_for(&value);
+
+        let error = Filter::deserialize(deserializer).expect_err("payload should be required");
+
+        assert_matches!(
+            error.kind(),
+            DeserializeErrorKind::UnexpectedType { expected, found }
+                if *expected == "newtype variant" && *found == "unit variant"
+        );
+    }
+
+    #[test]
+    fn should_record_variant_in_path_when_payload_fails_then_point_at_variant() {
+        let arena = ParseArena::new();
+        let mut root = map_with_capacity(&arena, 1);
+        root.try_insert_str(&arena, "Limit", make_string(&arena, "many"))
+            .expect("unique key should insert");
+
+        let error =
+            deserialize_from_arena_map::<Filter>(&root).expect_err("invalid payload should fail");
+
+        assert_eq!(error.path(), &[PathSegment::Key("Limit".into())]);
+    }
+
     #[test]
     fn should_return_borrowed_bytes_when_deserializing_bytes_then_expose_underlying_slice() {
         let arena = ParseArena::new();
@@ -1203,6 +1435,7 @@ mod arena_value_deserializer {
             value: None,
             path: Vec::new(),
             pending_key: None,
+            config: DeserializeConfig::default(),
         };
 
         let error = serde::de::MapAccess::next_value_seed(&mut map_deserializer, UnitSeed)
@@ -1227,6 +1460,7 @@ mod arena_value_deserializer {
             seen: std::collections::HashSet::new(),
             path: Vec::new(),
             pending_key: None,
+            config: DeserializeConfig::default(),
         };
 
         let error = serde::de::MapAccess::next_value_seed(&mut struct_deserializer, UnitSeed)
@@ -1250,6 +1484,7 @@ mod arena_value_deserializer {
             value: Some(&missing_value),
             path: vec![PathSegment::Key("root".to_string())],
             pending_key: None,
+            config: DeserializeConfig::default(),
         };
 
         let error = serde::de::MapAccess::next_value_seed(&mut map_deserializer, InvalidBoolSeed)
@@ -1283,6 +1518,7 @@ mod arena_value_deserializer {
             seen: HashSet::new(),
             path: vec![PathSegment::Key("root".to_string())],
             pending_key: None,
+            config: DeserializeConfig::default(),
         };
 
         let error =
@@ -1302,3 +1538,395 @@ mod arena_value_deserializer {
         );
     }
 }
+
+mod null_value {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Flags {
+        debug: bool,
+        note: Option<String>,
+        label: String,
+    }
+
+    #[test]
+    fn should_map_null_by_target_type_when_fields_are_bare_then_produce_true_none_and_empty() {
+        let arena = ParseArena::new();
+        let mut map = map_with_capacity(&arena, 3);
+        for key in ["debug", "note", "label"] {
+            map.try_insert_str(&arena, key, ArenaValue::Null)
+                .expect("unique key should insert");
+        }
+        let options = ParseOptions::new().bool_literals(BoolLiterals::new().bare_key(true));
+
+        let (result, _) = deserialize_from_arena_entries::<Flags>(map.entries_slice(), &options)
+            .expect("deserialization should succeed");
+
+        assert_eq!(
+            result,
+            Flags {
+                debug: true,
+                note: None,
+                label: String::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn should_reject_bare_bool_when_bare_key_is_not_configured_then_report_invalid_bool() {
+        let value = ArenaValue::Null;
+
+        let error = bool::deserialize(deserializer_for(&value))
+            .expect_err("bare key should not imply true by default");
+
+        assert_matches!(
+            error.kind(),
+            DeserializeErrorKind::InvalidBool { value } if value.is_empty()
+        );
+    }
+
+    #[test]
+    fn should_deserialize_null_into_query_value_when_field_is_bare_then_keep_null() {
+        let value = ArenaValue::Null;
+
+        let result = Value::deserialize(deserializer_for(&value)).expect("null should deserialize");
+
+        assert_eq!(result, Value::Null);
+    }
+
+    #[test]
+    fn should_visit_unit_when_deserializing_null_as_any_then_produce_json_null() {
+        let value = ArenaValue::Null;
+
+        let result = serde_json::Value::deserialize(deserializer_for(&value))
+            .expect("null should deserialize");
+
+        assert_eq!(result, serde_json::Value::Null);
+    }
+
+    #[test]
+    fn should_reject_null_when_target_is_number_then_report_invalid_number() {
+        let value = ArenaValue::Null;
+
+        let error = u8::deserialize(deserializer_for(&value)).expect_err("null is not a number");
+
+        assert_matches!(error.kind(), DeserializeErrorKind::InvalidNumber { value } if value.is_empty());
+    }
+}
+
+mod empty_as_none {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Search {
+        page: Option<u32>,
+        query: Option<String>,
+        pages: Vec<Option<u32>>,
+    }
+
+    fn search_map<'arena>(
+        arena: &'arena ParseArena,
+    ) -> crate::parsing::arena::ArenaQueryMap<'arena> {
+        let mut map = map_with_capacity(arena, 3);
+        map.try_insert_str(arena, "page", make_string(arena, ""))
+            .expect("unique key should insert");
+        map.try_insert_str(arena, "query", make_string(arena, ""))
+            .expect("unique key should insert");
+        map.try_insert_str(arena, "pages", make_sequence(arena, &["", "2"]))
+            .expect("unique key should insert");
+        map
+    }
+
+    #[test]
+    fn should_produce_none_when_empty_as_none_is_enabled_then_treat_empty_strings_as_missing() {
+        let arena = ParseArena::new();
+        let map = search_map(&arena);
+        let options = ParseOptions::new().empty_as_none(true);
+
+        let (search, _) = deserialize_from_arena_entries::<Search>(map.entries_slice(), &options)
+            .expect("empty values should become none");
+
+        assert_eq!(
+            search,
+            Search {
+                page: None,
+                query: None,
+                pages: vec![None, Some(2)],
+            }
+        );
+    }
+
+    #[test]
+    fn should_reject_empty_number_when_empty_as_none_is_disabled_then_report_invalid_number() {
+        let arena = ParseArena::new();
+        let map = search_map(&arena);
+
+        let error =
+            deserialize_from_arena_entries::<Search>(map.entries_slice(), &ParseOptions::new())
+                .expect_err("empty page should fail");
+
+        assert_matches!(error.kind(), DeserializeErrorKind::InvalidNumber { value } if value.is_empty());
+        assert_eq!(error.path(), &[PathSegment::Key("page".into())]);
+    }
+}
+
+mod bool_literals {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Consent {
+        terms: bool,
+        marketing: bool,
+    }
+
+    fn consent_map<'arena>(
+        arena: &'arena ParseArena,
+        terms: &str,
+        marketing: &str,
+    ) -> crate::parsing::arena::ArenaQueryMap<'arena> {
+        let mut map = map_with_capacity(arena, 2);
+        map.try_insert_str(arena, "terms", make_string(arena, terms))
+            .expect("unique key should insert");
+        map.try_insert_str(arena, "marketing", make_string(arena, marketing))
+            .expect("unique key should insert");
+        map
+    }
+
+    #[test]
+    fn should_accept_configured_literals_when_case_insensitive_then_parse_checkbox_values() {
+        let arena = ParseArena::new();
+        let map = consent_map(&arena, "On", "0");
+        let options = ParseOptions::new().bool_literals(
+            BoolLiterals::new()
+                .format(BoolFormat::OnOff)
+                .truthy("1")
+                .falsy("0")
+                .case_insensitive(true),
+        );
+
+        let (consent, _) = deserialize_from_arena_entries::<Consent>(map.entries_slice(), &options)
+            .expect("configured literals should deserialize");
+
+        assert_eq!(
+            consent,
+            Consent {
+                terms: true,
+                marketing: false,
+            }
+        );
+    }
+
+    #[test]
+    fn should_treat_empty_value_as_true_when_empty_literal_is_truthy_then_accept_bare_flag() {
+        let arena = ParseArena::new();
+        let map = consent_map(&arena, "", "false");
+        let options = ParseOptions::new().bool_literals(BoolLiterals::new().truthy(""));
+
+        let (consent, _) = deserialize_from_arena_entries::<Consent>(map.entries_slice(), &options)
+            .expect("empty literal should deserialize");
+
+        assert!(consent.terms);
+        assert!(!consent.marketing);
+    }
+
+    #[test]
+    fn should_reject_other_casing_when_case_sensitive_then_report_invalid_bool() {
+        let arena = ParseArena::new();
+        let map = consent_map(&arena, "YES", "no");
+        let options =
+            ParseOptions::new().bool_literals(BoolLiterals::new().format(BoolFormat::YesNo));
+
+        let error = deserialize_from_arena_entries::<Consent>(map.entries_slice(), &options)
+            .expect_err("uppercase literal should fail");
+
+        assert_matches!(error.kind(), DeserializeErrorKind::InvalidBool { value } if value == "YES");
+        assert_eq!(error.path(), &[PathSegment::Key("terms".into())]);
+    }
+}
+
+mod sequence_coercion {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn indexed_map<'arena>(
+        arena: &'arena ParseArena,
+        pairs: &[(&str, &str)],
+    ) -> ArenaValue<'arena> {
+        let mut entries = arena.alloc_vec();
+        for (key, value) in pairs {
+            entries.push((alloc_key(arena, key), make_string(arena, value)));
+        }
+        ArenaValue::Map {
+            entries,
+            index: Default::default(),
+        }
+    }
+
+    #[test]
+    fn should_order_by_index_when_sequence_requested_from_integer_keyed_map_then_return_sorted_values()
+     {
+        let arena = ParseArena::new();
+        let value = indexed_map(&arena, &[("2", "3"), ("0", "1")]);
+
+        let result =
+            <Vec<u32>>::deserialize(deserializer_for(&value)).expect("indexed map should coerce");
+
+        assert_eq!(result, vec![1, 3]);
+    }
+
+    #[test]
+    fn should_report_original_index_when_indexed_element_is_invalid_then_include_key_in_path() {
+        let arena = ParseArena::new();
+        let value = indexed_map(&arena, &[("0", "1"), ("5", "x")]);
+
+        let error = <Vec<u32>>::deserialize(deserializer_for(&value))
+            .expect_err("invalid element should fail");
+
+        assert_matches!(error.kind(), DeserializeErrorKind::InvalidNumber { value } if value == "x");
+        assert_eq!(error.path(), &[PathSegment::Index(5)]);
+    }
+
+    #[test]
+    fn should_reject_map_when_keys_are_not_canonical_indices_then_return_expected_sequence_error() {
+        let arena = ParseArena::new();
+        let value = indexed_map(&arena, &[("0", "1"), ("01", "2")]);
+
+        let error = <Vec<u32>>::deserialize(deserializer_for(&value))
+            .expect_err("padded index should not coerce");
+
+        assert_matches!(
+            error.kind(),
+            DeserializeErrorKind::UnexpectedType { expected, found }
+                if *expected == "array" && *found == "object"
+        );
+    }
+
+    #[test]
+    fn should_wrap_scalar_when_set_expected_then_return_single_member_set() {
+        let arena = ParseArena::new();
+        let value = make_string(&arena, "red");
+
+        let result = <HashSet<String>>::deserialize(deserializer_for(&value))
+            .expect("scalar should coerce into set");
+
+        assert_eq!(result, HashSet::from(["red".to_string()]));
+    }
+
+    #[test]
+    fn should_fill_tuple_when_indexed_map_has_matching_length_then_return_tuple() {
+        let arena = ParseArena::new();
+        let value = indexed_map(&arena, &[("1", "9"), ("0", "4")]);
+
+        let result =
+            <(u8, u8)>::deserialize(deserializer_for(&value)).expect("indexed map should coerce");
+
+        assert_eq!(result, (4, 9));
+    }
+}
+
+mod infer_scalars {
+    use super::*;
+
+    fn inferred_json(raw: &str, options: &ParseOptions) -> serde_json::Value {
+        let arena = ParseArena::new();
+        let mut map = map_with_capacity(&arena, 1);
+        map.try_insert_str(&arena, "value", make_string(&arena, raw))
+            .expect("unique key should insert");
+
+        let (value, _) =
+            deserialize_from_arena_entries::<serde_json::Value>(map.entries_slice(), options)
+                .expect("any value should deserialize");
+        value["value"].clone()
+    }
+
+    #[test]
+    fn should_visit_typed_scalars_when_infer_scalars_is_enabled_then_produce_numbers_and_bools() {
+        let options = ParseOptions::new().infer_scalars(true);
+
+        let values: Vec<_> = ["42", "-7", "2.5", "true", "false"]
+            .iter()
+            .map(|raw| inferred_json(raw, &options))
+            .collect();
+
+        assert_eq!(
+            values,
+            vec![
+                serde_json::json!(42),
+                serde_json::json!(-7),
+                serde_json::json!(2.5),
+                serde_json::json!(true),
+                serde_json::json!(false),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_strings_when_value_is_not_canonical_number_then_visit_str() {
+        let options = ParseOptions::new().infer_scalars(true);
+
+        let values: Vec<_> = ["007", "+5", "1e3", "1.", "-", "inf", "TRUE", ""]
+            .iter()
+            .map(|raw| inferred_json(raw, &options))
+            .collect();
+
+        assert!(
+            values.iter().all(serde_json::Value::is_string),
+            "non-canonical literals should stay strings: {values:?}"
+        );
+    }
+
+    #[test]
+    fn should_use_configured_bool_literals_when_inferring_then_visit_bool() {
+        let options = ParseOptions::new()
+            .infer_scalars(true)
+            .bool_literals(BoolLiterals::new().format(BoolFormat::OnOff));
+
+        assert_eq!(inferred_json("on", &options), serde_json::json!(true));
+    }
+
+    #[test]
+    fn should_visit_str_when_infer_scalars_is_disabled_then_keep_numeric_text() {
+        assert_eq!(
+            inferred_json("42", &ParseOptions::new()),
+            serde_json::json!("42")
+        );
+    }
+}
+
+mod sparse_holes {
+    use super::*;
+
+    fn sparse_sequence(arena: &ParseArena) -> ArenaValue<'_> {
+        let mut items = arena.alloc_vec();
+        items.push(make_string(arena, ""));
+        items.push(ArenaValue::Hole);
+        items.push(make_string(arena, "x"));
+        ArenaValue::Seq(items)
+    }
+
+    #[test]
+    fn should_produce_none_for_holes_when_target_is_optional_then_keep_explicit_empty_values() {
+        let arena = ParseArena::new();
+        let value = sparse_sequence(&arena);
+
+        let result = <Vec<Option<String>>>::deserialize(deserializer_for(&value))
+            .expect("holes should deserialize as none");
+
+        assert_eq!(result, vec![Some(String::new()), None, Some("x".into())]);
+    }
+
+    #[test]
+    fn should_report_missing_value_when_hole_targets_required_string_then_include_index() {
+        let arena = ParseArena::new();
+        let value = sparse_sequence(&arena);
+
+        let error = <Vec<String>>::deserialize(deserializer_for(&value))
+            .expect_err("hole should not fill a required string");
+
+        assert_matches!(
+            error.kind(),
+            DeserializeErrorKind::ExpectedString { found } if *found == "missing value"
+        );
+        assert_eq!(error.path(), &[PathSegment::Index(1)]);
+    }
+}
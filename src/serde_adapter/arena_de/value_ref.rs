@@ -2,6 +2,8 @@ use crate::parsing::arena::ArenaValue;
 
 #[derive(Clone, Copy)]
 pub(crate) enum ArenaValueRef<'de> {
+    Null,
+    Hole,
     String(&'de str),
     Seq(&'de [ArenaValue<'de>]),
     Map(&'de [(&'de str, ArenaValue<'de>)]),
@@ -10,6 +12,8 @@ pub(crate) enum ArenaValueRef<'de> {
 impl<'de> ArenaValueRef<'de> {
     pub(crate) fn from_value(value: &'de ArenaValue<'de>) -> Self {
         match value {
+            ArenaValue::Null => ArenaValueRef::Null,
+            ArenaValue::Hole => ArenaValueRef::Hole,
             ArenaValue::String(s) => ArenaValueRef::String(s),
             ArenaValue::Seq(items) => ArenaValueRef::Seq(items.as_slice()),
             ArenaValue::Map { entries, .. } => ArenaValueRef::Map(entries.as_slice()),
@@ -29,6 +29,19 @@ mod arena_value_to_json {
         assert_eq!(json_value, json!(["alpha", "beta"]));
     }
 
+    #[test]
+    fn should_convert_hole_into_json_null_when_sequence_is_sparse_then_keep_position() {
+        let arena = ParseArena::new();
+        let mut values = arena.alloc_vec();
+        values.push(ArenaValue::Hole);
+        values.push(ArenaValue::string(arena.alloc_str("x")));
+        let sequence = ArenaValue::Seq(values);
+
+        let json_value = arena_value_to_json(&sequence);
+
+        assert_eq!(json_value, json!([null, "x"]));
+    }
+
     #[test]
     fn should_preserve_nested_structure_when_converting_map_into_json_when_map_contains_nested_entries_then_produce_nested_json_structure()
      {
@@ -85,3 +98,74 @@ mod arena_map_to_json_value {
         );
     }
 }
+
+mod arena_value_to_value {
+    use super::*;
+
+    #[test]
+    fn should_convert_nested_entries_into_model_values_when_map_contains_sequence_then_preserve_shape()
+     {
+        let arena = ParseArena::new();
+        let mut tags = arena.alloc_vec();
+        tags.push(ArenaValue::string(arena.alloc_str("rust")));
+        tags.push(ArenaValue::string(arena.alloc_str("serde")));
+        let mut entries = arena.alloc_vec();
+        entries.push((arena.alloc_str("tags"), ArenaValue::Seq(tags)));
+        let map_value = ArenaValue::Map {
+            entries,
+            index: Default::default(),
+        };
+
+        let value = arena_value_to_value(&map_value);
+
+        let object = value.as_object().expect("map should convert into object");
+        assert_eq!(
+            object.get("tags"),
+            Some(&Value::Array(vec![
+                Value::from("rust"),
+                Value::from("serde")
+            ]))
+        );
+    }
+}
+
+mod arena_map_to_query_map {
+    use super::*;
+
+    #[test]
+    fn should_preserve_insertion_order_when_converting_query_map_then_return_ordered_entries() {
+        let arena = ParseArena::new();
+        let mut query_map = map_with_capacity(&arena, 2);
+        query_map
+            .try_insert_str(&arena, "zeta", ArenaValue::string(arena.alloc_str("1")))
+            .expect("unique key should insert");
+        query_map
+            .try_insert_str(&arena, "alpha", ArenaValue::string(arena.alloc_str("2")))
+            .expect("unique key should insert");
+
+        let map = arena_map_to_query_map(&query_map);
+
+        let keys: Vec<&str> = map.keys().map(String::as_str).collect();
+        assert_eq!(keys, vec!["zeta", "alpha"]);
+        assert_eq!(map.get("alpha"), Some(&Value::from("2")));
+    }
+}
+
+mod null_conversion {
+    use super::*;
+    use crate::model::Value;
+
+    #[test]
+    fn should_convert_null_into_json_null_when_value_is_null_then_return_json_null() {
+        let json_value = arena_value_to_json(&ArenaValue::Null);
+
+        assert_eq!(json_value, serde_json::Value::Null);
+    }
+
+    #[test]
+    fn should_convert_null_into_value_null_when_value_is_null_then_return_null_variant() {
+        let value = arena_value_to_value(&ArenaValue::Null);
+
+        assert_eq!(value, Value::Null);
+    }
+}
@@ -37,6 +37,10 @@ pub enum PathSegment {
     Index(usize),
 }
 
+pub(crate) fn format_path(path: &[PathSegment]) -> String {
+    PathDisplay(path).to_string()
+}
+
 struct PathDisplay<'a>(&'a [PathSegment]);
 
 impl<'a> fmt::Display for PathDisplay<'a> {
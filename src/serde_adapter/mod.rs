@@ -3,8 +3,8 @@ mod arena_de;
 mod errors;
 mod ser;
 
-pub(crate) use arena::arena_map_to_json_value;
-pub(crate) use arena_de::deserialize_from_arena_map;
-pub(crate) use ser::serialize_to_query_map;
+pub(crate) use arena::{arena_map_to_json_value, arena_map_to_query_map};
+pub(crate) use arena_de::{deserialize_from_arena_entries, deserialize_from_arena_map};
+pub(crate) use ser::{SerializeConfig, serialize_to_query_map, serialize_to_query_map_with};
 
 pub use errors::{DeserializeError, DeserializeErrorKind, PathSegment, SerializeError};
@@ -1,5 +1,7 @@
 use crate::model::{OrderedMap, Value};
 use crate::serde_adapter::errors::SerializeError;
+
+use super::value::SerializeConfig;
 use ahash::RandomState;
 use serde::Serialize;
 use serde::ser::SerializeMap;
@@ -7,6 +9,7 @@ use serde::ser::SerializeMap;
 pub(crate) struct ValueMapSerializer {
     entries: OrderedMap<String, Value>,
     next_key: Option<String>,
+    config: SerializeConfig,
 }
 
 impl ValueMapSerializer {
@@ -14,8 +17,14 @@ impl ValueMapSerializer {
         ValueMapSerializer {
             entries: OrderedMap::with_hasher(RandomState::default()),
             next_key: None,
+            config: SerializeConfig::default(),
         }
     }
+
+    pub(crate) fn with_config(mut self, config: SerializeConfig) -> Self {
+        self.config = config;
+        self
+    }
 }
 
 impl SerializeMap for ValueMapSerializer {
@@ -32,7 +41,8 @@ impl SerializeMap for ValueMapSerializer {
         let key = self.next_key.take().ok_or_else(|| {
             SerializeError::Message("serialize_value called before serialize_key".into())
         })?;
-        if let Some(serialized) = value.serialize(super::value::ValueSerializer::root())? {
+        let serializer = super::value::ValueSerializer::root().with_config(self.config);
+        if let Some(serialized) = value.serialize(serializer)? {
             self.entries.insert(key, serialized);
         }
         Ok(())
@@ -6,4 +6,4 @@ mod value;
 pub(crate) use map::ValueMapSerializer;
 pub(crate) use seq::ValueSeqSerializer;
 pub(crate) use struct_serializer::ValueStructSerializer;
-pub(crate) use value::serialize_to_query_map;
+pub(crate) use value::{SerializeConfig, serialize_to_query_map, serialize_to_query_map_with};
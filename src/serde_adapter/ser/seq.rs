@@ -1,19 +1,38 @@
 use crate::model::Value;
 use crate::serde_adapter::errors::SerializeError;
+
+use super::value::SerializeConfig;
 use serde::Serialize;
 use serde::ser::{SerializeSeq, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant};
 
 pub(crate) struct ValueSeqSerializer {
     items: Vec<Value>,
+    variant: Option<&'static str>,
+    config: SerializeConfig,
 }
 
 impl ValueSeqSerializer {
     pub(crate) fn new(len: Option<usize>) -> Self {
         ValueSeqSerializer {
             items: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+            config: SerializeConfig::default(),
+        }
+    }
+
+    pub(crate) fn for_variant(variant: &'static str, len: usize) -> Self {
+        ValueSeqSerializer {
+            items: Vec::with_capacity(len),
+            variant: Some(variant),
+            config: SerializeConfig::default(),
         }
     }
 
+    pub(crate) fn with_config(mut self, config: SerializeConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     fn push_value(&mut self, value: Option<Value>) {
         match value {
             Some(serialized) => self.items.push(serialized),
@@ -27,7 +46,7 @@ impl SerializeSeq for ValueSeqSerializer {
     type Error = SerializeError;
 
     fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
-        let serializer = super::value::ValueSerializer::sequence_element();
+        let serializer = super::value::ValueSerializer::sequence_element().with_config(self.config);
         let serialized = value.serialize(serializer)?;
         self.push_value(serialized);
         Ok(())
@@ -69,17 +88,15 @@ impl SerializeTupleVariant for ValueSeqSerializer {
     type Error = SerializeError;
 
     fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
-        let type_name = std::any::type_name::<T>();
-        let _ = value;
-        Err(SerializeError::Message(format!(
-            "tuple variants are unsupported; attempted to serialize element of type `{type_name}`"
-        )))
+        SerializeSeq::serialize_element(self, value)
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        Err(SerializeError::Message(
-            "tuple variants are unsupported in query string serialization".into(),
-        ))
+        let items = Value::Array(self.items);
+        Ok(Some(match self.variant {
+            Some(variant) => super::value::wrap_variant(variant, items),
+            None => items,
+        }))
     }
 }
 
@@ -150,26 +150,39 @@ mod value_seq_serializer {
     }
 
     #[test]
-    fn should_report_unsupported_message_when_tuple_variant_field_is_serialized_then_return_error()
-    {
-        let mut serializer = ValueSeqSerializer::new(None);
+    fn should_wrap_items_under_variant_key_when_tuple_variant_ends_then_return_object() {
+        let mut serializer = ValueSeqSerializer::for_variant("Between", 2);
 
-        let error = SerializeTupleVariant::serialize_field(&mut serializer, &123i32)
-            .expect_err("tuple variant field should be unsupported");
+        SerializeTupleVariant::serialize_field(&mut serializer, &1u8)
+            .expect("first tuple variant field should serialize");
+        SerializeTupleVariant::serialize_field(&mut serializer, &9u8)
+            .expect("second tuple variant field should serialize");
+        let result = SerializeTupleVariant::end(serializer).expect("tuple variant should finish");
 
-        assert!(error.to_string().contains("tuple variants are unsupported"));
+        let value = result.expect("tuple variant serializer should produce a value");
+        let object = value
+            .as_object()
+            .expect("variant should be wrapped in object");
+        assert_eq!(
+            object.get("Between"),
+            Some(&Value::Array(vec![
+                Value::String("1".into()),
+                Value::String("9".into()),
+            ]))
+        );
     }
 
     #[test]
-    fn should_report_unsupported_message_when_ending_tuple_variant_then_return_unsupported_error() {
-        let serializer = ValueSeqSerializer::new(None);
+    fn should_return_plain_array_when_tuple_variant_has_no_variant_name_then_skip_wrapping() {
+        let mut serializer = ValueSeqSerializer::new(None);
 
-        let error =
-            SerializeTupleVariant::end(serializer).expect_err("tuple variant end should fail");
+        SerializeTupleVariant::serialize_field(&mut serializer, &123i32)
+            .expect("tuple variant field should serialize");
+        let result = SerializeTupleVariant::end(serializer).expect("tuple variant should finish");
 
         assert_eq!(
-            error.to_string(),
-            "tuple variants are unsupported in query string serialization"
+            result,
+            Some(Value::Array(vec![Value::String("123".into())]))
         );
     }
 
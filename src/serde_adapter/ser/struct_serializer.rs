@@ -1,20 +1,39 @@
 use crate::model::{OrderedMap, Value};
 use crate::serde_adapter::errors::SerializeError;
+
+use super::value::SerializeConfig;
 use ahash::RandomState;
 use serde::Serialize;
-use serde::ser::{self, Impossible, SerializeStruct};
+use serde::ser::{self, Impossible, SerializeStruct, SerializeStructVariant};
 use std::fmt::Display;
 
 pub(crate) struct ValueStructSerializer {
     entries: OrderedMap<String, Value>,
+    variant: Option<&'static str>,
+    config: SerializeConfig,
 }
 
 impl ValueStructSerializer {
     pub(crate) fn new() -> Self {
         ValueStructSerializer {
             entries: OrderedMap::with_hasher(RandomState::default()),
+            variant: None,
+            config: SerializeConfig::default(),
+        }
+    }
+
+    pub(crate) fn for_variant(variant: &'static str) -> Self {
+        ValueStructSerializer {
+            entries: OrderedMap::with_hasher(RandomState::default()),
+            variant: Some(variant),
+            config: SerializeConfig::default(),
         }
     }
+
+    pub(crate) fn with_config(mut self, config: SerializeConfig) -> Self {
+        self.config = config;
+        self
+    }
 }
 
 impl SerializeStruct for ValueStructSerializer {
@@ -26,7 +45,8 @@ impl SerializeStruct for ValueStructSerializer {
         key: &'static str,
         value: &T,
     ) -> Result<(), Self::Error> {
-        if let Some(serialized) = value.serialize(super::value::ValueSerializer::root())? {
+        let serializer = super::value::ValueSerializer::root().with_config(self.config);
+        if let Some(serialized) = value.serialize(serializer)? {
             self.entries.insert(key.to_string(), serialized);
         }
         Ok(())
@@ -37,6 +57,27 @@ impl SerializeStruct for ValueStructSerializer {
     }
 }
 
+impl SerializeStructVariant for ValueStructSerializer {
+    type Ok = Option<Value>;
+    type Error = SerializeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        let fields = Value::Object(self.entries);
+        Ok(Some(match self.variant {
+            Some(variant) => super::value::wrap_variant(variant, fields),
+            None => fields,
+        }))
+    }
+}
+
 #[cfg(test)]
 #[path = "struct_serializer_test.rs"]
 mod struct_serializer_test;
@@ -1,7 +1,7 @@
 use super::*;
 use crate::model::Value;
 use assert_matches::assert_matches;
-use serde::ser::SerializeStruct;
+use serde::ser::{SerializeStruct, SerializeStructVariant};
 
 mod value_struct_serializer {
     use super::*;
@@ -53,6 +53,30 @@ mod value_struct_serializer {
         assert_matches!(array.first(), Some(Value::String(text)) if text == "drums");
         assert_matches!(array.get(1), Some(Value::String(text)) if text == "guitar");
     }
+
+    #[test]
+    fn should_wrap_fields_under_variant_key_when_struct_variant_ends_then_return_nested_object() {
+        let mut serializer = ValueStructSerializer::for_variant("Range");
+
+        SerializeStructVariant::serialize_field(&mut serializer, "min", &1u32)
+            .expect("serializing min should succeed");
+        SerializeStructVariant::serialize_field(&mut serializer, "max", &5u32)
+            .expect("serializing max should succeed");
+        let result =
+            SerializeStructVariant::end(serializer).expect("ending serializer should succeed");
+
+        let value = result.expect("struct variant serializer should produce a value");
+        let outer = value
+            .as_object()
+            .expect("variant should be wrapped in object");
+        assert_eq!(outer.len(), 1);
+        let fields = outer
+            .get("Range")
+            .and_then(Value::as_object)
+            .expect("variant payload should be object");
+        assert_matches!(fields.get("min"), Some(Value::String(text)) if text == "1");
+        assert_matches!(fields.get("max"), Some(Value::String(text)) if text == "5");
+    }
 }
 
 mod map_key_serializer {
@@ -1,6 +1,8 @@
+use crate::config::{BoolFormat, StringifyOptions};
 use crate::model::{OrderedMap, Value};
 use crate::serde_adapter::errors::SerializeError;
-use serde::ser::{self, Impossible, Serialize};
+use ahash::RandomState;
+use serde::ser::{self, Serialize};
 use std::fmt::Display;
 
 use super::{ValueMapSerializer, ValueSeqSerializer, ValueStructSerializer};
@@ -8,7 +10,14 @@ use super::{ValueMapSerializer, ValueSeqSerializer, ValueStructSerializer};
 pub(crate) fn serialize_to_query_map<T: Serialize>(
     data: &T,
 ) -> Result<OrderedMap<String, Value>, SerializeError> {
-    match data.serialize(ValueSerializer::root())? {
+    serialize_to_query_map_with(data, SerializeConfig::default())
+}
+
+pub(crate) fn serialize_to_query_map_with<T: Serialize>(
+    data: &T,
+    config: SerializeConfig,
+) -> Result<OrderedMap<String, Value>, SerializeError> {
+    match data.serialize(ValueSerializer::root().with_config(config))? {
         Some(Value::Object(map)) => Ok(map),
         Some(other) => Err(SerializeError::TopLevel(describe_value(&other))),
         None => Err(SerializeError::UnexpectedSkip),
@@ -17,33 +26,63 @@ pub(crate) fn serialize_to_query_map<T: Serialize>(
 
 fn describe_value(value: &Value) -> String {
     match value {
+        Value::Null => "null".into(),
         Value::String(_) => "string".into(),
         Value::Array(_) => "array".into(),
         Value::Object(_) => "object".into(),
     }
 }
 
+pub(crate) fn wrap_variant(variant: &'static str, payload: Value) -> Value {
+    let mut entries = OrderedMap::with_capacity_and_hasher(1, RandomState::default());
+    entries.insert(variant.to_string(), payload);
+    Value::Object(entries)
+}
+
 #[cfg(test)]
 #[path = "value_test.rs"]
 mod value_test;
 
+#[derive(Clone, Copy, Default)]
+pub(crate) struct SerializeConfig {
+    pub(crate) strict_null: bool,
+    pub(crate) bool_format: BoolFormat,
+}
+
+impl SerializeConfig {
+    pub(crate) fn new(options: &StringifyOptions) -> Self {
+        Self {
+            strict_null: options.strict_null_handling,
+            bool_format: options.bool_format,
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 pub(crate) struct ValueSerializer {
     preserve_none: bool,
+    config: SerializeConfig,
 }
 
 impl ValueSerializer {
     pub(crate) fn root() -> Self {
         Self {
             preserve_none: false,
+            config: SerializeConfig::default(),
         }
     }
 
     pub(crate) fn sequence_element() -> Self {
         Self {
             preserve_none: true,
+            config: SerializeConfig::default(),
         }
     }
+
+    pub(crate) fn with_config(mut self, config: SerializeConfig) -> Self {
+        self.config = config;
+        self
+    }
 }
 
 fn string_value<T: Display>(value: T) -> Value {
@@ -56,13 +95,14 @@ impl ser::Serializer for ValueSerializer {
     type SerializeSeq = ValueSeqSerializer;
     type SerializeTuple = ValueSeqSerializer;
     type SerializeTupleStruct = ValueSeqSerializer;
-    type SerializeTupleVariant = Impossible<Option<Value>, SerializeError>;
+    type SerializeTupleVariant = ValueSeqSerializer;
     type SerializeMap = ValueMapSerializer;
     type SerializeStruct = ValueStructSerializer;
-    type SerializeStructVariant = Impossible<Option<Value>, SerializeError>;
+    type SerializeStructVariant = ValueStructSerializer;
 
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
-        Ok(Some(Value::String(if v { "true" } else { "false" }.into())))
+        let (truthy, falsy) = self.config.bool_format.literals();
+        Ok(Some(Value::String(if v { truthy } else { falsy }.into())))
     }
 
     fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
@@ -126,7 +166,9 @@ impl ser::Serializer for ValueSerializer {
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        if self.preserve_none {
+        if self.config.strict_null {
+            Ok(Some(Value::Null))
+        } else if self.preserve_none {
             Ok(Some(Value::String("".into())))
         } else {
             Ok(None)
@@ -141,7 +183,11 @@ impl ser::Serializer for ValueSerializer {
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-        Ok(Some(Value::String("".into())))
+        if self.config.strict_null {
+            Ok(Some(Value::Null))
+        } else {
+            Ok(Some(Value::String("".into())))
+        }
     }
 
     fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
@@ -172,17 +218,20 @@ impl ser::Serializer for ValueSerializer {
         self,
         _name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
-        _value: &T,
+        variant: &'static str,
+        value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: ?Sized + Serialize,
     {
-        Err(SerializeError::Unsupported("newtype variant"))
+        let payload = value
+            .serialize(ValueSerializer::sequence_element().with_config(self.config))?
+            .unwrap_or_else(|| Value::String(String::new()));
+        Ok(Some(wrap_variant(variant, payload)))
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        Ok(ValueSeqSerializer::new(len))
+        Ok(ValueSeqSerializer::new(len).with_config(self.config))
     }
 
     fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
@@ -201,14 +250,14 @@ impl ser::Serializer for ValueSerializer {
         self,
         _name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
-        _len: usize,
+        variant: &'static str,
+        len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        Err(SerializeError::Unsupported("tuple variant"))
+        Ok(ValueSeqSerializer::for_variant(variant, len).with_config(self.config))
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Ok(ValueMapSerializer::new())
+        Ok(ValueMapSerializer::new().with_config(self.config))
     }
 
     fn serialize_struct(
@@ -216,16 +265,16 @@ impl ser::Serializer for ValueSerializer {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        Ok(ValueStructSerializer::new())
+        Ok(ValueStructSerializer::new().with_config(self.config))
     }
 
     fn serialize_struct_variant(
         self,
         _name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
+        variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        Err(SerializeError::Unsupported("struct variant"))
+        Ok(ValueStructSerializer::for_variant(variant).with_config(self.config))
     }
 }
@@ -2,7 +2,10 @@ use super::*;
 use crate::model::Value;
 use assert_matches::assert_matches;
 use serde::Serialize;
-use serde::ser::{SerializeMap, SerializeSeq, SerializeTuple, SerializeTupleStruct, Serializer};
+use serde::ser::{
+    SerializeMap, SerializeSeq, SerializeStructVariant, SerializeTuple, SerializeTupleStruct,
+    SerializeTupleVariant, Serializer,
+};
 
 mod serialize_to_query_map {
     use super::*;
@@ -74,17 +77,19 @@ mod serialize_to_query_map {
     }
 
     #[test]
-    fn should_propagate_unsupported_error_when_enum_contains_tuple_variant_then_return_unsupported_message()
-     {
+    fn should_nest_tuple_variant_under_variant_key_when_top_level_is_enum_then_emit_indexed_items()
+    {
         let command = Command::Invoke("ping", "now");
 
-        let error =
-            serialize_to_query_map(&command).expect_err("tuple variant should be unsupported");
+        let map = serialize_to_query_map(&command).expect("tuple variant should serialize");
 
-        assert_matches!(
-            &error,
-            SerializeError::Unsupported(kind) if *kind == "tuple variant",
-            "unexpected error: {error:?}"
+        assert_eq!(map.len(), 1);
+        assert_eq!(
+            map.get("Invoke"),
+            Some(&Value::Array(vec![
+                Value::String("ping".into()),
+                Value::String("now".into()),
+            ]))
         );
     }
 }
@@ -127,19 +132,34 @@ mod value_serializer {
     }
 
     #[test]
-    fn should_error_for_newtype_variant_when_serializer_does_not_support_variant_then_return_unsupported_error()
-     {
+    fn should_wrap_payload_under_variant_key_when_serializing_newtype_variant_then_return_object() {
         let serializer = ValueSerializer::root();
 
-        let error = serializer
+        let result = serializer
             .serialize_newtype_variant("Mode", 0, "Special", &42u8)
-            .expect_err("newtype variant should be unsupported");
+            .expect("newtype variant should serialize");
+
+        let value = result.expect("newtype variant should produce a value");
+        let object = value
+            .as_object()
+            .expect("variant should be wrapped in object");
+        assert_eq!(object.len(), 1);
+        assert_eq!(object.get("Special"), Some(&Value::String("42".into())));
+    }
 
-        assert_matches!(
-            &error,
-            SerializeError::Unsupported(kind) if *kind == "newtype variant",
-            "unexpected error: {error:?}"
-        );
+    #[test]
+    fn should_keep_variant_key_when_newtype_payload_is_none_then_emit_empty_string() {
+        let serializer = ValueSerializer::root();
+
+        let result = serializer
+            .serialize_newtype_variant("Mode", 0, "Special", &Option::<u8>::None)
+            .expect("newtype variant should serialize");
+
+        let value = result.expect("newtype variant should produce a value");
+        let object = value
+            .as_object()
+            .expect("variant should be wrapped in object");
+        assert_eq!(object.get("Special"), Some(&Value::String(String::new())));
     }
 
     #[test]
@@ -312,37 +332,51 @@ mod value_serializer {
     }
 
     #[test]
-    fn should_error_for_tuple_variant_when_serializer_does_not_support_variant_then_return_unsupported_error()
-     {
+    fn should_wrap_elements_when_serializing_tuple_variant_then_nest_array_under_variant() {
         let serializer = ValueSerializer::root();
 
-        let error = serializer
+        let mut variant = serializer
             .serialize_tuple_variant("Mode", 1, "Burst", 2)
-            .err()
-            .expect("tuple variant should be unsupported");
-
-        assert_matches!(
-            &error,
-            SerializeError::Unsupported(kind) if *kind == "tuple variant",
-            "unexpected error: {error:?}"
+            .expect("tuple variant should serialize");
+        SerializeTupleVariant::serialize_field(&mut variant, &"a").expect("field should serialize");
+        SerializeTupleVariant::serialize_field(&mut variant, &"b").expect("field should serialize");
+        let value = SerializeTupleVariant::end(variant)
+            .expect("tuple variant should finish")
+            .expect("tuple variant should produce a value");
+
+        let object = value
+            .as_object()
+            .expect("variant should be wrapped in object");
+        assert_eq!(
+            object.get("Burst"),
+            Some(&Value::Array(vec![
+                Value::String("a".into()),
+                Value::String("b".into()),
+            ]))
         );
     }
 
     #[test]
-    fn should_error_for_struct_variant_when_serializer_does_not_support_variant_then_return_unsupported_error()
-     {
+    fn should_wrap_fields_when_serializing_struct_variant_then_nest_object_under_variant() {
         let serializer = ValueSerializer::root();
 
-        let error = serializer
+        let mut variant = serializer
             .serialize_struct_variant("Mode", 3, "Drift", 1)
-            .err()
-            .expect("struct variant should be unsupported");
-
-        assert_matches!(
-            &error,
-            SerializeError::Unsupported(kind) if *kind == "struct variant",
-            "unexpected error: {error:?}"
-        );
+            .expect("struct variant should serialize");
+        SerializeStructVariant::serialize_field(&mut variant, "angle", &30u8)
+            .expect("field should serialize");
+        let value = SerializeStructVariant::end(variant)
+            .expect("struct variant should finish")
+            .expect("struct variant should produce a value");
+
+        let object = value
+            .as_object()
+            .expect("variant should be wrapped in object");
+        let fields = object
+            .get("Drift")
+            .and_then(Value::as_object)
+            .expect("variant payload should be object");
+        assert_eq!(fields.get("angle"), Some(&Value::String("30".into())));
     }
 }
 
@@ -359,3 +393,98 @@ mod describe_value {
         assert_eq!(description, "object");
     }
 }
+
+mod strict_null {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Flags {
+        debug: Option<bool>,
+        marker: (),
+        tags: Vec<Option<&'static str>>,
+    }
+
+    #[test]
+    fn should_keep_none_as_null_when_strict_null_is_enabled_then_emit_null_entries() {
+        let flags = Flags {
+            debug: None,
+            marker: (),
+            tags: vec![Some("a"), None],
+        };
+
+        let map = serialize_to_query_map_with(
+            &flags,
+            SerializeConfig {
+                strict_null: true,
+                ..SerializeConfig::default()
+            },
+        )
+        .expect("serialization should succeed");
+
+        assert_eq!(map.get("debug"), Some(&Value::Null));
+        assert_eq!(map.get("marker"), Some(&Value::Null));
+        assert_eq!(
+            map.get("tags"),
+            Some(&Value::Array(vec![Value::from("a"), Value::Null]))
+        );
+    }
+
+    #[test]
+    fn should_drop_none_when_strict_null_is_disabled_then_skip_field() {
+        let flags = Flags {
+            debug: None,
+            marker: (),
+            tags: vec![None],
+        };
+
+        let map = serialize_to_query_map(&flags).expect("serialization should succeed");
+
+        assert!(!map.contains_key("debug"));
+        assert_eq!(map.get("marker"), Some(&Value::from("")));
+        assert_eq!(map.get("tags"), Some(&Value::Array(vec![Value::from("")])));
+    }
+}
+
+mod bool_format {
+    use super::*;
+    use crate::config::BoolFormat;
+
+    #[derive(Serialize)]
+    struct Consent {
+        terms: bool,
+        marketing: bool,
+    }
+
+    #[test]
+    fn should_render_configured_literals_when_bool_format_is_set_then_emit_one_and_zero() {
+        let consent = Consent {
+            terms: true,
+            marketing: false,
+        };
+
+        let map = serialize_to_query_map_with(
+            &consent,
+            SerializeConfig {
+                bool_format: BoolFormat::OneZero,
+                ..SerializeConfig::default()
+            },
+        )
+        .expect("serialization should succeed");
+
+        assert_eq!(map.get("terms"), Some(&Value::from("1")));
+        assert_eq!(map.get("marketing"), Some(&Value::from("0")));
+    }
+
+    #[test]
+    fn should_render_true_and_false_when_bool_format_is_default_then_keep_legacy_literals() {
+        let consent = Consent {
+            terms: true,
+            marketing: false,
+        };
+
+        let map = serialize_to_query_map(&consent).expect("serialization should succeed");
+
+        assert_eq!(map.get("terms"), Some(&Value::from("true")));
+        assert_eq!(map.get("marketing"), Some(&Value::from("false")));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::config::StringifyOptions;
 use crate::model::QueryMap;
-use crate::serde_adapter::serialize_to_query_map;
+use crate::serde_adapter::{SerializeConfig, serialize_to_query_map_with};
 use serde::Serialize;
 
 use super::StringifyResult;
@@ -11,11 +11,16 @@ pub fn stringify<T>(data: &T, options: &StringifyOptions) -> StringifyResult<Str
 where
     T: Serialize,
 {
-    let map = serialize_to_query_map(data).map_err(StringifyError::from)?;
+    let map = serialize_to_query_map_with(data, SerializeConfig::new(options))
+        .map_err(StringifyError::from)?;
     let query_map = QueryMap::from(map);
     stringify_query_map_with(&query_map, options)
 }
 
+pub fn stringify_query_map(map: &QueryMap, options: &StringifyOptions) -> StringifyResult<String> {
+    stringify_query_map_with(map, options)
+}
+
 #[cfg(test)]
 #[path = "api_test.rs"]
 mod api_test;
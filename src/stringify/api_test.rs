@@ -138,3 +138,25 @@ mod stringify {
         );
     }
 }
+
+mod stringify_query_map {
+    use super::*;
+    use crate::model::{QueryMap, Value};
+    use crate::stringify::stringify_query_map;
+
+    #[test]
+    fn given_dynamic_map_when_stringify_query_map_then_encodes_nested_pairs() {
+        let map = QueryMap::from_iter([
+            ("name", Value::from("Alice Smith")),
+            (
+                "tags",
+                Value::Array(vec![Value::from("a"), Value::from("b")]),
+            ),
+        ]);
+        let options = StringifyOptions::new().space_as_plus(true);
+
+        let result = stringify_query_map(&map, &options).expect("stringify should succeed");
+
+        assert_eq!(result, "name=Alice+Smith&tags%5B0%5D=a&tags%5B1%5D=b");
+    }
+}
@@ -1,58 +1,194 @@
-use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+use percent_encoding::{AsciiSet, CONTROLS, percent_encode, utf8_percent_encode};
+use smallvec::SmallVec;
 use std::fmt::Write as _;
 
-const fn build_component_set() -> AsciiSet {
-    CONTROLS
-        .add(b' ')
-        .add(b'"')
-        .add(b'#')
-        .add(b'%')
-        .add(b'&')
-        .add(b'+')
-        .add(b',')
-        .add(b'/')
-        .add(b':')
-        .add(b';')
-        .add(b'<')
-        .add(b'>')
-        .add(b'=')
-        .add(b'?')
-        .add(b'@')
-        .add(b'[')
-        .add(b'\\')
-        .add(b']')
-        .add(b'^')
-        .add(b'`')
-        .add(b'{')
-        .add(b'|')
-        .add(b'}')
-}
-
-const COMPONENT_ENCODE_SET: &AsciiSet = &build_component_set();
-
-pub(crate) fn encode_key_into(buffer: &mut String, key: &str, space_as_plus: bool) {
-    encode_into(key, space_as_plus, buffer);
-}
-
-pub(crate) fn encode_value_into(buffer: &mut String, value: &str, space_as_plus: bool) {
-    encode_into(value, space_as_plus, buffer);
-}
-
-pub(crate) fn estimate_encoded_extra(component: &str, space_as_plus: bool) -> usize {
+use crate::config::{Charset, EncodingProfile};
+
+const COMPONENT: u8 = 1 << 0;
+const RFC3986: u8 = 1 << 1;
+const RFC1738: u8 = 1 << 2;
+const WHATWG: u8 = 1 << 3;
+const ALL_PROFILES: u8 = COMPONENT | RFC3986 | RFC1738 | WHATWG;
+
+const ENCODE_TABLE: [u8; 128] = build_encode_table();
+
+const fn build_encode_table() -> [u8; 128] {
+    let mut table = [0u8; 128];
+    let mut byte = 0;
+    while byte < 0x20 {
+        table[byte] = ALL_PROFILES;
+        byte += 1;
+    }
+    table[0x7F] = ALL_PROFILES;
+
+    table = mark(table, b" \"#%&+;<=>?[\\]^`{|}", ALL_PROFILES);
+    table = mark(table, b",", COMPONENT | WHATWG);
+    table = mark(table, b"/:@", COMPONENT | RFC1738 | WHATWG);
+    table = mark(table, b"~", RFC1738 | WHATWG);
+    table = mark(table, b"!$'()", WHATWG);
+    table
+}
+
+const fn mark(mut table: [u8; 128], bytes: &[u8], profiles: u8) -> [u8; 128] {
+    let mut idx = 0;
+    while idx < bytes.len() {
+        table[bytes[idx] as usize] |= profiles;
+        idx += 1;
+    }
+    table
+}
+
+struct ProfileSets {
+    component: AsciiSet,
+    dotted_key: AsciiSet,
+    comma_value: AsciiSet,
+}
+
+const fn build_profile_sets(profile: u8) -> ProfileSets {
+    let mut set = CONTROLS.add(b' ');
+    let mut byte = 0;
+    while byte < ENCODE_TABLE.len() {
+        if ENCODE_TABLE[byte] & profile != 0 {
+            set = set.add(byte as u8);
+        }
+        byte += 1;
+    }
+
+    let dotted_key = set.add(b'.');
+    let comma_value = set.add(b',');
+    ProfileSets {
+        component: set,
+        dotted_key,
+        comma_value,
+    }
+}
+
+static COMPONENT_SETS: ProfileSets = build_profile_sets(COMPONENT);
+static RFC3986_SETS: ProfileSets = build_profile_sets(RFC3986);
+static RFC1738_SETS: ProfileSets = build_profile_sets(RFC1738);
+static WHATWG_SETS: ProfileSets = build_profile_sets(WHATWG);
+
+fn profile_bit(profile: EncodingProfile) -> u8 {
+    match profile {
+        EncodingProfile::Component => COMPONENT,
+        EncodingProfile::Rfc3986 => RFC3986,
+        EncodingProfile::Rfc1738 => RFC1738,
+        EncodingProfile::Whatwg => WHATWG,
+    }
+}
+
+fn profile_sets(profile: EncodingProfile) -> &'static ProfileSets {
+    match profile {
+        EncodingProfile::Component => &COMPONENT_SETS,
+        EncodingProfile::Rfc3986 => &RFC3986_SETS,
+        EncodingProfile::Rfc1738 => &RFC1738_SETS,
+        EncodingProfile::Whatwg => &WHATWG_SETS,
+    }
+}
+
+pub(crate) fn profile_encodes(profile: EncodingProfile, character: char) -> bool {
+    u8::try_from(u32::from(character)).map_or(true, |byte| {
+        needs_encoding(byte, false, profile_bit(profile))
+    })
+}
+
+pub(crate) fn encode_key_into(
+    buffer: &mut String,
+    key: &str,
+    space_as_plus: bool,
+    charset: Charset,
+    profile: EncodingProfile,
+) {
+    let set = &profile_sets(profile).component;
+    encode_into(key, space_as_plus, charset, buffer, set);
+}
+
+pub(crate) fn encode_structured_key_into(
+    buffer: &mut String,
+    key: &str,
+    boundaries: &[usize],
+    space_as_plus: bool,
+    charset: Charset,
+    profile: EncodingProfile,
+    allow_dots: bool,
+) {
+    let sets = profile_sets(profile);
+    let set = if allow_dots {
+        &sets.dotted_key
+    } else {
+        &sets.component
+    };
+
+    encode_structured_key_with(buffer, key, boundaries, |buffer, piece| {
+        encode_into(piece, space_as_plus, charset, buffer, set);
+    });
+}
+
+pub(crate) fn encode_structured_key_with<F>(
+    buffer: &mut String,
+    key: &str,
+    boundaries: &[usize],
+    mut encode_piece: F,
+) where
+    F: FnMut(&mut String, &str),
+{
+    let mut tail = 0;
+    for &idx in boundaries {
+        encode_piece(buffer, &key[tail..idx]);
+        buffer.push_str(&key[idx..=idx]);
+        tail = idx + 1;
+    }
+    encode_piece(buffer, &key[tail..]);
+}
+
+pub(crate) fn encode_value_into(
+    buffer: &mut String,
+    value: &str,
+    space_as_plus: bool,
+    charset: Charset,
+    profile: EncodingProfile,
+) {
+    let set = &profile_sets(profile).component;
+    encode_into(value, space_as_plus, charset, buffer, set);
+}
+
+pub(crate) fn encode_joined_value_into(
+    buffer: &mut String,
+    value: &str,
+    space_as_plus: bool,
+    charset: Charset,
+    profile: EncodingProfile,
+) {
+    let set = &profile_sets(profile).comma_value;
+    encode_into(value, space_as_plus, charset, buffer, set);
+}
+
+pub(crate) fn estimate_encoded_extra(
+    component: &str,
+    space_as_plus: bool,
+    profile: EncodingProfile,
+) -> usize {
+    let bit = profile_bit(profile);
     component
         .bytes()
-        .filter(|byte| needs_encoding(*byte, space_as_plus))
+        .filter(|byte| needs_encoding(*byte, space_as_plus, bit))
         .count()
         .saturating_mul(2)
 }
 
-fn encode_into(component: &str, space_as_plus: bool, buffer: &mut String) {
+fn encode_into(
+    component: &str,
+    space_as_plus: bool,
+    charset: Charset,
+    buffer: &mut String,
+    set: &'static AsciiSet,
+) {
     if component.is_empty() {
         return;
     }
 
     if !space_as_plus {
-        append_encoded(component, buffer);
+        append_encoded(component, charset, buffer, set);
         return;
     }
 
@@ -60,7 +196,7 @@ fn encode_into(component: &str, space_as_plus: bool, buffer: &mut String) {
     for (idx, ch) in component.char_indices() {
         if ch == ' ' {
             if tail < idx {
-                append_encoded(&component[tail..idx], buffer);
+                append_encoded(&component[tail..idx], charset, buffer, set);
             }
             buffer.push('+');
             tail = idx + ch.len_utf8();
@@ -68,34 +204,43 @@ fn encode_into(component: &str, space_as_plus: bool, buffer: &mut String) {
     }
 
     if tail < component.len() {
-        append_encoded(&component[tail..], buffer);
+        append_encoded(&component[tail..], charset, buffer, set);
     }
 }
 
-fn append_encoded(segment: &str, buffer: &mut String) {
+fn append_encoded(segment: &str, charset: Charset, buffer: &mut String, set: &'static AsciiSet) {
     if segment.is_empty() {
         return;
     }
 
-    let _ = write!(
-        buffer,
-        "{}",
-        utf8_percent_encode(segment, COMPONENT_ENCODE_SET)
-    );
+    match charset {
+        Charset::Utf8 | Charset::Utf8Lossy => {
+            let _ = write!(buffer, "{}", utf8_percent_encode(segment, set));
+        }
+        Charset::Iso88591 => {
+            let latin1 = encode_latin1(segment);
+            let _ = write!(buffer, "{}", percent_encode(&latin1, set));
+        }
+    }
 }
 
-#[inline]
-fn needs_encoding(byte: u8, space_as_plus: bool) -> bool {
-    if byte >= 0x80 {
-        return true;
+fn encode_latin1(segment: &str) -> SmallVec<[u8; 64]> {
+    let mut bytes = SmallVec::with_capacity(segment.len());
+    for ch in segment.chars() {
+        match u8::try_from(u32::from(ch)) {
+            Ok(byte) => bytes.push(byte),
+            Err(_) => bytes.extend_from_slice(format!("&#{};", u32::from(ch)).as_bytes()),
+        }
     }
+    bytes
+}
 
+#[inline]
+fn needs_encoding(byte: u8, space_as_plus: bool, profile_bit: u8) -> bool {
     match byte {
+        0x80.. => true,
         b' ' => !space_as_plus,
-        0x00..=0x1F | 0x7F => true,
-        b'"' | b'#' | b'%' | b'&' | b'+' | b',' | b'/' | b':' | b';' | b'<' | b'>' | b'='
-        | b'?' | b'@' | b'[' | b'\\' | b']' | b'^' | b'`' | b'{' | b'|' | b'}' => true,
-        _ => false,
+        _ => ENCODE_TABLE[usize::from(byte)] & profile_bit != 0,
     }
 }
 
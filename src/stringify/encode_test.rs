@@ -1,12 +1,26 @@
+use crate::config::{Charset, EncodingProfile};
+
 fn encode_key(initial: &str, input: &str, space_as_plus: bool) -> String {
     let mut buffer = String::from(initial);
-    super::encode_key_into(&mut buffer, input, space_as_plus);
+    super::encode_key_into(
+        &mut buffer,
+        input,
+        space_as_plus,
+        Charset::Utf8,
+        EncodingProfile::Component,
+    );
     buffer
 }
 
 fn encode_value(initial: &str, input: &str, space_as_plus: bool) -> String {
     let mut buffer = String::from(initial);
-    super::encode_value_into(&mut buffer, input, space_as_plus);
+    super::encode_value_into(
+        &mut buffer,
+        input,
+        space_as_plus,
+        Charset::Utf8,
+        EncodingProfile::Component,
+    );
     buffer
 }
 
@@ -113,25 +127,215 @@ mod encode_value_into {
     }
 }
 
-mod build_component_set {
+mod build_profile_sets {
     #[test]
     fn should_mark_expected_bytes_for_encoding_when_building_component_set_then_include_reserved_characters()
      {
-        let component_set = Box::leak(Box::new(super::super::build_component_set()));
-        let encoded = percent_encoding::utf8_percent_encode(" +-_", component_set).to_string();
+        let sets = Box::leak(Box::new(super::super::build_profile_sets(
+            super::super::COMPONENT,
+        )));
+        let encoded = percent_encoding::utf8_percent_encode(" +-_", &sets.component).to_string();
 
         assert_eq!(encoded, "%20%2B-_");
     }
+
+    #[test]
+    fn should_add_separator_bytes_when_building_derived_sets_then_escape_dot_and_comma() {
+        let sets = Box::leak(Box::new(super::super::build_profile_sets(
+            super::super::RFC3986,
+        )));
+
+        let dotted = percent_encoding::utf8_percent_encode("a.b,c", &sets.dotted_key).to_string();
+        let joined = percent_encoding::utf8_percent_encode("a.b,c", &sets.comma_value).to_string();
+
+        assert_eq!(dotted, "a%2Eb,c");
+        assert_eq!(joined, "a.b%2Cc");
+    }
+}
+
+mod encoding_profiles {
+    use super::*;
+
+    const SAMPLE: &str = "a b,/:?@~!$'()*;&=+#";
+
+    fn encode_with(profile: EncodingProfile, space_as_plus: bool) -> String {
+        let mut buffer = String::new();
+        super::super::encode_value_into(&mut buffer, SAMPLE, space_as_plus, Charset::Utf8, profile);
+        buffer
+    }
+
+    #[test]
+    fn should_keep_query_safe_characters_when_profile_is_rfc3986_then_leave_sub_delims_literal() {
+        assert_eq!(
+            encode_with(EncodingProfile::Rfc3986, false),
+            "a%20b,/:%3F@~!$'()*%3B%26%3D%2B%23"
+        );
+    }
+
+    #[test]
+    fn should_encode_reserved_and_unsafe_characters_when_profile_is_rfc1738_then_keep_safe_set() {
+        assert_eq!(
+            encode_with(EncodingProfile::Rfc1738, true),
+            "a+b,%2F%3A%3F%40%7E!$'()*%3B%26%3D%2B%23"
+        );
+    }
+
+    #[test]
+    fn should_match_form_serializer_when_profile_is_whatwg_then_keep_only_alphanumerics_and_marks()
+    {
+        assert_eq!(
+            encode_with(EncodingProfile::Whatwg, true),
+            "a+b%2C%2F%3A%3F%40%7E%21%24%27%28%29*%3B%26%3D%2B%23"
+        );
+    }
+
+    #[test]
+    fn should_agree_with_encoder_when_estimating_extra_bytes_then_match_every_profile() {
+        for profile in [
+            EncodingProfile::Component,
+            EncodingProfile::Rfc3986,
+            EncodingProfile::Rfc1738,
+            EncodingProfile::Whatwg,
+        ] {
+            for space_as_plus in [false, true] {
+                let encoded = encode_with(profile, space_as_plus);
+                let estimate = super::super::estimate_encoded_extra(SAMPLE, space_as_plus, profile);
+
+                assert_eq!(encoded.len(), SAMPLE.len() + estimate, "{profile:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn should_report_delimiter_encoding_when_checking_profile_then_follow_table() {
+        assert!(super::super::profile_encodes(EncodingProfile::Rfc3986, '&'));
+        assert!(!super::super::profile_encodes(
+            EncodingProfile::Rfc3986,
+            ','
+        ));
+        assert!(super::super::profile_encodes(
+            EncodingProfile::Component,
+            ','
+        ));
+        assert!(super::super::profile_encodes(
+            EncodingProfile::Whatwg,
+            '\u{e9}'
+        ));
+    }
+}
+
+mod encode_structured_key_into {
+    use super::{Charset, EncodingProfile};
+
+    fn encode_dotted(input: &str, boundaries: &[usize]) -> String {
+        let mut buffer = String::new();
+        super::super::encode_structured_key_into(
+            &mut buffer,
+            input,
+            boundaries,
+            false,
+            Charset::Utf8,
+            EncodingProfile::Component,
+            true,
+        );
+        buffer
+    }
+
+    #[test]
+    fn should_emit_literal_brackets_when_boundaries_mark_brackets_then_encode_segment_contents() {
+        let mut buffer = String::new();
+
+        super::super::encode_structured_key_into(
+            &mut buffer,
+            "a[b c][]",
+            &[1, 5, 6, 7],
+            true,
+            Charset::Utf8,
+            EncodingProfile::Component,
+            false,
+        );
+
+        assert_eq!(buffer, "a[b+c][]");
+    }
+
+    #[test]
+    fn should_keep_literal_dots_when_dots_are_disabled_then_only_encode_component_set() {
+        let mut buffer = String::new();
+
+        super::super::encode_structured_key_into(
+            &mut buffer,
+            "v1.0[x]",
+            &[4, 6],
+            false,
+            Charset::Utf8,
+            EncodingProfile::Component,
+            false,
+        );
+
+        assert_eq!(buffer, "v1.0[x]");
+    }
+
+    #[test]
+    fn should_emit_raw_dot_when_boundary_marks_separator_then_join_segments_with_dots() {
+        assert_eq!(
+            encode_dotted("filter.owner.id", &[6, 12]),
+            "filter.owner.id"
+        );
+    }
+
+    #[test]
+    fn should_percent_encode_literal_dots_when_segment_contains_dot_then_emit_percent_two_e() {
+        assert_eq!(encode_dotted("v1.0.a b", &[4]), "v1%2E0.a%20b");
+    }
 }
 
 mod append_encoded {
+    use super::Charset;
+
+    fn append_with(segment: &str, charset: Charset) -> String {
+        let mut buffer = String::new();
+        super::super::append_encoded(
+            segment,
+            charset,
+            &mut buffer,
+            &super::super::COMPONENT_SETS.component,
+        );
+        buffer
+    }
+
     #[test]
     fn should_leave_buffer_unchanged_when_segment_is_empty_then_skip_encoding() {
         let mut buffer = String::from("seed");
         let segment = std::hint::black_box("");
 
-        super::super::append_encoded(segment, &mut buffer);
+        super::super::append_encoded(
+            segment,
+            Charset::Utf8,
+            &mut buffer,
+            &super::super::COMPONENT_SETS.component,
+        );
 
         assert_eq!(buffer, "seed");
     }
+
+    #[test]
+    fn should_emit_single_byte_escapes_when_charset_is_latin1_then_encode_code_points_directly() {
+        let encoded = append_with("caf\u{e9} \u{fc}", Charset::Iso88591);
+
+        assert_eq!(encoded, "caf%E9%20%FC");
+    }
+
+    #[test]
+    fn should_emit_numeric_reference_when_latin1_cannot_represent_character_then_escape_entity() {
+        let encoded = append_with("\u{2713}ok", Charset::Iso88591);
+
+        assert_eq!(encoded, "%26%2310003%3Bok");
+    }
+
+    #[test]
+    fn should_emit_utf8_escapes_when_charset_is_utf8_then_encode_multibyte_sequences() {
+        let encoded = append_with("caf\u{e9}", Charset::Utf8);
+
+        assert_eq!(encoded, "caf%C3%A9");
+    }
 }
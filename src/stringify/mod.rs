@@ -1,12 +1,12 @@
 pub mod api;
-mod encode;
+pub(crate) mod encode;
 pub mod errors;
 mod runtime;
 mod validate;
 mod walker;
 mod writer;
 
-pub use api::stringify;
+pub use api::{stringify, stringify_query_map};
 pub use errors::StringifyError;
 
 pub(crate) type StringifyResult<T> = Result<T, errors::StringifyError>;
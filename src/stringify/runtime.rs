@@ -1,26 +1,46 @@
 use super::StringifyResult;
 use super::errors::StringifyError;
-use crate::config::StringifyOptions;
+use crate::config::{ArrayFormat, Charset, Encoder, EncodingProfile, StringifyOptions};
 use crate::memory::{acquire_string, buffer::StringGuard};
 use crate::model::{QueryMap, Value};
 use smallvec::SmallVec;
 
 use super::validate::ensure_no_control;
-use super::walker::{Segment, StackItem, append_segment};
-use super::writer::write_pair;
+use super::walker::{KeyBoundaries, Segment, StackItem, append_segment, truncate_key};
+use super::writer::{write_bare_key, write_joined_pair, write_pair};
 
 const STACK_INLINE_CAPACITY: usize = 96;
 type StringifyStack<'a> = SmallVec<[StackItem<'a>; STACK_INLINE_CAPACITY]>;
 
 #[derive(Clone, Copy)]
-pub(crate) struct StringifyRuntime {
+pub(crate) struct StringifyRuntime<'a> {
     pub(crate) space_as_plus: bool,
+    pub(crate) array_format: ArrayFormat,
+    pub(crate) allow_dots: bool,
+    pub(crate) literal_brackets: bool,
+    pub(crate) pair_delimiter: char,
+    pub(crate) key_value_separator: char,
+    pub(crate) strict_null_handling: bool,
+    pub(crate) skip_empty: bool,
+    pub(crate) charset: Charset,
+    pub(crate) encoding_profile: EncodingProfile,
+    pub(crate) encoder: Option<&'a dyn Encoder>,
 }
 
-impl StringifyRuntime {
-    pub(crate) fn new(options: &StringifyOptions) -> Self {
+impl<'a> StringifyRuntime<'a> {
+    pub(crate) fn new(options: &'a StringifyOptions) -> Self {
         Self {
             space_as_plus: options.space_as_plus,
+            array_format: options.array_format,
+            allow_dots: options.allow_dots,
+            literal_brackets: options.literal_brackets,
+            pair_delimiter: options.delimiters.pair(),
+            key_value_separator: options.delimiters.key_value(),
+            strict_null_handling: options.strict_null_handling,
+            skip_empty: options.skip_empty,
+            charset: options.charset,
+            encoding_profile: options.encoding_profile,
+            encoder: options.encoder.as_deref(),
         }
     }
 }
@@ -37,6 +57,7 @@ pub(crate) fn stringify_query_map_with(
         runtime,
         mut output,
         mut key_guard,
+        mut boundaries,
         mut stack,
     } = prepare_stringify_state(map, options)?;
 
@@ -45,6 +66,7 @@ pub(crate) fn stringify_query_map_with(
         runtime,
         &mut stack,
         key_guard.as_mut(),
+        &mut boundaries,
         &mut output,
         &mut first_pair,
     )?;
@@ -53,15 +75,16 @@ pub(crate) fn stringify_query_map_with(
 }
 
 struct PreparedState<'map> {
-    runtime: StringifyRuntime,
+    runtime: StringifyRuntime<'map>,
     output: String,
     key_guard: StringGuard,
+    boundaries: KeyBoundaries,
     stack: StringifyStack<'map>,
 }
 
 fn prepare_stringify_state<'map>(
     map: &'map QueryMap,
-    options: &StringifyOptions,
+    options: &'map StringifyOptions,
 ) -> StringifyResult<PreparedState<'map>> {
     let runtime = StringifyRuntime::new(options);
     let output = String::with_capacity(map.len().saturating_mul(16));
@@ -84,14 +107,16 @@ fn prepare_stringify_state<'map>(
         runtime,
         output,
         key_guard,
+        boundaries: KeyBoundaries::new(),
         stack,
     })
 }
 
 fn process_pairs(
-    runtime: StringifyRuntime,
+    runtime: StringifyRuntime<'_>,
     stack: &mut StringifyStack<'_>,
     key_buffer: &mut String,
+    boundaries: &mut KeyBoundaries,
     output: &mut String,
     first_pair: &mut bool,
 ) -> StringifyResult<()> {
@@ -102,18 +127,29 @@ fn process_pairs(
             value,
         } = item;
 
-        key_buffer.truncate(parent_len);
-        append_segment(key_buffer, segment);
+        truncate_key(key_buffer, boundaries, parent_len);
+        append_segment(key_buffer, boundaries, segment, runtime);
 
         match value {
+            Value::Null if runtime.strict_null_handling => {
+                write_bare_key(output, key_buffer, boundaries, runtime, first_pair);
+            }
+            Value::Null if runtime.skip_empty => {}
+            Value::Null => write_pair(output, key_buffer, boundaries, "", runtime, first_pair),
+            Value::String(s) if s.is_empty() && runtime.skip_empty => {}
             Value::String(s) => {
                 ensure_no_control(s).map_err(|_| StringifyError::InvalidValue {
-                    key: key_buffer.clone(),
+                    key: key_buffer.to_string(),
                     value: s.to_string(),
                 })?;
-                write_pair(output, key_buffer, s, runtime.space_as_plus, first_pair);
+                write_pair(output, key_buffer, boundaries, s, runtime, first_pair);
             }
             Value::Array(arr) => {
+                if runtime.array_format == ArrayFormat::Comma
+                    && write_comma_pair(runtime, key_buffer, boundaries, arr, output, first_pair)?
+                {
+                    continue;
+                }
                 let current_len = key_buffer.len();
                 stack.reserve(arr.len());
                 for idx in (0..arr.len()).rev() {
@@ -129,9 +165,12 @@ fn process_pairs(
                 stack.reserve(obj.len());
                 for (sub_key, sub_value) in obj.iter().rev() {
                     if ensure_no_control(sub_key).is_err() {
-                        return Err(StringifyError::InvalidKey {
-                            key: format!("{}[{}]", key_buffer, sub_key),
-                        });
+                        let key = if runtime.allow_dots {
+                            format!("{key_buffer}.{sub_key}")
+                        } else {
+                            format!("{key_buffer}[{sub_key}]")
+                        };
+                        return Err(StringifyError::InvalidKey { key });
                     }
                     stack.push(StackItem {
                         parent_len: current_len,
@@ -146,6 +185,32 @@ fn process_pairs(
     Ok(())
 }
 
+fn write_comma_pair(
+    runtime: StringifyRuntime<'_>,
+    key_buffer: &str,
+    boundaries: &[usize],
+    arr: &[Value],
+    output: &mut String,
+    first_pair: &mut bool,
+) -> StringifyResult<bool> {
+    let mut values: SmallVec<[&str; 16]> = SmallVec::with_capacity(arr.len());
+    for item in arr {
+        let Value::String(s) = item else {
+            return Ok(false);
+        };
+        ensure_no_control(s).map_err(|_| StringifyError::InvalidValue {
+            key: key_buffer.to_string(),
+            value: s.to_string(),
+        })?;
+        values.push(s);
+    }
+
+    if !values.is_empty() {
+        write_joined_pair(output, key_buffer, boundaries, &values, runtime, first_pair);
+    }
+    Ok(true)
+}
+
 #[cfg(test)]
 #[path = "runtime_test.rs"]
 mod runtime_test;
@@ -1,5 +1,5 @@
 use super::{StringifyError, StringifyRuntime};
-use crate::config::StringifyOptions;
+use crate::config::{ArrayFormat, StringifyOptions};
 use crate::model::{OrderedMap, QueryMap, Value};
 use assert_matches::assert_matches;
 
@@ -8,7 +8,10 @@ fn stringify_map(map: QueryMap, options: StringifyOptions) -> Result<String, Str
 }
 
 fn options(space_as_plus: bool) -> StringifyOptions {
-    StringifyOptions { space_as_plus }
+    StringifyOptions {
+        space_as_plus,
+        ..StringifyOptions::default()
+    }
 }
 
 fn nested_profile_map() -> QueryMap {
@@ -139,6 +142,92 @@ mod stringify_query_map_with {
     }
 }
 
+mod stringify_with_array_format {
+    use super::*;
+
+    fn tags_map(items: Vec<Value>) -> QueryMap {
+        QueryMap::from_iter([("tags", Value::Array(items))])
+    }
+
+    fn format_options(format: ArrayFormat) -> StringifyOptions {
+        StringifyOptions::new().array_format(format)
+    }
+
+    #[test]
+    fn should_emit_single_comma_joined_pair_when_format_is_comma_then_join_string_items() {
+        let map = tags_map(vec![Value::from("a"), Value::from("b")]);
+
+        let result = stringify_map(map, format_options(ArrayFormat::Comma)).expect("comma format");
+
+        assert_eq!(result, "tags=a,b");
+    }
+
+    #[test]
+    fn should_skip_pair_when_comma_format_receives_empty_array_then_produce_empty_output() {
+        let map = tags_map(Vec::new());
+
+        let result = stringify_map(map, format_options(ArrayFormat::Comma)).expect("comma format");
+
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn should_fall_back_to_indices_when_comma_array_contains_containers_then_emit_indexed_keys() {
+        let nested = OrderedMap::from_iter([("id".to_string(), Value::from("1"))]);
+        let map = tags_map(vec![Value::from("a"), Value::Object(nested)]);
+
+        let result = stringify_map(map, format_options(ArrayFormat::Comma)).expect("comma format");
+
+        assert_eq!(result, "tags%5B0%5D=a&tags%5B1%5D%5Bid%5D=1");
+    }
+
+    #[test]
+    fn should_report_array_key_when_comma_item_contains_control_then_return_invalid_value_error() {
+        let map = tags_map(vec![Value::from("ok"), Value::from("bad\n")]);
+
+        let error = stringify_map(map, format_options(ArrayFormat::Comma))
+            .expect_err("control characters should be rejected");
+
+        assert_matches!(
+            error,
+            StringifyError::InvalidValue { key, value } if key == "tags" && value == "bad\n"
+        );
+    }
+}
+
+mod stringify_with_dots {
+    use super::*;
+
+    #[test]
+    fn should_report_dotted_path_when_nested_value_contains_control_then_return_invalid_value_error()
+     {
+        let inner = OrderedMap::from_iter([("note".to_string(), Value::from("bad\u{0007}"))]);
+        let map = QueryMap::from_iter([("profile", Value::Object(inner))]);
+
+        let error = stringify_map(map, StringifyOptions::new().allow_dots(true))
+            .expect_err("control characters should be rejected");
+
+        assert_matches!(
+            error,
+            StringifyError::InvalidValue { key, .. } if key == "profile.note"
+        );
+    }
+
+    #[test]
+    fn should_report_dotted_path_when_nested_key_contains_control_then_return_invalid_key_error() {
+        let inner = OrderedMap::from_iter([("bad\u{0007}".to_string(), Value::from("x"))]);
+        let map = QueryMap::from_iter([("profile", Value::Object(inner))]);
+
+        let error = stringify_map(map, StringifyOptions::new().allow_dots(true))
+            .expect_err("control characters should be rejected");
+
+        assert_matches!(
+            error,
+            StringifyError::InvalidKey { key } if key == "profile.bad\u{0007}"
+        );
+    }
+}
+
 mod stringify_runtime {
     use super::*;
 
@@ -149,6 +238,7 @@ mod stringify_runtime {
         let runtime = StringifyRuntime::new(&options);
 
         assert!(runtime.space_as_plus);
+        assert_eq!(runtime.array_format, ArrayFormat::Indices);
     }
 
     #[test]
@@ -163,3 +253,69 @@ mod stringify_runtime {
         assert!(state.stack.is_empty());
     }
 }
+
+mod stringify_with_null {
+    use super::*;
+
+    fn flags_map() -> QueryMap {
+        QueryMap::from_iter([
+            ("debug", Value::Null),
+            ("page", Value::from("2")),
+            ("tags", Value::Array(vec![Value::Null])),
+        ])
+    }
+
+    #[test]
+    fn should_write_bare_keys_when_strict_null_handling_is_enabled_then_omit_separator() {
+        let options = StringifyOptions::new().strict_null_handling(true);
+
+        let result = stringify_map(flags_map(), options).expect("stringify should succeed");
+
+        assert_eq!(result, "debug&page=2&tags%5B0%5D");
+    }
+
+    #[test]
+    fn should_write_empty_values_when_strict_null_handling_is_disabled_then_keep_separator() {
+        let result =
+            stringify_map(flags_map(), StringifyOptions::new()).expect("stringify should succeed");
+
+        assert_eq!(result, "debug=&page=2&tags%5B0%5D=");
+    }
+}
+
+mod stringify_with_skip_empty {
+    use super::*;
+
+    fn form_map() -> QueryMap {
+        QueryMap::from_iter([
+            ("name", Value::from("")),
+            ("page", Value::from("2")),
+            ("note", Value::Null),
+            (
+                "tags",
+                Value::Array(vec![Value::from(""), Value::from("x")]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn should_omit_empty_values_when_skip_empty_is_enabled_then_keep_non_empty_pairs() {
+        let options = StringifyOptions::new().skip_empty(true);
+
+        let result = stringify_map(form_map(), options).expect("stringify should succeed");
+
+        assert_eq!(result, "page=2&tags%5B1%5D=x");
+    }
+
+    #[test]
+    fn should_keep_bare_null_keys_when_skip_empty_and_strict_null_are_enabled_then_write_null_only()
+    {
+        let options = StringifyOptions::new()
+            .skip_empty(true)
+            .strict_null_handling(true);
+
+        let result = stringify_map(form_map(), options).expect("stringify should succeed");
+
+        assert_eq!(result, "page=2&note&tags%5B1%5D=x");
+    }
+}
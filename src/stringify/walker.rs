@@ -1,4 +1,8 @@
+use crate::config::ArrayFormat;
 use crate::model::Value;
+use smallvec::SmallVec;
+
+use super::runtime::StringifyRuntime;
 
 pub(crate) struct StackItem<'a> {
     pub(crate) parent_len: usize,
@@ -6,6 +10,8 @@ pub(crate) struct StackItem<'a> {
     pub(crate) value: &'a Value,
 }
 
+pub(crate) type KeyBoundaries = SmallVec<[usize; 16]>;
+
 #[derive(Clone, Copy)]
 pub(crate) enum Segment<'a> {
     Root(&'a str),
@@ -13,19 +19,51 @@ pub(crate) enum Segment<'a> {
     Array(usize),
 }
 
-pub(crate) fn append_segment(buffer: &mut String, segment: Segment<'_>) {
+pub(crate) fn append_segment(
+    buffer: &mut String,
+    boundaries: &mut KeyBoundaries,
+    segment: Segment<'_>,
+    runtime: StringifyRuntime<'_>,
+) {
+    let literal = runtime.literal_brackets;
+
     match segment {
         Segment::Root(key) => buffer.push_str(key),
-        Segment::Object(sub_key) => {
-            buffer.push('[');
+        Segment::Object(sub_key) if runtime.allow_dots => {
+            push_structural(buffer, boundaries, '.', true);
             buffer.push_str(sub_key);
-            buffer.push(']');
         }
-        Segment::Array(index) => {
-            buffer.push('[');
-            push_usize_decimal(buffer, index);
-            buffer.push(']');
+        Segment::Object(sub_key) => {
+            push_structural(buffer, boundaries, '[', literal);
+            buffer.push_str(sub_key);
+            push_structural(buffer, boundaries, ']', literal);
         }
+        Segment::Array(index) => match runtime.array_format {
+            ArrayFormat::Indices | ArrayFormat::Comma => {
+                push_structural(buffer, boundaries, '[', literal);
+                push_usize_decimal(buffer, index);
+                push_structural(buffer, boundaries, ']', literal);
+            }
+            ArrayFormat::Brackets => {
+                push_structural(buffer, boundaries, '[', literal);
+                push_structural(buffer, boundaries, ']', literal);
+            }
+            ArrayFormat::Repeat => {}
+        },
+    }
+}
+
+fn push_structural(buffer: &mut String, boundaries: &mut KeyBoundaries, ch: char, literal: bool) {
+    if literal {
+        boundaries.push(buffer.len());
+    }
+    buffer.push(ch);
+}
+
+pub(crate) fn truncate_key(buffer: &mut String, boundaries: &mut KeyBoundaries, len: usize) {
+    buffer.truncate(len);
+    while boundaries.last().is_some_and(|&offset| offset >= len) {
+        boundaries.pop();
     }
 }
 
@@ -1,16 +1,42 @@
-use super::Segment;
+use super::{KeyBoundaries, Segment};
+use crate::config::{ArrayFormat, StringifyOptions};
+use crate::stringify::runtime::StringifyRuntime;
+
+fn append_marked(
+    initial: &str,
+    segment: Segment<'_>,
+    options: StringifyOptions,
+) -> (String, KeyBoundaries) {
+    let mut buffer = String::from(initial);
+    let mut boundaries = KeyBoundaries::new();
+    super::append_segment(
+        &mut buffer,
+        &mut boundaries,
+        segment,
+        StringifyRuntime::new(&options),
+    );
+    (buffer, boundaries)
+}
+
+fn append_with_options(initial: &str, segment: Segment<'_>, options: StringifyOptions) -> String {
+    append_marked(initial, segment, options).0
+}
 
 fn append(initial: &str, segment: Segment<'_>) -> String {
-    let mut buffer = String::from(initial);
-    super::append_segment(&mut buffer, segment);
-    buffer
+    append_with_options(initial, segment, StringifyOptions::new())
 }
 
 fn append_all(initial: &str, segments: &[Segment<'_>]) -> String {
+    let mut boundaries = KeyBoundaries::new();
     segments
         .iter()
         .fold(String::from(initial), |mut buffer, segment| {
-            super::append_segment(&mut buffer, *segment);
+            super::append_segment(
+                &mut buffer,
+                &mut boundaries,
+                *segment,
+                StringifyRuntime::new(&StringifyOptions::new()),
+            );
             buffer
         })
 }
@@ -72,6 +98,132 @@ mod append_segment {
     }
 }
 
+mod append_segment_with_array_format {
+    use super::*;
+
+    fn append_with(initial: &str, segment: Segment<'_>, format: ArrayFormat) -> String {
+        append_with_options(
+            initial,
+            segment,
+            StringifyOptions::new().array_format(format),
+        )
+    }
+
+    #[test]
+    fn should_append_empty_brackets_when_format_is_brackets_then_omit_index() {
+        let result = append_with("items", Segment::Array(3), ArrayFormat::Brackets);
+
+        assert_eq!(result, "items[]");
+    }
+
+    #[test]
+    fn should_append_nothing_when_format_is_repeat_then_keep_parent_key() {
+        let result = append_with("items", Segment::Array(3), ArrayFormat::Repeat);
+
+        assert_eq!(result, "items");
+    }
+
+    #[test]
+    fn should_keep_indices_when_format_is_comma_then_fall_back_to_indexed_segment() {
+        let result = append_with("items", Segment::Array(3), ArrayFormat::Comma);
+
+        assert_eq!(result, "items[3]");
+    }
+
+    #[test]
+    fn should_ignore_format_when_segment_is_object_then_append_bracketed_key() {
+        let result = append_with("items", Segment::Object("name"), ArrayFormat::Repeat);
+
+        assert_eq!(result, "items[name]");
+    }
+}
+
+mod append_segment_with_dots {
+    use super::*;
+
+    fn append_dotted(initial: &str, segment: Segment<'_>) -> (String, KeyBoundaries) {
+        append_marked(initial, segment, StringifyOptions::new().allow_dots(true))
+    }
+
+    #[test]
+    fn should_record_separator_offset_when_segment_is_object_then_skip_brackets() {
+        let (result, boundaries) = append_dotted("filter", Segment::Object("status"));
+
+        assert_eq!(result, "filter.status");
+        assert_eq!(boundaries.as_slice(), &[6]);
+    }
+
+    #[test]
+    fn should_keep_brackets_unmarked_when_segment_is_array_then_append_indexed_segment() {
+        let (result, boundaries) = append_dotted("items", Segment::Array(1));
+
+        assert_eq!(result, "items[1]");
+        assert!(boundaries.is_empty());
+    }
+
+    #[test]
+    fn should_leave_dots_in_root_key_unmarked_when_dots_enabled_then_mark_only_separators() {
+        let (result, boundaries) = append_dotted("v1.0", Segment::Object("owner"));
+
+        assert_eq!(result, "v1.0.owner");
+        assert_eq!(boundaries.as_slice(), &[4]);
+    }
+}
+
+mod append_segment_with_literal_brackets {
+    use super::*;
+
+    #[test]
+    fn should_record_bracket_offsets_when_literal_brackets_enabled_then_mark_boundaries() {
+        let (result, boundaries) = append_marked(
+            "user",
+            Segment::Object("name"),
+            StringifyOptions::new().literal_brackets(true),
+        );
+
+        assert_eq!(result, "user[name]");
+        assert_eq!(boundaries.as_slice(), &[4, 9]);
+    }
+
+    #[test]
+    fn should_record_empty_bracket_offsets_when_format_is_brackets_then_mark_boundaries() {
+        let (result, boundaries) = append_marked(
+            "items",
+            Segment::Array(2),
+            StringifyOptions::new()
+                .literal_brackets(true)
+                .array_format(ArrayFormat::Brackets),
+        );
+
+        assert_eq!(result, "items[]");
+        assert_eq!(boundaries.as_slice(), &[5, 6]);
+    }
+
+    #[test]
+    fn should_skip_offsets_when_literal_brackets_disabled_then_leave_boundaries_empty() {
+        let (result, boundaries) =
+            append_marked("user", Segment::Array(0), StringifyOptions::new());
+
+        assert_eq!(result, "user[0]");
+        assert!(boundaries.is_empty());
+    }
+}
+
+mod truncate_key {
+    use super::*;
+
+    #[test]
+    fn should_drop_offsets_past_parent_length_when_truncating_then_keep_parent_marks() {
+        let mut buffer = String::from("a[b][c]");
+        let mut boundaries: KeyBoundaries = [1, 3, 4, 6].into_iter().collect();
+
+        super::super::truncate_key(&mut buffer, &mut boundaries, 4);
+
+        assert_eq!(buffer, "a[b]");
+        assert_eq!(boundaries.as_slice(), &[1, 3]);
+    }
+}
+
 mod ascii_digits_to_str {
     #[test]
     fn should_convert_digit_bytes_to_str_when_bytes_are_numeric_then_return_string() {
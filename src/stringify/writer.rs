@@ -1,16 +1,24 @@
-use super::encode::{encode_key_into, encode_value_into, estimate_encoded_extra};
+use super::encode::{
+    encode_joined_value_into, encode_key_into, encode_structured_key_into,
+    encode_structured_key_with, encode_value_into, estimate_encoded_extra,
+};
+use super::runtime::StringifyRuntime;
+use crate::config::{ComponentKind, EncodeContext};
 
 pub(crate) fn write_pair(
     output: &mut String,
     key: &str,
+    boundaries: &[usize],
     value: &str,
-    space_as_plus: bool,
+    runtime: StringifyRuntime<'_>,
     first_pair: &mut bool,
 ) {
+    let space_as_plus = runtime.space_as_plus;
+    let profile = runtime.encoding_profile;
     let separators = 1 + usize::from(!*first_pair);
     let base = separators + key.len() + value.len();
-    let extra = estimate_encoded_extra(key, space_as_plus)
-        .saturating_add(estimate_encoded_extra(value, space_as_plus));
+    let extra = estimate_encoded_extra(key, space_as_plus, profile)
+        .saturating_add(estimate_encoded_extra(value, space_as_plus, profile));
     let required = base.saturating_add(extra);
     let available = output.capacity() - output.len();
     if available < required {
@@ -18,14 +26,154 @@ pub(crate) fn write_pair(
     }
 
     if !*first_pair {
-        output.push('&');
+        output.push(runtime.pair_delimiter);
     } else {
         *first_pair = false;
     }
 
-    encode_key_into(output, key, space_as_plus);
-    output.push('=');
-    encode_value_into(output, value, space_as_plus);
+    write_key(output, key, boundaries, runtime);
+    output.push(runtime.key_value_separator);
+    write_value(output, key, value, runtime, false);
+}
+
+pub(crate) fn write_bare_key(
+    output: &mut String,
+    key: &str,
+    boundaries: &[usize],
+    runtime: StringifyRuntime<'_>,
+    first_pair: &mut bool,
+) {
+    let required = usize::from(!*first_pair)
+        .saturating_add(key.len())
+        .saturating_add(estimate_encoded_extra(
+            key,
+            runtime.space_as_plus,
+            runtime.encoding_profile,
+        ));
+    let available = output.capacity() - output.len();
+    if available < required {
+        output.reserve(required - available);
+    }
+
+    if !*first_pair {
+        output.push(runtime.pair_delimiter);
+    } else {
+        *first_pair = false;
+    }
+
+    write_key(output, key, boundaries, runtime);
+}
+
+pub(crate) fn write_joined_pair(
+    output: &mut String,
+    key: &str,
+    boundaries: &[usize],
+    values: &[&str],
+    runtime: StringifyRuntime<'_>,
+    first_pair: &mut bool,
+) {
+    let space_as_plus = runtime.space_as_plus;
+    let profile = runtime.encoding_profile;
+    let separators = 1 + usize::from(!*first_pair) + values.len().saturating_sub(1);
+    let mut required = separators
+        .saturating_add(key.len())
+        .saturating_add(estimate_encoded_extra(key, space_as_plus, profile));
+    for value in values {
+        required = required
+            .saturating_add(value.len())
+            .saturating_add(estimate_encoded_extra(value, space_as_plus, profile));
+    }
+    let available = output.capacity() - output.len();
+    if available < required {
+        output.reserve(required - available);
+    }
+
+    if !*first_pair {
+        output.push(runtime.pair_delimiter);
+    } else {
+        *first_pair = false;
+    }
+
+    write_key(output, key, boundaries, runtime);
+    output.push(runtime.key_value_separator);
+    for (idx, value) in values.iter().enumerate() {
+        if idx > 0 {
+            output.push(',');
+        }
+        write_value(output, key, value, runtime, true);
+    }
+}
+
+fn write_value(
+    output: &mut String,
+    key: &str,
+    value: &str,
+    runtime: StringifyRuntime<'_>,
+    joined: bool,
+) {
+    let encode_default = |component: &str, output: &mut String| {
+        let encode = if joined {
+            encode_joined_value_into
+        } else {
+            encode_value_into
+        };
+        encode(
+            output,
+            component,
+            runtime.space_as_plus,
+            runtime.charset,
+            runtime.encoding_profile,
+        );
+    };
+
+    let Some(encoder) = runtime.encoder else {
+        encode_default(value, output);
+        return;
+    };
+
+    let context = EncodeContext::new(key, ComponentKind::Value, &encode_default);
+    encoder.encode(value, &context, output);
+}
+
+fn write_key(output: &mut String, key: &str, boundaries: &[usize], runtime: StringifyRuntime<'_>) {
+    let Some(encoder) = runtime.encoder else {
+        write_key_default(output, key, boundaries, runtime);
+        return;
+    };
+
+    let encode_default =
+        |component: &str, output: &mut String| write_key_default(output, component, &[], runtime);
+    let context = EncodeContext::new(key, ComponentKind::Key, &encode_default);
+    encode_structured_key_with(output, key, boundaries, |output, piece| {
+        encoder.encode(piece, &context, output);
+    });
+}
+
+fn write_key_default(
+    output: &mut String,
+    key: &str,
+    boundaries: &[usize],
+    runtime: StringifyRuntime<'_>,
+) {
+    if runtime.allow_dots || runtime.literal_brackets {
+        encode_structured_key_into(
+            output,
+            key,
+            boundaries,
+            runtime.space_as_plus,
+            runtime.charset,
+            runtime.encoding_profile,
+            runtime.allow_dots,
+        );
+    } else {
+        encode_key_into(
+            output,
+            key,
+            runtime.space_as_plus,
+            runtime.charset,
+            runtime.encoding_profile,
+        );
+    }
 }
 
 #[cfg(test)]
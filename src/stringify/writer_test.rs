@@ -1,3 +1,10 @@
+use crate::config::StringifyOptions;
+use crate::stringify::runtime::StringifyRuntime;
+
+fn runtime(options: StringifyOptions) -> StringifyRuntime<'static> {
+    StringifyRuntime::new(Box::leak(Box::new(options)))
+}
+
 struct WriteOutcome {
     output: String,
     first_pair: bool,
@@ -12,7 +19,14 @@ fn write_once(
 ) -> WriteOutcome {
     let mut output = String::from(initial);
     let mut first = first_pair;
-    crate::stringify::writer::write_pair(&mut output, key, value, space_as_plus, &mut first);
+    crate::stringify::writer::write_pair(
+        &mut output,
+        key,
+        &[],
+        value,
+        runtime(StringifyOptions::new().space_as_plus(space_as_plus)),
+        &mut first,
+    );
     WriteOutcome {
         output,
         first_pair: first,
@@ -67,8 +81,9 @@ mod write_pair {
         crate::stringify::writer::write_pair(
             &mut output,
             "name+role?",
+            &[],
             "value/with=reserved&stuff",
-            false,
+            runtime(StringifyOptions::new()),
             &mut first_pair,
         );
 
@@ -77,3 +92,201 @@ mod write_pair {
         assert!(output.capacity() >= output.len());
     }
 }
+
+mod write_pair_with_structured_keys {
+    use super::*;
+
+    #[test]
+    fn should_emit_dotted_key_when_allow_dots_enabled_then_keep_separators_unescaped() {
+        let mut output = String::new();
+        let mut first_pair = true;
+
+        crate::stringify::writer::write_pair(
+            &mut output,
+            "filter.v1.0",
+            &[6],
+            "open",
+            runtime(StringifyOptions::new().allow_dots(true)),
+            &mut first_pair,
+        );
+
+        assert_eq!(output, "filter.v1%2E0=open");
+    }
+
+    #[test]
+    fn should_leave_structural_brackets_literal_when_literal_brackets_enabled_then_encode_contents_only()
+     {
+        let mut output = String::new();
+        let mut first_pair = true;
+
+        crate::stringify::writer::write_pair(
+            &mut output,
+            "a b[x[y]][0]",
+            &[3, 8, 9, 11],
+            "v&w",
+            runtime(StringifyOptions::new().literal_brackets(true)),
+            &mut first_pair,
+        );
+
+        assert_eq!(output, "a%20b[x%5By%5D][0]=v%26w");
+    }
+}
+
+mod write_joined_pair {
+    use super::*;
+
+    fn write_joined(initial: &str, key: &str, values: &[&str], first_pair: bool) -> (String, bool) {
+        let mut output = String::from(initial);
+        let mut first = first_pair;
+        crate::stringify::writer::write_joined_pair(
+            &mut output,
+            key,
+            &[],
+            values,
+            runtime(StringifyOptions::new()),
+            &mut first,
+        );
+        (output, first)
+    }
+
+    #[test]
+    fn should_join_values_with_literal_comma_when_writing_first_pair_then_emit_single_pair() {
+        let (output, first_pair) = write_joined("", "tags", &["a", "b", "c"], true);
+
+        assert_eq!(output, "tags=a,b,c");
+        assert!(!first_pair);
+    }
+
+    #[test]
+    fn should_percent_encode_commas_inside_values_when_joining_then_keep_items_distinguishable() {
+        let (output, _) = write_joined("x=1", "tags", &["a,b", "c d"], false);
+
+        assert_eq!(output, "x=1&tags=a%2Cb,c%20d");
+    }
+}
+
+mod write_pair_with_delimiters {
+    use super::*;
+    use crate::stringify::writer::{write_joined_pair, write_pair};
+
+    #[test]
+    fn should_use_configured_delimiters_when_writing_pairs_then_join_with_custom_separators() {
+        let runtime = runtime(
+            StringifyOptions::new()
+                .pair_delimiter(';')
+                .key_value_separator(':'),
+        );
+        let mut output = String::new();
+        let mut first = true;
+
+        write_pair(&mut output, "a", &[], "1", runtime, &mut first);
+        write_joined_pair(&mut output, "b", &[], &["x", "y"], runtime, &mut first);
+
+        assert_eq!(output, "a:1;b:x,y");
+    }
+
+    #[test]
+    fn should_encode_default_delimiters_when_custom_delimiters_are_configured_then_keep_them_escaped()
+     {
+        let runtime = runtime(StringifyOptions::new().pair_delimiter(';'));
+        let mut output = String::new();
+        let mut first = true;
+
+        write_pair(&mut output, "a&b", &[], "c;d", runtime, &mut first);
+
+        assert_eq!(output, "a%26b=c%3Bd");
+    }
+}
+
+mod write_bare_key {
+    use super::*;
+    use crate::stringify::writer::write_bare_key;
+
+    #[test]
+    fn should_write_key_without_separator_when_writing_bare_key_then_only_add_pair_delimiter() {
+        let runtime = runtime(StringifyOptions::new().strict_null_handling(true));
+        let mut output = String::from("a=1");
+        let mut first = false;
+
+        write_bare_key(&mut output, "debug mode", &[], runtime, &mut first);
+
+        assert_eq!(output, "a=1&debug%20mode");
+    }
+}
+
+mod write_pair_with_encoder {
+    use super::*;
+    use crate::config::{ComponentKind, EncodeContext, Encoder};
+    use crate::stringify::writer::{write_joined_pair, write_pair};
+
+    #[derive(Debug)]
+    struct SignaturePassthrough;
+
+    impl Encoder for SignaturePassthrough {
+        fn encode(&self, component: &str, context: &EncodeContext<'_>, output: &mut String) {
+            if context.kind() == ComponentKind::Value && context.key_path().ends_with("[sig]") {
+                output.push_str(component);
+            } else {
+                context.encode_default(component, output);
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct TaggingEncoder;
+
+    impl Encoder for TaggingEncoder {
+        fn encode(&self, component: &str, context: &EncodeContext<'_>, output: &mut String) {
+            output.push(match context.kind() {
+                ComponentKind::Key => 'k',
+                ComponentKind::Value => 'v',
+            });
+            context.encode_default(component, output);
+        }
+    }
+
+    #[test]
+    fn should_emit_value_verbatim_when_hook_passes_it_through_then_skip_default_encoding() {
+        let runtime = runtime(StringifyOptions::new().encoder(SignaturePassthrough));
+        let mut output = String::new();
+        let mut first = true;
+
+        write_pair(
+            &mut output,
+            "auth[sig]",
+            &[],
+            "ab%2Fcd",
+            runtime,
+            &mut first,
+        );
+        write_pair(&mut output, "auth[note]", &[], "a/b", runtime, &mut first);
+
+        assert_eq!(output, "auth%5Bsig%5D=ab%2Fcd&auth%5Bnote%5D=a%2Fb");
+    }
+
+    #[test]
+    fn should_call_hook_per_key_piece_when_brackets_are_literal_then_keep_structure() {
+        let runtime = runtime(
+            StringifyOptions::new()
+                .literal_brackets(true)
+                .encoder(TaggingEncoder),
+        );
+        let mut output = String::new();
+        let mut first = true;
+
+        write_pair(&mut output, "user[name]", &[4, 9], "x", runtime, &mut first);
+
+        assert_eq!(output, "kuser[kname]k=vx");
+    }
+
+    #[test]
+    fn should_call_hook_per_item_when_joining_values_then_keep_commas_escaped_by_default() {
+        let runtime = runtime(StringifyOptions::new().encoder(TaggingEncoder));
+        let mut output = String::new();
+        let mut first = true;
+
+        write_joined_pair(&mut output, "tags", &[], &["a,b", "c"], runtime, &mut first);
+
+        assert_eq!(output, "ktags=va%2Cb,vc");
+    }
+}
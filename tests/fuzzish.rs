@@ -392,9 +392,11 @@ proptest! {
             max_params: config.max_params,
             max_length: config.max_length,
             max_depth: config.max_depth,
+            ..Default::default()
         };
         let stringify_options = StringifyOptions {
             space_as_plus: config.space_as_plus,
+            ..Default::default()
         };
 
         if let Some(limit) = parse_options.max_params {
@@ -423,6 +425,7 @@ proptest! {
     ) {
         let options = StringifyOptions {
             space_as_plus: true,
+            ..Default::default()
         };
         let encoded = stringify_with(&json!({"msg": value.clone()}), &options)
             .expect("stringify should succeed");
@@ -452,6 +455,7 @@ proptest! {
     ) {
         let stringify_options = StringifyOptions {
             space_as_plus: config.space_as_plus,
+            ..Default::default()
         };
         let encoded = stringify_with(&map, &stringify_options).expect("stringify should succeed");
         let total_len = total_string_length(&map);
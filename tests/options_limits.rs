@@ -8,7 +8,8 @@ mod json;
 use asserts::assert_str_path;
 use bunner_qs_rs::parsing::ParseError;
 use bunner_qs_rs::{
-    OptionsValidationError, ParseOptions, QsParseError, QsStringifyError, StringifyOptions,
+    MalformedPairBehavior, OptionsValidationError, ParseOptions, QsParseError, QsStringifyError,
+    StringifyOptions,
 };
 use json::json_from_pairs;
 use serde_json::Value;
@@ -79,6 +80,18 @@ fn expect_depth_exceeded(query: &str, options: &ParseOptions) -> (String, usize,
     }
 }
 
+fn expect_array_index_too_large(query: &str, options: &ParseOptions) -> (String, usize) {
+    match parse_query::<Value>(query, options).expect_err("should exceed array limit") {
+        QsParseError::Parse(ParseError::ArrayIndexTooLarge { segment, limit, .. }) => {
+            (segment, limit)
+        }
+        QsParseError::Parse(other) => panic!("expected array index too large error, got {other:?}"),
+        QsParseError::MissingParseOptions => {
+            unreachable!("parse options must be configured before parsing")
+        }
+    }
+}
+
 fn expect_validation_error<F>(configure: F) -> OptionsValidationError
 where
     F: FnOnce(ParseOptions) -> ParseOptions,
@@ -110,6 +123,7 @@ where
 
 mod parse_limits_tests {
     use super::*;
+    use bunner_qs_rs::DepthOverflow;
 
     #[test]
     fn should_parse_within_max_params_limit_when_limits_allow() {
@@ -169,6 +183,59 @@ mod parse_limits_tests {
         assert_eq!(depth, 3);
     }
 
+    #[test]
+    fn should_collapse_overflowing_brackets_into_literal_key_when_configured() {
+        let options = build_parse_options(|builder| {
+            builder
+                .max_depth(2)
+                .depth_overflow(DepthOverflow::CollapseRemainder)
+        });
+
+        let parsed = parse_with_options("a[b][c][d]=x&a[b][c][e][f]=y&a[b][g]=z", &options);
+
+        assert_eq!(
+            parsed,
+            serde_json::json!({
+                "a": { "b": { "c": { "[d]": "x", "[e][f]": "y" }, "g": "z" } }
+            })
+        );
+    }
+
+    #[test]
+    fn should_keep_appending_when_collapsed_remainder_ends_with_brackets() {
+        let options = build_parse_options(|builder| {
+            builder
+                .max_depth(2)
+                .depth_overflow(DepthOverflow::CollapseRemainder)
+        });
+
+        let parsed = parse_with_options(
+            "a[b][c][]=1&a[b][c][]=2&a[b][d][e][]=3&a[b][d][e][]=4",
+            &options,
+        );
+
+        assert_eq!(
+            parsed,
+            serde_json::json!({
+                "a": { "b": { "c": ["1", "2"], "d": { "[e]": ["3", "4"] } } }
+            })
+        );
+    }
+
+    #[test]
+    fn should_collapse_dotted_keys_beyond_depth_when_configured() {
+        let options = build_parse_options(|builder| {
+            builder
+                .allow_dots(true)
+                .max_depth(1)
+                .depth_overflow(DepthOverflow::CollapseRemainder)
+        });
+
+        let parsed = parse_with_options("a.b.c=x", &options);
+
+        assert_eq!(parsed, serde_json::json!({ "a": { "b": { "[c]": "x" } } }));
+    }
+
     #[test]
     fn should_prioritize_length_limit_when_length_and_params_conflict() {
         let options = build_parse_options(|builder| builder.max_params(5).max_length(5));
@@ -225,6 +292,877 @@ mod parse_limits_tests {
     }
 }
 
+mod array_limit_tests {
+    use super::*;
+    use bunner_qs_rs::{ArrayLimitBehavior, DuplicateKeyBehavior};
+    use serde_json::json;
+
+    #[test]
+    fn should_keep_sequence_when_indices_stay_within_array_limit() {
+        let options = build_parse_options(|builder| builder.array_limit(2));
+
+        let parsed = parse_with_options("a[0]=x&a[2]=z", &options);
+
+        assert_eq!(parsed, json!({ "a": ["x", "", "z"] }));
+    }
+
+    #[test]
+    fn should_reject_index_when_array_limit_is_exceeded() {
+        let options = build_parse_options(|builder| {
+            builder
+                .array_limit(20)
+                .array_limit_behavior(ArrayLimitBehavior::Reject)
+        });
+
+        let (segment, limit) = expect_array_index_too_large("a[99999999]=x", &options);
+
+        assert_eq!(segment, "99999999");
+        assert_eq!(limit, 20);
+    }
+
+    #[test]
+    fn should_reject_append_when_implicit_index_exceeds_array_limit() {
+        let options = build_parse_options(|builder| {
+            builder
+                .array_limit(1)
+                .array_limit_behavior(ArrayLimitBehavior::Reject)
+        });
+
+        let (segment, limit) = expect_array_index_too_large("a[]=x&a[]=y&a[]=z", &options);
+
+        assert_eq!(segment, "2");
+        assert_eq!(limit, 1);
+    }
+
+    #[test]
+    fn should_convert_to_object_when_index_exceeds_limit_and_behavior_converts() {
+        let options = build_parse_options(|builder| {
+            builder
+                .array_limit(20)
+                .array_limit_behavior(ArrayLimitBehavior::ConvertToObject)
+        });
+
+        let parsed = parse_with_options("a[99999999]=x", &options);
+
+        assert_eq!(parsed, json!({ "a": { "99999999": "x" } }));
+    }
+
+    #[test]
+    fn should_rekey_existing_items_when_sequence_is_converted_to_object() {
+        let options = build_parse_options(|builder| {
+            builder
+                .array_limit(20)
+                .array_limit_behavior(ArrayLimitBehavior::ConvertToObject)
+        });
+
+        let parsed = parse_with_options("a[0]=x&a[1]=y&a[50]=z", &options);
+
+        assert_eq!(parsed, json!({ "a": { "0": "x", "1": "y", "50": "z" } }));
+    }
+
+    #[test]
+    fn should_append_after_highest_index_when_converted_object_receives_brackets() {
+        let expected = json!({ "a": { "0": "x", "5": "y", "6": "z" } });
+        let converting = |behavior| {
+            build_parse_options(|builder| {
+                builder
+                    .array_limit(3)
+                    .array_limit_behavior(ArrayLimitBehavior::ConvertToObject)
+                    .duplicate_keys(behavior)
+            })
+        };
+
+        let rejecting = parse_with_options(
+            "a[0]=x&a[5]=y&a[]=z",
+            &converting(DuplicateKeyBehavior::Reject),
+        );
+        let overwriting = parse_with_options(
+            "a[0]=x&a[5]=y&a[]=z",
+            &converting(DuplicateKeyBehavior::LastWins),
+        );
+
+        assert_eq!(rejecting, expected);
+        assert_eq!(overwriting, expected);
+    }
+
+    #[test]
+    fn should_convert_huge_index_to_object_by_default() {
+        let parsed = parse_value("a[0]=x&a[99999999]=y");
+
+        assert_eq!(parsed, json!({ "a": { "0": "x", "99999999": "y" } }));
+    }
+
+    #[test]
+    fn should_convert_appended_values_when_implicit_index_exceeds_limit() {
+        let options = build_parse_options(|builder| {
+            builder
+                .array_limit(1)
+                .array_limit_behavior(ArrayLimitBehavior::ConvertToObject)
+        });
+
+        let parsed = parse_with_options("a[]=x&a[]=y&a[]=z", &options);
+
+        assert_eq!(parsed, json!({ "a": { "0": "x", "1": "y", "2": "z" } }));
+    }
+}
+
+mod comma_tests {
+    use super::*;
+    use bunner_qs_rs::DuplicateKeyBehavior;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[test]
+    fn should_keep_comma_in_string_when_comma_option_is_disabled() {
+        let parsed = parse_value("ids=1,2,3");
+
+        assert_eq!(parsed, json!({ "ids": "1,2,3" }));
+    }
+
+    #[test]
+    fn should_split_value_into_sequence_when_comma_option_is_enabled() {
+        let options = build_parse_options(|builder| builder.comma(true));
+
+        let parsed = parse_with_options("ids=1,2,3&name=kim", &options);
+
+        assert_eq!(parsed, json!({ "ids": ["1", "2", "3"], "name": "kim" }));
+    }
+
+    #[test]
+    fn should_keep_encoded_comma_literal_when_splitting_values() {
+        let options = build_parse_options(|builder| builder.comma(true));
+
+        let parsed = parse_with_options("tags=a%2Cb,c&note=x%2Cy", &options);
+
+        assert_eq!(parsed, json!({ "tags": ["a,b", "c"], "note": "x,y" }));
+    }
+
+    #[test]
+    fn should_keep_empty_items_when_value_has_trailing_comma() {
+        let options = build_parse_options(|builder| builder.comma(true));
+
+        let parsed = parse_with_options("ids=1,", &options);
+
+        assert_eq!(parsed, json!({ "ids": ["1", ""] }));
+    }
+
+    #[test]
+    fn should_split_nested_values_when_key_has_brackets() {
+        let options = build_parse_options(|builder| builder.comma(true));
+
+        let parsed = parse_with_options("filter[ids]=1,2&filter[kind]=a", &options);
+
+        assert_eq!(
+            parsed,
+            json!({ "filter": { "ids": ["1", "2"], "kind": "a" } })
+        );
+    }
+
+    #[test]
+    fn should_flatten_split_values_when_combining_duplicate_keys() {
+        let options = build_parse_options(|builder| {
+            builder
+                .comma(true)
+                .duplicate_keys(DuplicateKeyBehavior::Combine)
+        });
+
+        let parsed = parse_with_options("ids=1,2&ids=3", &options);
+
+        assert_eq!(parsed, json!({ "ids": ["1", "2", "3"] }));
+    }
+
+    #[test]
+    fn should_count_split_items_when_enforcing_max_params() {
+        let options = build_parse_options(|builder| builder.comma(true).max_params(3));
+
+        let (limit, actual) = expect_too_many_parameters("ids=1,2,3&name=kim", &options);
+
+        assert_eq!(limit, 3);
+        assert_eq!(actual, 4);
+    }
+
+    #[test]
+    fn should_deserialize_split_values_into_vec_when_target_is_typed() {
+        #[derive(Debug, Deserialize, Default, PartialEq)]
+        struct Filter {
+            ids: Vec<u32>,
+        }
+
+        let options = build_parse_options(|builder| builder.comma(true));
+
+        let parsed: Filter = parse_query("ids=1,2,3", &options).expect("comma values should parse");
+
+        assert_eq!(parsed, Filter { ids: vec![1, 2, 3] });
+    }
+}
+
+mod allow_dots_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn should_keep_dotted_key_literal_when_allow_dots_is_disabled() {
+        let parsed = parse_value("filter.status=open");
+
+        assert_eq!(parsed, json!({ "filter.status": "open" }));
+    }
+
+    #[test]
+    fn should_nest_dotted_keys_when_allow_dots_is_enabled() {
+        let options = build_parse_options(|builder| builder.allow_dots(true));
+
+        let parsed = parse_with_options("filter.status=open&filter.owner.id=7", &options);
+
+        assert_eq!(
+            parsed,
+            json!({ "filter": { "status": "open", "owner": { "id": "7" } } })
+        );
+    }
+
+    #[test]
+    fn should_keep_encoded_dot_literal_when_allow_dots_is_enabled() {
+        let options = build_parse_options(|builder| builder.allow_dots(true));
+
+        let parsed = parse_with_options("version.v1%2E0=stable&a%2Eb=c", &options);
+
+        assert_eq!(
+            parsed,
+            json!({ "version": { "v1.0": "stable" }, "a.b": "c" })
+        );
+    }
+
+    #[test]
+    fn should_combine_dots_and_brackets_when_both_appear_in_keys() {
+        let options = build_parse_options(|builder| builder.allow_dots(true));
+
+        let parsed = parse_with_options("users[0].name=kim&users[1].name=lee", &options);
+
+        assert_eq!(
+            parsed,
+            json!({ "users": [{ "name": "kim" }, { "name": "lee" }] })
+        );
+    }
+
+    #[test]
+    fn should_count_dot_segments_when_enforcing_max_depth() {
+        let options = build_parse_options(|builder| builder.allow_dots(true).max_depth(1));
+
+        let (key, limit, depth) = expect_depth_exceeded("a.b.c=1", &options);
+
+        assert_eq!(key, "a[b][c]");
+        assert_eq!(limit, 1);
+        assert_eq!(depth, 2);
+    }
+
+    #[test]
+    fn should_emit_dotted_keys_when_stringify_allow_dots_is_enabled() {
+        let value = json!({ "filter": { "status": "open", "tags": ["a", "b"] } });
+
+        let encoded = stringify_with_options_map(&value, |builder| builder.allow_dots(true));
+
+        assert_eq!(
+            encoded,
+            "filter.status=open&filter.tags%5B0%5D=a&filter.tags%5B1%5D=b"
+        );
+    }
+
+    #[test]
+    fn should_roundtrip_literal_dots_when_both_sides_allow_dots() {
+        let value = json!({ "a.b": "1", "filter": { "v1.0": "stable" } });
+        let options = build_parse_options(|builder| builder.allow_dots(true));
+
+        let encoded = stringify_with_options_map(&value, |builder| builder.allow_dots(true));
+        let reparsed = parse_with_options(&encoded, &options);
+
+        assert_eq!(encoded, "a%2Eb=1&filter.v1%2E0=stable");
+        assert_eq!(reparsed, value);
+    }
+}
+
+mod malformed_pairs_tests {
+    use super::*;
+    use bunner_qs_rs::{ArrayLimitBehavior, DuplicateKeyBehavior};
+    use serde_json::json;
+
+    #[test]
+    fn should_drop_malformed_pairs_when_behavior_is_skip() {
+        let options =
+            build_parse_options(|builder| builder.malformed_pairs(MalformedPairBehavior::Skip));
+
+        let parsed = parse_with_options("a=1&b=%GG&c[=2&d=4", &options);
+
+        assert_eq!(parsed, json!({ "a": "1", "d": "4" }));
+    }
+
+    #[test]
+    fn should_keep_raw_pairs_when_behavior_is_keep_raw() {
+        let options =
+            build_parse_options(|builder| builder.malformed_pairs(MalformedPairBehavior::KeepRaw));
+
+        let parsed = parse_with_options("a=1&b=%GG&c[=2&d=4", &options);
+
+        assert_eq!(parsed, json!({ "a": "1", "b": "%GG", "c[": "2", "d": "4" }));
+    }
+
+    #[test]
+    fn should_drop_conflicting_pattern_when_behavior_is_skip() {
+        let options =
+            build_parse_options(|builder| builder.malformed_pairs(MalformedPairBehavior::Skip));
+
+        let parsed = parse_with_options("tags[0]=x&tags[name]=y&tags[1]=z", &options);
+
+        assert_eq!(parsed, json!({ "tags": ["x", "z"] }));
+    }
+
+    #[test]
+    fn should_drop_only_offending_pair_when_skip_sees_disallowed_characters() {
+        let options =
+            build_parse_options(|builder| builder.malformed_pairs(MalformedPairBehavior::Skip));
+
+        let question = parse_with_options("a=1&b=?x&c=3", &options);
+        let space = parse_with_options("a=1&b c=2&d=4", &options);
+
+        assert_eq!(question, json!({ "a": "1", "c": "3" }));
+        assert_eq!(space, json!({ "a": "1", "d": "4" }));
+    }
+
+    #[test]
+    fn should_keep_raw_pair_when_keep_raw_sees_question_mark() {
+        let options =
+            build_parse_options(|builder| builder.malformed_pairs(MalformedPairBehavior::KeepRaw));
+
+        let parsed = parse_with_options("a=1&b=?x&c=3", &options);
+
+        assert_eq!(parsed, json!({ "a": "1", "b": "?x", "c": "3" }));
+    }
+
+    #[test]
+    fn should_roll_back_pattern_state_when_skipped_pair_fails_midway() {
+        let options = build_parse_options(|builder| {
+            builder
+                .malformed_pairs(MalformedPairBehavior::Skip)
+                .array_limit(5)
+                .array_limit_behavior(ArrayLimitBehavior::Reject)
+        });
+
+        let parsed = parse_with_options("a[][9]=1&a[]=z", &options);
+
+        assert_eq!(parsed, json!({ "a": ["z"] }));
+    }
+
+    #[test]
+    fn should_roll_back_rewritten_containers_when_skipped_pair_fails_midway() {
+        let options = build_parse_options(|builder| {
+            builder
+                .malformed_pairs(MalformedPairBehavior::Skip)
+                .duplicate_keys(DuplicateKeyBehavior::Combine)
+        });
+
+        let parsed = parse_with_options("tag=a&tag[0][x]=1", &options);
+
+        assert_eq!(parsed, json!({ "tag": "a" }));
+    }
+
+    #[test]
+    fn should_still_reject_parameter_limit_when_behavior_is_skip() {
+        let options = build_parse_options(|builder| {
+            builder
+                .malformed_pairs(MalformedPairBehavior::Skip)
+                .max_params(1)
+        });
+
+        let error = parse_query::<Value>("a=1&b=2", &options).expect_err("limit should stay fatal");
+
+        assert!(matches!(
+            error,
+            QsParseError::Parse(ParseError::TooManyParameters {
+                limit: 1,
+                actual: 2
+            })
+        ));
+    }
+}
+
+mod delimiter_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn should_parse_semicolon_pairs_when_pair_delimiters_include_semicolon() {
+        let options = build_parse_options(|builder| builder.pair_delimiters("&;"));
+
+        let parsed = parse_with_options("a=1;b[0]=x&b[1]=y", &options);
+
+        assert_eq!(parsed, json!({ "a": "1", "b": ["x", "y"] }));
+    }
+
+    #[test]
+    fn should_roundtrip_custom_delimiters_when_both_sides_match() {
+        let value = json!({ "a": "x;y", "b": { "c": "1=2" } });
+        let options =
+            build_parse_options(|builder| builder.pair_delimiters(";").key_value_separator(':'));
+
+        let encoded = stringify_with_options_map(&value, |builder| {
+            builder.pair_delimiter(';').key_value_separator(':')
+        });
+        let reparsed = parse_with_options(&encoded, &options);
+
+        assert_eq!(encoded, "a:x%3By;b%5Bc%5D:1%3D2");
+        assert_eq!(reparsed, value);
+    }
+
+    #[test]
+    fn should_reject_unsupported_delimiter_when_building_parse_options() {
+        let error = api_build_parse_options(|builder| builder.pair_delimiters("&."))
+            .expect_err("dot delimiter should be rejected");
+
+        assert_eq!(
+            error,
+            OptionsValidationError::InvalidDelimiter {
+                field: "pair_delimiters",
+                character: '.'
+            }
+        );
+    }
+}
+
+mod strict_null_tests {
+    use super::*;
+    use bunner_qs_rs::{BoolLiterals, Qs};
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+    struct Flags {
+        debug: bool,
+        note: Option<String>,
+        page: Option<u32>,
+    }
+
+    #[test]
+    fn should_parse_bare_key_as_null_when_strict_null_handling_is_enabled() {
+        let options = build_parse_options(|builder| builder.strict_null_handling(true));
+
+        let parsed = parse_with_options("debug&empty=", &options);
+
+        assert_eq!(parsed, json!({ "debug": null, "empty": "" }));
+    }
+
+    #[test]
+    fn should_deserialize_bare_keys_into_true_and_none_when_strict_null_handling_is_enabled() {
+        let qs = Qs::new()
+            .with_parse(
+                ParseOptions::new()
+                    .strict_null_handling(true)
+                    .bool_literals(BoolLiterals::new().bare_key(true)),
+            )
+            .expect(BUILD_OK);
+
+        let flags: Flags = qs.parse("debug&note&page=3").expect("parse should succeed");
+
+        assert_eq!(
+            flags,
+            Flags {
+                debug: true,
+                note: None,
+                page: Some(3),
+            }
+        );
+    }
+
+    #[test]
+    fn should_reject_bare_bool_key_when_bare_key_literal_is_not_configured() {
+        let qs = Qs::new()
+            .with_parse(ParseOptions::new().strict_null_handling(true))
+            .expect(BUILD_OK);
+
+        let result: Result<Flags, _> = qs.parse("debug&page=3");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_stringify_none_as_bare_key_when_strict_null_handling_is_enabled() {
+        let qs = Qs::new()
+            .with_stringify(StringifyOptions::new().strict_null_handling(true))
+            .expect(STRINGIFY_BUILD_OK);
+        let flags = Flags {
+            debug: true,
+            note: None,
+            page: None,
+        };
+
+        let encoded = qs.stringify(&flags).expect("stringify should succeed");
+
+        assert_eq!(encoded, "debug=true&note&page");
+    }
+}
+
+mod empty_value_tests {
+    use super::*;
+    use bunner_qs_rs::Qs;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+    struct SearchForm {
+        q: String,
+        page: Option<u32>,
+        slots: Vec<Option<u8>>,
+    }
+
+    #[test]
+    fn should_deserialize_empty_inputs_as_none_when_empty_as_none_is_enabled() {
+        let qs = Qs::new()
+            .with_parse(ParseOptions::new().empty_as_none(true))
+            .expect(BUILD_OK);
+
+        let form: SearchForm = qs
+            .parse("q=rust&page=&slots[2]=7")
+            .expect("empty inputs should parse");
+
+        assert_eq!(
+            form,
+            SearchForm {
+                q: "rust".into(),
+                page: None,
+                slots: vec![None, None, Some(7)],
+            }
+        );
+    }
+
+    #[test]
+    fn should_reject_empty_number_when_empty_as_none_is_disabled() {
+        let qs = Qs::new().with_parse(ParseOptions::new()).expect(BUILD_OK);
+
+        let result = qs.parse::<SearchForm>("q=rust&page=");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_omit_empty_fields_when_skip_empty_is_enabled() {
+        let qs = Qs::new()
+            .with_stringify(StringifyOptions::new().skip_empty(true))
+            .expect(STRINGIFY_BUILD_OK);
+        let form = SearchForm {
+            q: String::new(),
+            page: Some(1),
+            slots: Vec::new(),
+        };
+
+        let encoded = qs.stringify(&form).expect("stringify should succeed");
+
+        assert_eq!(encoded, "page=1");
+    }
+}
+
+mod bool_literal_tests {
+    use super::*;
+    use bunner_qs_rs::{BoolFormat, BoolLiterals, Qs};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+    struct Preferences {
+        newsletter: bool,
+        tracking: bool,
+    }
+
+    #[test]
+    fn should_accept_checkbox_and_numeric_literals_when_configured() {
+        let options = build_parse_options(|builder| {
+            builder.bool_literals(
+                BoolLiterals::new()
+                    .format(BoolFormat::OnOff)
+                    .truthy("1")
+                    .falsy("0")
+                    .truthy("yes")
+                    .falsy("no")
+                    .case_insensitive(true),
+            )
+        });
+
+        let checkbox: Preferences =
+            parse_query("newsletter=on&tracking=0", &options).expect("checkbox should parse");
+        let partner: Preferences =
+            parse_query("newsletter=No&tracking=YES", &options).expect("partner should parse");
+
+        assert_eq!((checkbox.newsletter, checkbox.tracking), (true, false));
+        assert_eq!((partner.newsletter, partner.tracking), (false, true));
+    }
+
+    #[test]
+    fn should_reject_default_literals_when_format_replaces_them() {
+        let options = build_parse_options(|builder| {
+            builder.bool_literals(BoolLiterals::new().format(BoolFormat::OneZero))
+        });
+
+        let result = parse_query::<Preferences>("newsletter=true&tracking=0", &options);
+
+        assert!(result.is_err(), "true should not parse after OneZero");
+    }
+
+    #[test]
+    fn should_round_trip_when_bool_format_matches_bool_literals() {
+        let qs = Qs::new()
+            .with_parse(
+                ParseOptions::new().bool_literals(BoolLiterals::new().format(BoolFormat::OneZero)),
+            )
+            .expect(BUILD_OK)
+            .with_stringify(StringifyOptions::new().bool_format(BoolFormat::OneZero))
+            .expect(STRINGIFY_BUILD_OK);
+        let preferences = Preferences {
+            newsletter: true,
+            tracking: false,
+        };
+
+        let encoded = qs
+            .stringify(&preferences)
+            .expect("stringify should succeed");
+        let decoded: Preferences = qs.parse(&encoded).expect("parse should succeed");
+
+        assert_eq!(encoded, "newsletter=1&tracking=0");
+        assert_eq!(decoded, preferences);
+    }
+
+    #[test]
+    fn should_reject_ambiguous_literals_when_building_parse_options() {
+        let result = api_build_parse_options(|builder| {
+            builder.bool_literals(BoolLiterals::new().truthy("1").falsy("1"))
+        });
+
+        assert!(matches!(
+            result,
+            Err(OptionsValidationError::AmbiguousBoolLiteral { literal }) if literal == "1"
+        ));
+    }
+}
+
+mod charset_tests {
+    use super::*;
+    use bunner_qs_rs::{Charset, Qs};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+    struct Signup {
+        name: String,
+        city: String,
+    }
+
+    #[test]
+    fn should_decode_latin1_bytes_when_charset_is_iso_8859_1() {
+        let options = build_parse_options(|builder| builder.charset(Charset::Iso88591));
+
+        let parsed = parse_with_options("name=Jos%E9&city=K%F6ln", &options);
+
+        assert_str_path(&parsed, &["name"], "Jos\u{e9}");
+        assert_str_path(&parsed, &["city"], "K\u{f6}ln");
+    }
+
+    #[test]
+    fn should_replace_invalid_sequences_when_charset_is_lossy_utf8() {
+        let options = build_parse_options(|builder| builder.charset(Charset::Utf8Lossy));
+
+        let parsed = parse_with_options("name=Jos%E9", &options);
+
+        assert_str_path(&parsed, &["name"], "Jos\u{fffd}");
+    }
+
+    #[test]
+    fn should_reject_latin1_bytes_when_charset_is_default() {
+        let result = parse_default::<Value>("name=Jos%E9");
+
+        assert!(matches!(
+            result,
+            Err(QsParseError::Parse(ParseError::InvalidUtf8 { .. }))
+        ));
+    }
+
+    #[test]
+    fn should_follow_sentinel_per_request_when_charset_sentinel_is_enabled() {
+        let options = build_parse_options(|builder| builder.charset_sentinel(true));
+
+        let legacy = parse_with_options("utf8=%26%2310003%3B&name=Jos%E9", &options);
+        let modern = parse_with_options("utf8=%E2%9C%93&name=Jos%C3%A9", &options);
+
+        assert_eq!(legacy, serde_json::json!({ "name": "Jos\u{e9}" }));
+        assert_eq!(modern, serde_json::json!({ "name": "Jos\u{e9}" }));
+    }
+
+    #[test]
+    fn should_round_trip_latin1_when_both_sides_use_iso_8859_1() {
+        let qs = Qs::new()
+            .with_parse(ParseOptions::new().charset(Charset::Iso88591))
+            .expect(BUILD_OK)
+            .with_stringify(StringifyOptions::new().charset(Charset::Iso88591))
+            .expect(STRINGIFY_BUILD_OK);
+        let signup = Signup {
+            name: "Jos\u{e9}".into(),
+            city: "K\u{f6}ln".into(),
+        };
+
+        let encoded = qs.stringify(&signup).expect("stringify should succeed");
+        let decoded: Signup = qs.parse(&encoded).expect("parse should succeed");
+
+        assert_eq!(encoded, "name=Jos%E9&city=K%F6ln");
+        assert_eq!(decoded, signup);
+    }
+}
+
+mod sparse_array_tests {
+    use super::*;
+    use bunner_qs_rs::{Qs, SparseArrayBehavior};
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    struct Slots {
+        a: Vec<Option<String>>,
+    }
+
+    #[test]
+    fn should_fill_holes_with_empty_strings_by_default() {
+        let parsed = parse_value("a[0]=&a[2]=x");
+
+        assert_eq!(parsed, serde_json::json!({ "a": ["", "", "x"] }));
+    }
+
+    #[test]
+    fn should_emit_null_for_holes_when_keeping_holes() {
+        let options =
+            build_parse_options(|builder| builder.sparse_arrays(SparseArrayBehavior::KeepHoles));
+
+        let parsed = parse_with_options("a[0]=&a[2]=x", &options);
+
+        assert_eq!(parsed, serde_json::json!({ "a": ["", null, "x"] }));
+    }
+
+    #[test]
+    fn should_deserialize_holes_as_none_when_keeping_holes() {
+        let qs = Qs::new()
+            .with_parse(ParseOptions::new().sparse_arrays(SparseArrayBehavior::KeepHoles))
+            .expect(BUILD_OK);
+
+        let slots: Slots = qs.parse("a[0]=&a[2]=x").expect("sparse slots should parse");
+
+        assert_eq!(slots.a, vec![Some(String::new()), None, Some("x".into())]);
+    }
+
+    #[test]
+    fn should_deserialize_numbers_without_gaps_when_compacting() {
+        #[derive(Debug, Default, Deserialize)]
+        struct Ids {
+            ids: Vec<u32>,
+        }
+
+        let qs = Qs::new()
+            .with_parse(ParseOptions::new().sparse_arrays(SparseArrayBehavior::Compact))
+            .expect(BUILD_OK);
+
+        let parsed: Ids = qs
+            .parse("ids[0]=1&ids[2]=3")
+            .expect("compacted indices should parse");
+
+        assert_eq!(parsed.ids, [1, 3]);
+    }
+
+    #[test]
+    fn should_reject_duplicate_of_explicit_empty_value_when_keeping_holes() {
+        let options =
+            build_parse_options(|builder| builder.sparse_arrays(SparseArrayBehavior::KeepHoles));
+
+        let result = parse_query::<Value>("a[0]=&a[0]=y", &options);
+
+        assert!(matches!(
+            result,
+            Err(QsParseError::Parse(ParseError::DuplicateSequenceIndex {
+                index: 0,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn should_compact_sparse_indices_when_compacting() {
+        let options =
+            build_parse_options(|builder| builder.sparse_arrays(SparseArrayBehavior::Compact));
+
+        let parsed = parse_with_options("a[1]=x&a[5]=y&b[3][name]=z", &options);
+
+        assert_eq!(
+            parsed,
+            serde_json::json!({ "a": ["x", "y"], "b": [{ "name": "z" }] })
+        );
+    }
+}
+
+mod mixed_container_tests {
+    use super::*;
+    use bunner_qs_rs::{DuplicateKeyBehavior, MixedContainerPolicy};
+
+    fn promoting() -> ParseOptions {
+        build_parse_options(|builder| {
+            builder.mixed_containers(MixedContainerPolicy::PromoteToObject)
+        })
+    }
+
+    #[test]
+    fn should_reject_mixed_children_by_default() {
+        let result = parse_query::<Value>("a[0]=x&a[name]=y", &ParseOptions::default());
+
+        assert!(matches!(
+            result,
+            Err(QsParseError::Parse(ParseError::KeyPatternConflict { .. }))
+        ));
+    }
+
+    #[test]
+    fn should_promote_sequence_to_object_when_named_child_follows_index() {
+        let parsed = parse_with_options("a[0]=x&a[name]=y", &promoting());
+
+        assert_eq!(
+            parsed,
+            serde_json::json!({ "a": { "0": "x", "name": "y" } })
+        );
+    }
+
+    #[test]
+    fn should_keep_object_when_index_follows_named_child() {
+        let parsed = parse_with_options("a[name]=y&a[0]=x", &promoting());
+
+        assert_eq!(
+            parsed,
+            serde_json::json!({ "a": { "name": "y", "0": "x" } })
+        );
+    }
+
+    #[test]
+    fn should_append_after_explicit_indices_when_promoted_container_receives_brackets() {
+        let expected = serde_json::json!({ "a": { "0": "x", "name": "y", "1": "z" } });
+        let last_wins = build_parse_options(|builder| {
+            builder
+                .mixed_containers(MixedContainerPolicy::PromoteToObject)
+                .duplicate_keys(DuplicateKeyBehavior::LastWins)
+        });
+
+        let rejecting = parse_with_options("a[0]=x&a[name]=y&a[]=z", &promoting());
+        let overwriting = parse_with_options("a[0]=x&a[name]=y&a[]=z", &last_wins);
+
+        assert_eq!(rejecting, expected);
+        assert_eq!(overwriting, expected);
+    }
+
+    #[test]
+    fn should_promote_nested_sequence_when_mixing_under_deeper_parent() {
+        let parsed = parse_with_options("a[b][0][id]=1&a[b][1][id]=2&a[b][tag]=t", &promoting());
+
+        assert_eq!(
+            parsed,
+            serde_json::json!({
+                "a": { "b": { "0": { "id": "1" }, "1": { "id": "2" }, "tag": "t" } }
+            })
+        );
+    }
+}
+
 mod parse_builder_tests {
     use super::*;
 
@@ -327,6 +327,165 @@ mod option_behavior_tests {
         assert_str_path(&parsed, &["color"], "blue");
         assert_str_path(&parsed, &["user", "name"], "Bob");
     }
+
+    #[test]
+    fn should_collect_repeated_values_when_duplicate_keys_use_combine_then_build_arrays() {
+        let options =
+            build_parse_options(|builder| builder.duplicate_keys(DuplicateKeyBehavior::Combine));
+        let query = "color=red&color=blue&user[name]=Alice&user[name]=Bob";
+
+        let parsed = parse_with_options(query, &options);
+
+        assert_string_array_path(&parsed, &["color"], &["red", "blue"]);
+        assert_string_array_path(&parsed, &["user", "name"], &["Alice", "Bob"]);
+    }
+
+    #[test]
+    fn should_match_bracket_arrays_when_duplicate_keys_use_combine_then_treat_repeats_like_append()
+    {
+        let options =
+            build_parse_options(|builder| builder.duplicate_keys(DuplicateKeyBehavior::Combine));
+
+        let repeated = parse_with_options("tag=a&tag=b", &options);
+        let bracketed = parse_with_options("tag[]=a&tag[]=b", &options);
+
+        assert_eq!(repeated, bracketed);
+        assert_eq!(repeated, json!({ "tag": ["a", "b"] }));
+    }
+
+    #[test]
+    fn should_append_flat_values_when_combine_mixes_flat_and_bracket_keys_then_keep_one_sequence() {
+        let options =
+            build_parse_options(|builder| builder.duplicate_keys(DuplicateKeyBehavior::Combine));
+
+        let flat_first = parse_with_options("tag=a&tag=b&tag[]=c", &options);
+        let single_flat = parse_with_options("tag=a&tag[]=b", &options);
+        let bracket_first = parse_with_options("tag[]=a&tag=b", &options);
+
+        assert_eq!(flat_first, json!({ "tag": ["a", "b", "c"] }));
+        assert_eq!(single_flat, json!({ "tag": ["a", "b"] }));
+        assert_eq!(bracket_first, json!({ "tag": ["a", "b"] }));
+    }
+
+    #[test]
+    fn should_combine_at_addressed_slot_when_combine_repeats_explicit_index_then_keep_positions() {
+        let options =
+            build_parse_options(|builder| builder.duplicate_keys(DuplicateKeyBehavior::Combine));
+
+        let repeated = parse_with_options("a[0]=1&a[0]=2", &options);
+        let interleaved = parse_with_options("a[0]=1&a[1]=x&a[0]=2", &options);
+
+        assert_eq!(repeated, json!({ "a": [["1", "2"]] }));
+        assert_eq!(interleaved, json!({ "a": [["1", "2"], "x"] }));
+    }
+
+    #[test]
+    fn should_deserialize_into_vec_when_duplicate_keys_use_combine_then_collect_form_values() {
+        #[derive(Debug, serde::Deserialize, Default)]
+        struct Filters {
+            tag: Vec<String>,
+        }
+
+        let options =
+            build_parse_options(|builder| builder.duplicate_keys(DuplicateKeyBehavior::Combine));
+
+        let filters: Filters =
+            parse_query("tag=a&tag=b&tag=c", &options).expect("combine should produce a sequence");
+
+        assert_eq!(filters.tag, ["a", "b", "c"]);
+    }
+}
+
+mod lenient_key_syntax_tests {
+    use super::*;
+    use bunner_qs_rs::KeySyntax;
+
+    fn lenient() -> ParseOptions {
+        build_parse_options(|builder| builder.key_syntax(KeySyntax::Lenient))
+    }
+
+    #[test]
+    fn should_keep_malformed_brackets_as_literal_root_keys_when_lenient() {
+        let parsed = parse_with_options("a[b=1&c]d=2&arr[[0]=3", &lenient());
+
+        assert_eq!(parsed, json!({ "a[b": "1", "c]d": "2", "arr[[0]": "3" }));
+    }
+
+    #[test]
+    fn should_keep_key_with_trailing_text_after_bracket_as_literal_when_lenient() {
+        let parsed = parse_with_options("a[b]c=1&a[b]=2", &lenient());
+
+        assert_eq!(parsed, json!({ "a[b]c": "1", "a": { "b": "2" } }));
+    }
+
+    #[test]
+    fn should_nest_well_formed_keys_when_lenient() {
+        let parsed = parse_with_options("user[name]=kim&tags[]=a&tags[]=b", &lenient());
+
+        assert_eq!(
+            parsed,
+            json!({ "user": { "name": "kim" }, "tags": ["a", "b"] })
+        );
+    }
+
+    #[test]
+    fn should_still_enforce_depth_on_well_formed_keys_when_lenient() {
+        let options =
+            build_parse_options(|builder| builder.key_syntax(KeySyntax::Lenient).max_depth(1));
+
+        let result = parse_query::<Value>("a[b][c]=1", &options);
+
+        assert!(matches!(
+            result,
+            Err(QsParseError::Parse(
+                bunner_qs_rs::parsing::ParseError::DepthExceeded { .. }
+            ))
+        ));
+    }
+}
+
+mod decoder_hook_tests {
+    use super::*;
+    use bunner_qs_rs::parsing::ParseError;
+    use bunner_qs_rs::{ComponentKind, DecodeContext, Decoder};
+    use std::borrow::Cow;
+
+    #[derive(Debug)]
+    struct TrimValues;
+
+    impl Decoder for TrimValues {
+        fn decode<'a>(
+            &self,
+            component: &'a str,
+            context: &mut DecodeContext<'_>,
+        ) -> Result<Cow<'a, str>, ParseError> {
+            let decoded = context.decode_default(component)?;
+            if context.kind() == ComponentKind::Key || context.key_path() == "raw" {
+                return Ok(decoded);
+            }
+            Ok(Cow::Owned(decoded.trim().to_string()))
+        }
+    }
+
+    #[test]
+    fn should_transform_decoded_values_when_decoder_hook_installed() {
+        let options = build_parse_options(|builder| builder.decoder(TrimValues));
+
+        let parsed =
+            parse_with_options("user[name]=%20kim%20&raw=%20keep%20&tags[]=%20a", &options);
+
+        assert_eq!(
+            parsed,
+            json!({ "user": { "name": "kim" }, "raw": " keep ", "tags": ["a"] })
+        );
+    }
+
+    #[test]
+    fn should_use_builtin_decoding_when_no_hook_installed() {
+        let parsed = parse_value("user[name]=%20kim%20");
+
+        assert_eq!(parsed, json!({ "user": { "name": " kim " } }));
+    }
 }
 
 mod parse_error_by_variant_tests {
@@ -428,7 +587,11 @@ mod parse_error_by_variant_tests {
      {
         let invalid_segment = "18446744073709551616"; // u64::MAX + 1
         let query = format!("items[{invalid_segment}]=value");
-        let error = expect_error(&query);
+        let options = ParseOptions {
+            array_limit: None,
+            ..ParseOptions::new()
+        };
+        let error = expect_error_with_options(&query, &options);
 
         match error {
             ParseError::InvalidSequenceIndex { parent, segment } => {
@@ -1,7 +1,7 @@
 use bunner_qs_rs::{
     OptionsValidationError, ParseOptions, Qs, QsParseError, QsStringifyError, StringifyOptions,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 mod with_parse {
     use super::*;
@@ -74,6 +74,81 @@ mod parse {
     }
 }
 
+mod parse_detailed {
+    use super::*;
+    use bunner_qs_rs::UnknownFieldPolicy;
+
+    #[derive(Debug, Deserialize, Default, PartialEq)]
+    struct Search {
+        q: String,
+        page: u32,
+    }
+
+    fn qs_with_policy(policy: UnknownFieldPolicy) -> Qs {
+        Qs::new()
+            .with_parse(ParseOptions::new().unknown_fields(policy))
+            .expect("parse options configuration should succeed")
+    }
+
+    #[test]
+    fn given_missing_parse_options_when_parse_detailed_called_then_return_missing_options_error() {
+        let qs = Qs::new();
+        let result = qs.parse_detailed::<Search>("q=rust");
+
+        assert!(matches!(result, Err(QsParseError::MissingParseOptions)));
+    }
+
+    #[test]
+    fn given_default_policy_when_tracking_parameter_present_then_reject_unknown_field() {
+        let qs = qs_with_policy(UnknownFieldPolicy::Reject);
+        let result = qs.parse::<Search>("q=rust&page=2&utm_source=mail");
+
+        assert!(matches!(result, Err(QsParseError::Parse(_))));
+    }
+
+    #[test]
+    fn given_ignore_policy_when_tracking_parameters_present_then_parse_known_fields() {
+        let qs = qs_with_policy(UnknownFieldPolicy::Ignore);
+        let outcome = qs
+            .parse_detailed::<Search>("q=rust&_=1712&page=2&utm_source=mail")
+            .expect("unknown fields should be ignored");
+
+        assert_eq!(
+            outcome.value,
+            Search {
+                q: "rust".into(),
+                page: 2
+            }
+        );
+        assert!(outcome.unknown_fields.is_empty());
+    }
+
+    #[test]
+    fn given_collect_policy_when_tracking_parameters_present_then_return_their_paths() {
+        let qs = qs_with_policy(UnknownFieldPolicy::Collect);
+        let outcome = qs
+            .parse_detailed::<Search>("q=rust&_=1712&page=2&utm[source]=mail")
+            .expect("unknown fields should be collected");
+
+        assert_eq!(outcome.value.page, 2);
+        assert_eq!(
+            outcome.unknown_fields,
+            vec!["_".to_string(), "utm".to_string()]
+        );
+    }
+
+    #[test]
+    fn given_collect_policy_when_parsing_into_json_value_then_report_no_unknown_fields() {
+        let qs = qs_with_policy(UnknownFieldPolicy::Collect);
+        let outcome = qs
+            .parse_detailed::<serde_json::Value>("anything=goes")
+            .expect("json values accept every key");
+
+        assert_eq!(outcome.value["anything"], "goes");
+        assert!(outcome.unknown_fields.is_empty());
+    }
+}
+
 mod stringify {
     use super::*;
 
@@ -531,9 +531,209 @@ mod enum_roundtrip_tests {
         value: UntaggedValue,
     }
 
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone, Copy)]
+    #[serde(rename_all = "lowercase")]
+    enum Order {
+        #[default]
+        Asc,
+        Desc,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+    struct SortQuery {
+        sort: Order,
+        fallback: Option<Order>,
+        history: Vec<Order>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    enum Filter {
+        Exact(String),
+        Between(u32, u32),
+        Range { min: u32, max: u32 },
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+    struct FilterQuery {
+        filter: Option<Filter>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[serde(tag = "type")]
+    enum Shape {
+        Circle { radius: String },
+        Label { text: String },
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct ShapeEnvelope {
+        shape: Shape,
+    }
+
+    impl Default for ShapeEnvelope {
+        fn default() -> Self {
+            Self {
+                shape: Shape::Label {
+                    text: String::new(),
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn should_preserve_unit_variants_when_enum_fields_roundtrip() -> Result<(), Box<dyn Error>> {
+        let query = SortQuery {
+            sort: Order::Desc,
+            fallback: Some(Order::Asc),
+            history: vec![Order::Asc, Order::Desc],
+        };
+
+        let encoded = stringify(&query)?;
+        assert_encoded_contains(&encoded, &["sort=desc", "fallback=asc"]);
+        let reparsed: SortQuery = parse(&encoded)?;
+
+        assert_eq!(reparsed, query);
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_unit_variant_when_plain_string_is_given() -> Result<(), Box<dyn Error>> {
+        #[derive(Debug, Deserialize, PartialEq, Default)]
+        struct Sort {
+            sort: Order,
+        }
+
+        let parsed: Sort = parse("sort=asc")?;
+
+        assert_eq!(parsed.sort, Order::Asc);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unknown_unit_variant_when_value_is_not_listed() {
+        asserts::assert_err_matches!(
+            parse::<SortQuery>("sort=sideways&history[]=asc"),
+            ParseError::Serde(_) => |message| {
+                assert!(
+                    message.contains("unknown variant `sideways`"),
+                    "unexpected variant error: {message}"
+                );
+            }
+        );
+    }
+
+    #[test]
+    fn should_parse_struct_variant_when_single_entry_map_names_variant()
+    -> Result<(), Box<dyn Error>> {
+        let parsed: FilterQuery = parse("filter[Range][min]=1&filter[Range][max]=5")?;
+
+        assert_eq!(parsed.filter, Some(Filter::Range { min: 1, max: 5 }));
+        Ok(())
+    }
+
     #[test]
-    fn should_show_deserialize_error_when_internally_tagged_enum_stringified()
+    fn should_parse_newtype_variant_when_single_entry_map_holds_scalar()
     -> Result<(), Box<dyn Error>> {
+        let parsed: FilterQuery = parse("filter[Exact]=rust")?;
+
+        assert_eq!(parsed.filter, Some(Filter::Exact("rust".into())));
+        Ok(())
+    }
+
+    #[test]
+    fn should_preserve_newtype_variant_when_filter_roundtrips() -> Result<(), Box<dyn Error>> {
+        let query = FilterQuery {
+            filter: Some(Filter::Exact("rust".into())),
+        };
+
+        let encoded = stringify(&query)?;
+        assert_eq!(encoded, "filter%5BExact%5D=rust");
+        let reparsed: FilterQuery = parse(&encoded)?;
+
+        assert_eq!(reparsed, query);
+        Ok(())
+    }
+
+    #[test]
+    fn should_preserve_tuple_variant_when_filter_roundtrips() -> Result<(), Box<dyn Error>> {
+        let query = FilterQuery {
+            filter: Some(Filter::Between(3, 7)),
+        };
+
+        let encoded = stringify(&query)?;
+        assert_encoded_contains(
+            &encoded,
+            &[
+                "filter%5BBetween%5D%5B0%5D=3",
+                "filter%5BBetween%5D%5B1%5D=7",
+            ],
+        );
+        let reparsed: FilterQuery = parse(&encoded)?;
+
+        assert_eq!(reparsed, query);
+        Ok(())
+    }
+
+    #[test]
+    fn should_preserve_struct_variant_when_filter_roundtrips() -> Result<(), Box<dyn Error>> {
+        let query = FilterQuery {
+            filter: Some(Filter::Range { min: 1, max: 5 }),
+        };
+
+        let encoded = stringify(&query)?;
+        assert_encoded_contains(
+            &encoded,
+            &[
+                "filter%5BRange%5D%5Bmin%5D=1",
+                "filter%5BRange%5D%5Bmax%5D=5",
+            ],
+        );
+        let reparsed: FilterQuery = parse(&encoded)?;
+
+        assert_eq!(reparsed, query);
+        Ok(())
+    }
+
+    #[test]
+    fn should_preserve_variants_when_sequence_of_enums_roundtrips() -> Result<(), Box<dyn Error>> {
+        #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+        struct FilterList {
+            filters: Vec<Filter>,
+        }
+
+        let list = FilterList {
+            filters: vec![
+                Filter::Exact("a".into()),
+                Filter::Between(1, 2),
+                Filter::Range { min: 0, max: 9 },
+            ],
+        };
+
+        let encoded = stringify(&list)?;
+        let reparsed: FilterList = parse(&encoded)?;
+
+        assert_eq!(reparsed, list);
+        Ok(())
+    }
+
+    #[test]
+    fn should_preserve_variant_when_internally_tagged_enum_roundtrips() -> Result<(), Box<dyn Error>>
+    {
+        let envelope = ShapeEnvelope {
+            shape: Shape::Circle { radius: "4".into() },
+        };
+
+        let encoded = stringify(&envelope)?;
+        assert_encoded_contains(&encoded, &["shape%5Btype%5D=Circle", "shape%5Bradius%5D=4"]);
+        let reparsed: ShapeEnvelope = parse(&encoded)?;
+
+        assert_eq!(reparsed, envelope);
+        Ok(())
+    }
+
+    #[test]
+    fn should_preserve_variant_when_adjacently_tagged_enum_roundtrips() -> Result<(), Box<dyn Error>>
+    {
         let envelope = InternalEnvelope {
             message: InternalMessage::Text {
                 text: "pong".into(),
@@ -550,13 +750,9 @@ mod enum_roundtrip_tests {
                 "priority=9",
             ],
         );
+        let reparsed: InternalEnvelope = parse(&encoded)?;
 
-        asserts::assert_err_matches!(
-            parse::<InternalEnvelope>(&encoded),
-            ParseError::Serde(_) => |message| {
-                assert!(message.contains("enum"), "expected enum error: {message}");
-            }
-        );
+        assert_eq!(reparsed, envelope);
         Ok(())
     }
 
@@ -587,6 +783,50 @@ mod enum_roundtrip_tests {
     }
 }
 
+mod query_map_roundtrip_tests {
+    use super::*;
+    use bunner_qs_rs::parsing::parse_to_query_map;
+    use bunner_qs_rs::stringify::stringify_query_map;
+    use bunner_qs_rs::{QueryMap, Value as QueryValue};
+
+    #[test]
+    fn should_match_parse_to_query_map_when_query_map_target_is_parsed()
+    -> Result<(), Box<dyn Error>> {
+        let query = "profile%5Bname%5D=Ada&profile%5Btags%5D%5B0%5D=math&page=2";
+
+        let via_generic: QueryMap = parse(query)?;
+        let via_entry_point = parse_to_query_map(query, &ParseOptions::default())?;
+
+        assert_eq!(via_generic, via_entry_point);
+        assert_eq!(via_generic.get("page"), Some(&QueryValue::from("2")));
+        Ok(())
+    }
+
+    #[test]
+    fn should_restore_identical_map_when_query_map_roundtrips() -> Result<(), Box<dyn Error>> {
+        let query = "a=1&b%5Bc%5D=2&b%5Bd%5D%5B0%5D=x&b%5Bd%5D%5B1%5D=y";
+        let map = parse_to_query_map(query, &ParseOptions::default())?;
+
+        let encoded = stringify_query_map(&map, &StringifyOptions::default())?;
+        let via_serde = stringify(&map)?;
+
+        assert_eq!(encoded, query);
+        assert_eq!(via_serde, query);
+        Ok(())
+    }
+
+    #[test]
+    fn should_convert_into_struct_when_query_map_is_bridged() -> Result<(), Box<dyn Error>> {
+        let map = parse_to_query_map("username=ada&age=36&active=true", &ParseOptions::default())?;
+
+        let user: SimpleUser = map.to_struct()?;
+        let back = QueryMap::from_struct(&user)?;
+
+        assert_eq!(back, map);
+        Ok(())
+    }
+}
+
 mod options_behavior_tests {
     use super::*;
 
@@ -805,14 +1045,144 @@ mod adapter_behavior_tests {
     }
 }
 
-mod stringify_error_tests {
+mod sequence_coercion_tests {
+    use super::*;
+    use bunner_qs_rs::ArrayLimitBehavior;
+    use std::collections::HashSet;
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    struct Filter {
+        ids: Vec<u32>,
+        tags: HashSet<String>,
+        #[serde(default)]
+        range: Option<(u8,)>,
+    }
+
+    #[test]
+    fn should_accept_lone_scalars_when_sequence_fields_receive_single_values()
+    -> Result<(), Box<dyn Error>> {
+        let parsed: Filter = parse("ids=5&tags=red&range=3")?;
+
+        assert_eq!(
+            parsed,
+            Filter {
+                ids: vec![5],
+                tags: HashSet::from(["red".to_string()]),
+                range: Some((3,)),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_order_integer_keyed_objects_when_array_limit_converts_sequence_to_object()
+    -> Result<(), Box<dyn Error>> {
+        let options = build_parse_options(|builder| {
+            builder
+                .array_limit(2)
+                .array_limit_behavior(ArrayLimitBehavior::ConvertToObject)
+        });
+
+        let parsed: Filter = parse_query("ids[9]=3&ids[0]=1&tags[]=red", &options)?;
+
+        assert_eq!(parsed.ids, vec![1, 3]);
+        assert_eq!(parsed.tags, HashSet::from(["red".to_string()]));
+        Ok(())
+    }
+}
+
+mod infer_scalars_tests {
     use super::*;
 
-    #[derive(Debug, Serialize)]
-    enum UnsupportedVariant {
-        Tuple(String, String),
+    #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+    struct Paging {
+        page: u32,
+        per_page: u8,
+        descending: bool,
+    }
+
+    #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+    struct Listing {
+        query: String,
+        #[serde(flatten)]
+        paging: Paging,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[serde(untagged)]
+    enum Identifier {
+        Numeric(u64),
+        Ratio(f64),
+        Named(String),
+    }
+
+    impl Default for Identifier {
+        fn default() -> Self {
+            Identifier::Named(String::new())
+        }
+    }
+
+    #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+    struct Lookup {
+        id: Identifier,
+        fallback: Identifier,
+        weight: Identifier,
     }
 
+    fn infer_options() -> ParseOptions {
+        build_parse_options(|builder| builder.infer_scalars(true))
+    }
+
+    #[test]
+    fn should_roundtrip_flattened_numeric_fields_when_infer_scalars_is_enabled()
+    -> Result<(), Box<dyn Error>> {
+        let listing = Listing {
+            query: "rust".into(),
+            paging: Paging {
+                page: 3,
+                per_page: 50,
+                descending: true,
+            },
+        };
+
+        let encoded = stringify(&listing)?;
+        let reparsed: Listing = parse_query(&encoded, &infer_options())?;
+
+        assert_eq!(reparsed, listing);
+        Ok(())
+    }
+
+    #[test]
+    fn should_roundtrip_untagged_numeric_variants_when_infer_scalars_is_enabled()
+    -> Result<(), Box<dyn Error>> {
+        let lookup = Lookup {
+            id: Identifier::Numeric(42),
+            fallback: Identifier::Named("guest".into()),
+            weight: Identifier::Ratio(0.5),
+        };
+
+        let encoded = stringify(&lookup)?;
+        let reparsed: Lookup = parse_query(&encoded, &infer_options())?;
+
+        assert_eq!(reparsed, lookup);
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_flattened_numeric_fields_when_infer_scalars_is_disabled() {
+        let message =
+            parse_serde_error_message::<Listing>("query=rust&page=3&per_page=50&descending=true");
+
+        assert!(
+            message.contains("invalid type: string"),
+            "unexpected flatten error message: {message}"
+        );
+    }
+}
+
+mod stringify_error_tests {
+    use super::*;
+
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
     struct UnitKey;
 
@@ -825,18 +1195,6 @@ mod stringify_error_tests {
         }
     }
 
-    #[test]
-    fn should_return_error_when_tuple_variant_stringified() {
-        let value = UnsupportedVariant::Tuple("lhs".into(), "rhs".into());
-
-        asserts::assert_err_matches!(
-            stringify(&value),
-            StringifyError::Serialize(_) => |message| {
-                assert!(message.contains("tuple variant"), "unexpected serialize error: {message}");
-            }
-        );
-    }
-
     #[test]
     fn should_report_error_when_map_key_not_string() {
         let mut map = BTreeMap::new();
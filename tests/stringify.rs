@@ -8,12 +8,12 @@ mod json;
 mod serde_helpers;
 
 use api::{
-    build_parse_options, build_stringify_options, parse_default, stringify_default,
+    build_parse_options, build_stringify_options, parse_default, parse_query, stringify_default,
     stringify_with_options,
 };
 use asserts::assert_str_path;
 use bunner_qs_rs::stringify::StringifyError;
-use bunner_qs_rs::{QsStringifyError, StringifyOptions};
+use bunner_qs_rs::{ArrayFormat, DuplicateKeyBehavior, QsStringifyError, StringifyOptions};
 use json::json_from_pairs;
 use serde::Serialize;
 use serde::ser::{SerializeMap, Serializer};
@@ -372,3 +372,258 @@ fn should_preserve_none_placeholders_when_sequence_contains_gaps_then_preserve_g
     let encoded = stringify_default(&payload).expect("sequence placeholders should be preserved");
     assert_eq!(encoded, "tags%5B0%5D=zero&tags%5B1%5D=&tags%5B2%5D=two");
 }
+
+mod array_format_tests {
+    use super::*;
+
+    fn stringify_with_format(value: &Value, format: ArrayFormat) -> String {
+        let options = build_stringify_options(|builder| builder.array_format(format))
+            .expect(STRINGIFY_BUILD_OK);
+        stringify_with_options(value, &options).expect("array format should stringify")
+    }
+
+    #[test]
+    fn should_emit_indexed_keys_when_using_default_format_then_keep_indices() {
+        let value = json!({ "a": ["1", "2"] });
+
+        assert_eq!(
+            stringify_with_format(&value, ArrayFormat::Indices),
+            "a%5B0%5D=1&a%5B1%5D=2"
+        );
+    }
+
+    #[test]
+    fn should_emit_empty_brackets_when_using_brackets_format_then_drop_indices() {
+        let value = json!({ "a": ["1", "2"] });
+
+        assert_eq!(
+            stringify_with_format(&value, ArrayFormat::Brackets),
+            "a%5B%5D=1&a%5B%5D=2"
+        );
+    }
+
+    #[test]
+    fn should_repeat_key_when_using_repeat_format_then_emit_one_pair_per_item() {
+        let value = json!({ "a": ["1", "2"] });
+
+        assert_eq!(
+            stringify_with_format(&value, ArrayFormat::Repeat),
+            "a=1&a=2"
+        );
+    }
+
+    #[test]
+    fn should_join_values_when_using_comma_format_then_emit_single_pair() {
+        let value = json!({ "a": ["1", "2"], "b": "x,y" });
+
+        assert_eq!(
+            stringify_with_format(&value, ArrayFormat::Comma),
+            "a=1,2&b=x%2Cy"
+        );
+    }
+
+    #[test]
+    fn should_keep_nested_object_keys_when_using_brackets_format_then_emit_bracketed_children() {
+        let value = json!({ "users": [{ "name": "kim" }, { "name": "lee" }] });
+
+        assert_eq!(
+            stringify_with_format(&value, ArrayFormat::Brackets),
+            "users%5B%5D%5Bname%5D=kim&users%5B%5D%5Bname%5D=lee"
+        );
+    }
+
+    #[test]
+    fn should_roundtrip_repeated_keys_when_parsing_with_combine_then_restore_sequence() {
+        let value = json!({ "a": ["1", "2", "3"] });
+        let encoded = stringify_with_format(&value, ArrayFormat::Repeat);
+        let options =
+            build_parse_options(|builder| builder.duplicate_keys(DuplicateKeyBehavior::Combine))
+                .expect("parse options builder should succeed");
+
+        let reparsed: Value =
+            parse_query(&encoded, &options).expect("repeated keys should combine");
+
+        assert_eq!(reparsed, value);
+    }
+}
+
+mod encoding_profile_tests {
+    use super::*;
+    use bunner_qs_rs::EncodingProfile;
+
+    fn stringify_with_profile(profile: EncodingProfile) -> String {
+        let options = StringifyOptions::new().encoding_profile(profile);
+        let value = json!({ "q": "a b", "path": "/v1:list@main", "tags": "x,y", "note": "it's" });
+        stringify_with_options(&value, &options).expect("stringify should succeed")
+    }
+
+    #[test]
+    fn should_keep_default_encoding_without_profile() {
+        assert_eq!(
+            stringify_with_profile(EncodingProfile::Component),
+            "note=it's&path=%2Fv1%3Alist%40main&q=a%20b&tags=x%2Cy"
+        );
+    }
+
+    #[test]
+    fn should_leave_query_safe_characters_with_rfc3986_profile() {
+        assert_eq!(
+            stringify_with_profile(EncodingProfile::Rfc3986),
+            "note=it's&path=/v1:list@main&q=a%20b&tags=x,y"
+        );
+    }
+
+    #[test]
+    fn should_use_plus_and_escape_reserved_with_rfc1738_profile() {
+        assert_eq!(
+            stringify_with_profile(EncodingProfile::Rfc1738),
+            "note=it's&path=%2Fv1%3Alist%40main&q=a+b&tags=x,y"
+        );
+    }
+
+    #[test]
+    fn should_match_browser_form_encoding_with_whatwg_profile() {
+        assert_eq!(
+            stringify_with_profile(EncodingProfile::Whatwg),
+            "note=it%27s&path=%2Fv1%3Alist%40main&q=a+b&tags=x%2Cy"
+        );
+    }
+
+    #[test]
+    fn should_keep_comma_joined_items_distinct_with_rfc3986_profile() {
+        let options = StringifyOptions::new()
+            .encoding_profile(EncodingProfile::Rfc3986)
+            .array_format(ArrayFormat::Comma);
+        let value = json!({ "tags": ["a,b", "c"] });
+
+        let encoded = stringify_with_options(&value, &options).expect("stringify should succeed");
+
+        assert_eq!(encoded, "tags=a%2Cb,c");
+    }
+
+    #[test]
+    fn should_roundtrip_through_parser_with_every_profile() {
+        let value = json!({ "q": "a b+c", "path": "/x:y@z?w", "filter": { "name": "~kim's" } });
+        for profile in [
+            EncodingProfile::Component,
+            EncodingProfile::Rfc3986,
+            EncodingProfile::Rfc1738,
+            EncodingProfile::Whatwg,
+        ] {
+            let options = StringifyOptions::new().encoding_profile(profile);
+            let encoded =
+                stringify_with_options(&value, &options).expect("stringify should succeed");
+            let parse_options =
+                build_parse_options(|builder| builder.space_as_plus(options.space_as_plus))
+                    .expect("parse options builder should succeed");
+
+            let reparsed: Value =
+                parse_query(&encoded, &parse_options).expect("profile output should parse");
+
+            assert_eq!(reparsed, value, "{profile:?}");
+        }
+    }
+}
+
+mod encoder_hook_tests {
+    use super::*;
+    use bunner_qs_rs::{ComponentKind, EncodeContext, Encoder};
+
+    #[derive(Debug)]
+    struct SignedValues;
+
+    impl Encoder for SignedValues {
+        fn encode(&self, component: &str, context: &EncodeContext<'_>, output: &mut String) {
+            match context.kind() {
+                ComponentKind::Value if context.key_path() == "sig" => output.push_str(component),
+                ComponentKind::Value => {
+                    for byte in component.bytes() {
+                        if byte.is_ascii_alphanumeric() {
+                            output.push(char::from(byte));
+                        } else {
+                            output.push_str(&format!("%{byte:02X}"));
+                        }
+                    }
+                }
+                ComponentKind::Key => context.encode_default(component, output),
+            }
+        }
+    }
+
+    #[test]
+    fn should_route_components_through_encoder_hook_when_installed() {
+        let options = StringifyOptions::new().encoder(SignedValues);
+        let value = json!({ "sig": "ab%2Fcd==", "filter": { "q": "a-b_c" } });
+
+        let encoded = stringify_with_options(&value, &options).expect("stringify should succeed");
+
+        assert_eq!(encoded, "filter%5Bq%5D=a%2Db%5Fc&sig=ab%2Fcd==");
+    }
+}
+
+mod literal_brackets_tests {
+    use super::*;
+
+    fn stringify_literal(
+        value: &Value,
+        configure: fn(StringifyOptions) -> StringifyOptions,
+    ) -> String {
+        let options = build_stringify_options(|builder| configure(builder.literal_brackets(true)))
+            .expect(STRINGIFY_BUILD_OK);
+        stringify_with_options(value, &options).expect("literal brackets should stringify")
+    }
+
+    #[test]
+    fn should_leave_structural_brackets_unencoded_when_literal_brackets_enabled_then_emit_readable_keys()
+     {
+        let map = build_nested_user_value();
+
+        let encoded = stringify_literal(&map, |options| options);
+
+        assert_eq!(
+            encoded,
+            "user[address][city]=Seoul&user[address][postal]=04524&user[hobbies][0]=tea&user[hobbies][1]=hiking&user[name]=Jane"
+        );
+    }
+
+    #[test]
+    fn should_encode_brackets_inside_key_names_when_literal_brackets_enabled_then_keep_segments_distinct()
+     {
+        let value = json!({ "a[b]": { "c d": "x&y" } });
+
+        let encoded = stringify_literal(&value, |options| options);
+
+        assert_eq!(encoded, "a%5Bb%5D[c%20d]=x%26y");
+    }
+
+    #[test]
+    fn should_leave_empty_brackets_unencoded_when_combined_with_brackets_format_then_emit_php_style_keys()
+     {
+        let value = json!({ "ids": ["1", "2"] });
+
+        let encoded = stringify_literal(&value, |options| {
+            options.array_format(ArrayFormat::Brackets)
+        });
+
+        assert_eq!(encoded, "ids[]=1&ids[]=2");
+    }
+
+    #[test]
+    fn should_leave_dots_and_brackets_unencoded_when_combined_with_allow_dots_then_mix_notations() {
+        let value = json!({ "filter": { "tags": ["a"] } });
+
+        let encoded = stringify_literal(&value, |options| options.allow_dots(true));
+
+        assert_eq!(encoded, "filter.tags[0]=a");
+    }
+
+    #[test]
+    fn should_roundtrip_when_parsing_literal_bracket_output_then_restore_nested_structure() {
+        let map = build_nested_user_value();
+        let encoded = stringify_literal(&map, |options| options);
+
+        let reparsed: Value = parse_default(&encoded).expect("literal brackets should parse");
+
+        assert_eq!(reparsed, map);
+    }
+}